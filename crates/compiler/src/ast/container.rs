@@ -0,0 +1,39 @@
+use std::fmt;
+
+use codemap::Span;
+
+use crate::{ast::CssStmt, error::SassResult, lexer::Lexer, parse::AtRulePreludeParser};
+
+#[derive(Debug, Clone)]
+pub(crate) struct ContainerRule {
+    pub query: Vec<ContainerQuery>,
+    pub body: Vec<CssStmt>,
+    pub span: Span,
+    /// The number of style rules this rule bubbled out of
+    pub depth: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct ContainerQuery {
+    pub name: Option<String>,
+    pub condition: Option<String>,
+}
+
+impl ContainerQuery {
+    pub fn parse_list(list: &str, span: Span) -> SassResult<Vec<Self>> {
+        let toks = Lexer::new_from_string(list, span);
+
+        AtRulePreludeParser::new(toks).parse_container_queries()
+    }
+}
+
+impl fmt::Display for ContainerQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.name, &self.condition) {
+            (Some(name), Some(condition)) => write!(f, "{} {}", name, condition),
+            (Some(name), None) => f.write_str(name),
+            (None, Some(condition)) => f.write_str(condition),
+            (None, None) => Ok(()),
+        }
+    }
+}
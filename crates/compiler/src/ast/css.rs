@@ -1,8 +1,10 @@
+use std::mem;
+
 use codemap::Span;
 
-use crate::selector::ExtendedSelector;
+use crate::selector::{ExtendedSelector, SelectorList};
 
-use super::{MediaRule, Style, UnknownAtRule};
+use super::{ContainerRule, LayerRule, MediaRule, ScopeRule, Style, UnknownAtRule};
 
 #[derive(Debug, Clone)]
 pub(crate) enum CssStmt {
@@ -10,17 +12,36 @@ pub(crate) enum CssStmt {
         selector: ExtendedSelector,
         body: Vec<Self>,
         is_group_end: bool,
+        /// Set when this style rule is kept nested within its parent style
+        /// rule rather than being flattened
+        nested_selector: Option<Box<NestedSelector>>,
+        /// The number of style rules this rule was nested within in the
+        /// source, used to indent it in the nested output style
+        depth: usize,
     },
     Style(Style),
     Media(MediaRule, bool),
     UnknownAtRule(UnknownAtRule, bool),
     Supports(SupportsRule, bool),
+    Layer(LayerRule, bool),
+    Container(ContainerRule, bool),
+    Scope(ScopeRule, bool),
     Comment(String, Span),
     KeyframesRuleSet(KeyframesRuleSet),
     /// A plain import such as `@import "foo.css";` or
     /// `@import url(https://fonts.google.com/foo?bar);`
-    // todo: named fields, 0: url, 1: modifiers
-    Import(String, Option<String>),
+    // todo: named fields, 0: url, 1: modifiers, 2: span
+    Import(String, Option<String>, Span),
+}
+
+/// The selector of a style rule that is written nested within its parent
+#[derive(Debug, Clone)]
+pub(crate) struct NestedSelector {
+    /// The selector as written, relative to the parent style rule
+    pub selector: SelectorList,
+
+    /// The fully resolved selector, before any `@extend`s were applied to it
+    pub resolved: SelectorList,
 }
 
 impl CssStmt {
@@ -33,11 +54,14 @@ impl CssStmt {
             CssStmt::Media(_, is_group_end)
             | CssStmt::UnknownAtRule(_, is_group_end)
             | CssStmt::Supports(_, is_group_end)
+            | CssStmt::Layer(_, is_group_end)
+            | CssStmt::Container(_, is_group_end)
+            | CssStmt::Scope(_, is_group_end)
             | CssStmt::RuleSet { is_group_end, .. } => *is_group_end = true,
             CssStmt::Style(_)
             | CssStmt::Comment(_, _)
             | CssStmt::KeyframesRuleSet(_)
-            | CssStmt::Import(_, _) => {}
+            | CssStmt::Import(..) => {}
         }
     }
 
@@ -46,6 +70,9 @@ impl CssStmt {
             CssStmt::Media(_, is_group_end)
             | CssStmt::UnknownAtRule(_, is_group_end)
             | CssStmt::Supports(_, is_group_end)
+            | CssStmt::Layer(_, is_group_end)
+            | CssStmt::Container(_, is_group_end)
+            | CssStmt::Scope(_, is_group_end)
             | CssStmt::RuleSet { is_group_end, .. } => *is_group_end,
             _ => false,
         }
@@ -62,26 +89,156 @@ impl CssStmt {
             CssStmt::Supports(supports_rule, ..) => {
                 supports_rule.body.iter().all(CssStmt::is_invisible)
             }
+            // An empty `@layer` block still establishes the layer's position
+            // in the cascade
+            CssStmt::Layer(..) => false,
+            CssStmt::Container(container_rule, ..) => {
+                container_rule.body.iter().all(CssStmt::is_invisible)
+            }
+            CssStmt::Scope(scope_rule, ..) => scope_rule.body.iter().all(CssStmt::is_invisible),
             CssStmt::KeyframesRuleSet(kf) => kf.body.iter().all(CssStmt::is_invisible),
         }
     }
 
+    /// Removes the loud comments that `keep` returns `false` for, including
+    /// those nested within other statements
+    pub fn retain_comments(stmts: Vec<Self>, keep: &impl Fn(&str) -> bool) -> Vec<Self> {
+        stmts
+            .into_iter()
+            .filter(|stmt| !matches!(stmt, CssStmt::Comment(comment, _) if !keep(comment)))
+            .map(|mut stmt| {
+                if let Some(body) = stmt.body_mut() {
+                    *body = Self::retain_comments(mem::take(body), keep);
+                }
+
+                stmt
+            })
+            .collect()
+    }
+
+    /// Moves nested style rules whose selectors were changed by `@extend` out
+    /// of their enclosing style rules and writes them with their full
+    /// selector, since CSS nesting can't express the result of an extension
+    pub fn unnest_extended_rules(stmts: Vec<Self>) -> Vec<Self> {
+        let (stmts, escaped) = Self::unnest_children(stmts, false);
+        debug_assert!(escaped.is_empty());
+        stmts
+    }
+
+    fn unnest_children(stmts: Vec<Self>, in_style_rule: bool) -> (Vec<Self>, Vec<Self>) {
+        let mut kept = Vec::new();
+        let mut escaped = Vec::new();
+
+        for stmt in stmts {
+            let (mut stmt_kept, mut stmt_escaped) = stmt.unnest_extended(in_style_rule);
+            kept.append(&mut stmt_kept);
+            escaped.append(&mut stmt_escaped);
+        }
+
+        (kept, escaped)
+    }
+
+    /// Returns the statements that take the place of this one, along with the
+    /// statements that must be moved out of the enclosing style rule
+    fn unnest_extended(mut self, in_style_rule: bool) -> (Vec<Self>, Vec<Self>) {
+        if let CssStmt::RuleSet {
+            selector,
+            body,
+            mut is_group_end,
+            mut nested_selector,
+            depth,
+        } = self
+        {
+            let (body, mut escaped) = Self::unnest_children(body, true);
+
+            let was_extended = nested_selector
+                .as_ref()
+                .is_some_and(|nested| *selector.as_selector_list() != nested.resolved);
+
+            if was_extended {
+                nested_selector = None;
+            }
+
+            if !in_style_rule && is_group_end && !escaped.is_empty() {
+                is_group_end = false;
+                escaped.last_mut().unwrap().set_group_end();
+            }
+
+            let rule = CssStmt::RuleSet {
+                selector,
+                body,
+                is_group_end,
+                nested_selector,
+                depth,
+            };
+
+            return if !in_style_rule {
+                escaped.insert(0, rule);
+                (escaped, Vec::new())
+            } else if was_extended {
+                escaped.insert(0, rule);
+                (Vec::new(), escaped)
+            } else {
+                (vec![rule], escaped)
+            };
+        }
+
+        let body = match self.body_mut() {
+            Some(body) => mem::take(body),
+            None => return (vec![self], Vec::new()),
+        };
+
+        let (body, escaped) = Self::unnest_children(body, in_style_rule);
+        *self.body_mut().unwrap() = body;
+
+        if escaped.is_empty() {
+            return (vec![self], escaped);
+        }
+
+        // Rules that escape an at-rule nested within a style rule keep the
+        // at-rule's condition
+        let mut wrapper = self.copy_without_children();
+        *wrapper.body_mut().unwrap() = escaped;
+
+        (vec![self], vec![wrapper])
+    }
+
+    fn body_mut(&mut self) -> Option<&mut Vec<Self>> {
+        match self {
+            CssStmt::RuleSet { body, .. } => Some(body),
+            CssStmt::Media(media, ..) => Some(&mut media.body),
+            CssStmt::UnknownAtRule(at_rule, ..) => Some(&mut at_rule.body),
+            CssStmt::Supports(supports, ..) => Some(&mut supports.body),
+            CssStmt::Layer(layer, ..) => Some(&mut layer.body),
+            CssStmt::Container(container, ..) => Some(&mut container.body),
+            CssStmt::Scope(scope, ..) => Some(&mut scope.body),
+            CssStmt::KeyframesRuleSet(keyframes) => Some(&mut keyframes.body),
+            CssStmt::Style(..) | CssStmt::Comment(..) | CssStmt::Import(..) => None,
+        }
+    }
+
     pub fn copy_without_children(&self) -> Self {
         match self {
             CssStmt::RuleSet {
                 selector,
                 is_group_end,
+                nested_selector,
+                depth,
                 ..
             } => CssStmt::RuleSet {
                 selector: selector.clone(),
                 body: Vec::new(),
                 is_group_end: *is_group_end,
+                nested_selector: nested_selector.clone(),
+                depth: *depth,
             },
             CssStmt::Style(..) | CssStmt::Comment(..) | CssStmt::Import(..) => unreachable!(),
             CssStmt::Media(media, is_group_end) => CssStmt::Media(
                 MediaRule {
                     query: media.query.clone(),
                     body: Vec::new(),
+                    span: media.span,
+                    depth: media.depth,
                 },
                 *is_group_end,
             ),
@@ -91,6 +248,8 @@ impl CssStmt {
                     params: at_rule.params.clone(),
                     body: Vec::new(),
                     has_body: at_rule.has_body,
+                    span: at_rule.span,
+                    depth: at_rule.depth,
                 },
                 *is_group_end,
             ),
@@ -98,12 +257,43 @@ impl CssStmt {
                 SupportsRule {
                     params: supports.params.clone(),
                     body: Vec::new(),
+                    span: supports.span,
+                    depth: supports.depth,
+                },
+                *is_group_end,
+            ),
+            CssStmt::Layer(layer, is_group_end) => CssStmt::Layer(
+                LayerRule {
+                    names: layer.names.clone(),
+                    body: Vec::new(),
+                    has_body: layer.has_body,
+                    span: layer.span,
+                    depth: layer.depth,
+                },
+                *is_group_end,
+            ),
+            CssStmt::Container(container, is_group_end) => CssStmt::Container(
+                ContainerRule {
+                    query: container.query.clone(),
+                    body: Vec::new(),
+                    span: container.span,
+                    depth: container.depth,
+                },
+                *is_group_end,
+            ),
+            CssStmt::Scope(scope, is_group_end) => CssStmt::Scope(
+                ScopeRule {
+                    prelude: scope.prelude.clone(),
+                    body: Vec::new(),
+                    span: scope.span,
+                    depth: scope.depth,
                 },
                 *is_group_end,
             ),
             CssStmt::KeyframesRuleSet(keyframes) => CssStmt::KeyframesRuleSet(KeyframesRuleSet {
                 selector: keyframes.selector.clone(),
                 body: Vec::new(),
+                span: keyframes.span,
             }),
         }
     }
@@ -113,6 +303,7 @@ impl CssStmt {
 pub(crate) struct KeyframesRuleSet {
     pub selector: Vec<KeyframesSelector>,
     pub body: Vec<CssStmt>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -126,4 +317,7 @@ pub(crate) enum KeyframesSelector {
 pub(crate) struct SupportsRule {
     pub params: String,
     pub body: Vec<CssStmt>,
+    pub span: Span,
+    /// The number of style rules this rule bubbled out of
+    pub depth: usize,
 }
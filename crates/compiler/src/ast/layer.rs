@@ -0,0 +1,34 @@
+use codemap::Span;
+
+use crate::{ast::CssStmt, error::SassResult, lexer::Lexer, parse::AtRulePreludeParser};
+
+#[derive(Debug, Clone)]
+pub(crate) struct LayerRule {
+    /// Dot-separated layer names. Empty for an anonymous layer block
+    pub names: Vec<String>,
+    pub body: Vec<CssStmt>,
+
+    /// Whether this is a block (`@layer a {}`) as opposed to a statement
+    /// (`@layer a, b;`)
+    pub has_body: bool,
+    pub span: Span,
+    /// The number of style rules this rule bubbled out of
+    pub depth: usize,
+}
+
+impl LayerRule {
+    pub fn parse_names(names: &str, span: Span) -> SassResult<Vec<String>> {
+        let toks = Lexer::new_from_string(names, span);
+
+        AtRulePreludeParser::new(toks).parse_layer_names()
+    }
+
+    /// The fully qualified name of this layer, if it is a block declaring
+    /// exactly one name
+    pub fn block_name(&self) -> Option<&str> {
+        match self.names.as_slice() {
+            [name] if self.has_body => Some(name),
+            _ => None,
+        }
+    }
+}
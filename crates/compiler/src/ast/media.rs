@@ -1,4 +1,7 @@
-use std::fmt::{self, Write};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Write},
+};
 
 use codemap::Span;
 
@@ -8,16 +11,413 @@ use crate::{ast::CssStmt, error::SassResult, lexer::Lexer, parse::MediaQueryPars
 pub(crate) struct MediaRule {
     pub query: Vec<MediaQuery>,
     pub body: Vec<CssStmt>,
+    pub span: Span,
+    /// The number of style rules this rule bubbled out of
+    pub depth: usize,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub(crate) struct MediaQuery {
     pub modifier: Option<String>,
     pub media_type: Option<String>,
-    pub conditions: Vec<String>,
+    pub conditions: Vec<MediaCondition>,
     pub conjunction: bool,
 }
 
+/// A single `<media-in-parens>` within a media query, such as `(color)`,
+/// `(width >= 600px)` or `((hover) or (pointer: fine))`
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) enum MediaCondition {
+    /// A media feature that isn't in range syntax, such as `(color)` or
+    /// `(min-width: 100px)`, or any other parenthesized text that we don't
+    /// understand. This includes the parentheses
+    Feature(String),
+
+    /// A media feature in range syntax, such as `(width >= 600px)` or
+    /// `(400px < width < 800px)`
+    Range(MediaRange),
+
+    /// `not <media-in-parens>`
+    Not(Box<MediaCondition>),
+
+    /// Conditions joined by `and`
+    And(Vec<MediaCondition>),
+
+    /// Conditions joined by `or`
+    Or(Vec<MediaCondition>),
+}
+
+impl MediaCondition {
+    /// Parses the contents of a pair of parentheses that don't contain a
+    /// nested condition, falling back to an opaque feature if they aren't in
+    /// range syntax
+    pub fn from_feature(contents: &str) -> Self {
+        match MediaRange::parse(contents) {
+            Some(range) => Self::Range(range),
+            None => Self::Feature(format!("({})", contents)),
+        }
+    }
+
+    /// Writes this condition as it appears within a list of conditions, adding
+    /// parentheses around conditions that combine others
+    fn write_in_parens(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Feature(..) | Self::Range(..) => write!(f, "{}", self),
+            Self::Not(..) | Self::And(..) | Self::Or(..) => write!(f, "({})", self),
+        }
+    }
+
+    fn write_sequence(
+        conditions: &[Self],
+        operator: &str,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        for (idx, condition) in conditions.iter().enumerate() {
+            if idx != 0 {
+                write!(f, " {} ", operator)?;
+            }
+
+            condition.write_in_parens(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for MediaCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Feature(feature) => f.write_str(feature),
+            Self::Range(range) => write!(f, "{}", range),
+            Self::Not(condition) => {
+                f.write_str("not ")?;
+                condition.write_in_parens(f)
+            }
+            Self::And(conditions) => Self::write_sequence(conditions, "and", f),
+            Self::Or(conditions) => Self::write_sequence(conditions, "or", f),
+        }
+    }
+}
+
+/// A media feature in range syntax. A feature compared with `=` has equal,
+/// inclusive lower and upper bounds
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct MediaRange {
+    pub name: String,
+    pub lower: Option<RangeBound>,
+    pub upper: Option<RangeBound>,
+
+    /// Whether a range with a single comparison was written with the feature
+    /// name first, as in `(width > 1px)` rather than `(1px < width)`
+    pub name_first: bool,
+
+    /// Whether a range with two comparisons was written from its upper bound
+    /// to its lower bound, as in `(800px > width > 400px)`
+    pub descending: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct RangeBound {
+    pub value: String,
+    pub inclusive: bool,
+}
+
+impl RangeBound {
+    /// The numeric value and unit of this bound, if it's a plain number
+    fn as_number(&self) -> Option<(f64, String)> {
+        let value = self.value.trim();
+        let unit_start = value
+            .char_indices()
+            .find(|&(idx, c)| {
+                !(c.is_ascii_digit() || c == '.' || (idx == 0 && (c == '-' || c == '+')))
+            })
+            .map_or(value.len(), |(idx, _)| idx);
+
+        let (number, unit) = value.split_at(unit_start);
+
+        if !unit.chars().all(|c| c.is_ascii_alphabetic() || c == '%') {
+            return None;
+        }
+
+        Some((number.parse().ok()?, unit.to_ascii_lowercase()))
+    }
+
+    /// Compares the values of two bounds, if they're numbers with the same unit
+    fn compare(&self, other: &Self) -> Option<Ordering> {
+        let (this, this_unit) = self.as_number()?;
+        let (other, other_unit) = other.as_number()?;
+
+        if this_unit != other_unit {
+            return None;
+        }
+
+        this.partial_cmp(&other)
+    }
+}
+
+impl MediaRange {
+    fn parse(contents: &str) -> Option<Self> {
+        if contents.contains(':') {
+            return None;
+        }
+
+        let parts = split_comparisons(contents)?;
+
+        match parts.as_slice() {
+            [(left, None), (right, Some(op))] => {
+                let (left, right) = (left.trim(), right.trim());
+
+                if is_feature_name(left) && !is_feature_name(right) {
+                    let (lower, upper) = Self::bounds(op, right);
+
+                    Some(Self {
+                        name: left.to_owned(),
+                        lower,
+                        upper,
+                        name_first: true,
+                        descending: false,
+                    })
+                } else if is_feature_name(right) && !is_feature_name(left) {
+                    // `1px < width` is equivalent to `width > 1px`
+                    let flipped = match *op {
+                        "<" => ">",
+                        "<=" => ">=",
+                        ">" => "<",
+                        ">=" => "<=",
+                        op => op,
+                    };
+
+                    let (lower, upper) = Self::bounds(flipped, left);
+
+                    Some(Self {
+                        name: right.to_owned(),
+                        lower,
+                        upper,
+                        name_first: false,
+                        descending: false,
+                    })
+                } else {
+                    None
+                }
+            }
+            [(low, None), (name, Some(op1)), (high, Some(op2))] => {
+                let (low, name, high) = (low.trim(), name.trim(), high.trim());
+
+                if !is_feature_name(name) || is_feature_name(low) || is_feature_name(high) {
+                    return None;
+                }
+
+                let (lower, upper) = match (*op1, *op2) {
+                    ("<" | "<=", "<" | "<=") => (
+                        RangeBound {
+                            value: low.to_owned(),
+                            inclusive: *op1 == "<=",
+                        },
+                        RangeBound {
+                            value: high.to_owned(),
+                            inclusive: *op2 == "<=",
+                        },
+                    ),
+                    (">" | ">=", ">" | ">=") => (
+                        RangeBound {
+                            value: high.to_owned(),
+                            inclusive: *op2 == ">=",
+                        },
+                        RangeBound {
+                            value: low.to_owned(),
+                            inclusive: *op1 == ">=",
+                        },
+                    ),
+                    _ => return None,
+                };
+
+                Some(Self {
+                    name: name.to_owned(),
+                    lower: Some(lower),
+                    upper: Some(upper),
+                    name_first: false,
+                    descending: op1.starts_with('>'),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// The bounds given by `<name> <op> <value>`
+    fn bounds(op: &str, value: &str) -> (Option<RangeBound>, Option<RangeBound>) {
+        let bound = |inclusive| {
+            Some(RangeBound {
+                value: value.to_owned(),
+                inclusive,
+            })
+        };
+
+        match op {
+            "<" => (None, bound(false)),
+            "<=" => (None, bound(true)),
+            ">" => (bound(false), None),
+            ">=" => (bound(true), None),
+            _ => (bound(true), bound(true)),
+        }
+    }
+
+    fn is_equality(&self) -> bool {
+        matches!(
+            (&self.lower, &self.upper),
+            (Some(lower), Some(upper)) if lower == upper && lower.inclusive
+        )
+    }
+
+    /// The range covered by both this and `other`, or `None` if they can't be
+    /// compared. Returns `Some(None)` if the ranges don't overlap
+    fn intersect(&self, other: &Self) -> Option<Option<Self>> {
+        if !self.name.eq_ignore_ascii_case(&other.name) {
+            return None;
+        }
+
+        // The narrower of two lower bounds is the larger one, and an exclusive
+        // bound is narrower than an inclusive one with the same value
+        let lower = match (&self.lower, &other.lower) {
+            (Some(a), Some(b)) => Some(match a.compare(b)? {
+                Ordering::Greater => a.clone(),
+                Ordering::Less => b.clone(),
+                Ordering::Equal if a.inclusive => b.clone(),
+                Ordering::Equal => a.clone(),
+            }),
+            (a, b) => a.clone().or_else(|| b.clone()),
+        };
+
+        let upper = match (&self.upper, &other.upper) {
+            (Some(a), Some(b)) => Some(match a.compare(b)? {
+                Ordering::Less => a.clone(),
+                Ordering::Greater => b.clone(),
+                Ordering::Equal if a.inclusive => b.clone(),
+                Ordering::Equal => a.clone(),
+            }),
+            (a, b) => a.clone().or_else(|| b.clone()),
+        };
+
+        if let (Some(lower), Some(upper)) = (&lower, &upper) {
+            match lower.compare(upper)? {
+                Ordering::Greater => return Some(None),
+                Ordering::Equal if !lower.inclusive || !upper.inclusive => return Some(None),
+                _ => {}
+            }
+        }
+
+        Some(Some(Self {
+            name: self.name.clone(),
+            name_first: self.name_first,
+            descending: self.descending,
+            lower,
+            upper,
+        }))
+    }
+}
+
+impl fmt::Display for MediaRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = |bound: &RangeBound| if bound.inclusive { "<=" } else { "<" };
+        let reversed_op = |bound: &RangeBound| if bound.inclusive { ">=" } else { ">" };
+
+        if self.is_equality() {
+            let value = &self.lower.as_ref().unwrap().value;
+
+            return if self.name_first {
+                write!(f, "({} = {})", self.name, value)
+            } else {
+                write!(f, "({} = {})", value, self.name)
+            };
+        }
+
+        match (&self.lower, &self.upper) {
+            (Some(lower), Some(upper)) if self.descending => write!(
+                f,
+                "({} {} {} {} {})",
+                upper.value,
+                reversed_op(upper),
+                self.name,
+                reversed_op(lower),
+                lower.value
+            ),
+            (Some(lower), Some(upper)) => write!(
+                f,
+                "({} {} {} {} {})",
+                lower.value,
+                op(lower),
+                self.name,
+                op(upper),
+                upper.value
+            ),
+            (Some(lower), None) if self.name_first => {
+                write!(f, "({} {} {})", self.name, reversed_op(lower), lower.value)
+            }
+            (Some(lower), None) => write!(f, "({} {} {})", lower.value, op(lower), self.name),
+            (None, Some(upper)) if self.name_first => {
+                write!(f, "({} {} {})", self.name, op(upper), upper.value)
+            }
+            (None, Some(upper)) => {
+                write!(f, "({} {} {})", upper.value, reversed_op(upper), self.name)
+            }
+            (None, None) => unreachable!("media ranges always have a bound"),
+        }
+    }
+}
+
+/// Splits `contents` on the comparison operators outside of any parentheses
+/// or quotes, pairing each part with the operator before it
+fn split_comparisons(contents: &str) -> Option<Vec<(&str, Option<&str>)>> {
+    let mut parts = Vec::new();
+    let mut depth = 0_usize;
+    let mut quote = None;
+    let mut start = 0;
+    let mut op = None;
+    let mut chars = contents.char_indices().peekable();
+
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '"' | '\'' if quote == Some(c) => quote = None,
+            '"' | '\'' if quote.is_none() => quote = Some(c),
+            _ if quote.is_some() => {}
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.checked_sub(1)?,
+            '<' | '>' | '=' if depth == 0 => {
+                parts.push((&contents[start..idx], op));
+
+                let end = if c != '=' && matches!(chars.peek(), Some((_, '='))) {
+                    chars.next();
+                    idx + 2
+                } else {
+                    idx + 1
+                };
+
+                op = Some(&contents[idx..end]);
+                start = end;
+            }
+            _ => {}
+        }
+    }
+
+    parts.push((&contents[start..], op));
+
+    Some(parts)
+}
+
+/// Whether `text` is a lone identifier that can name a media feature
+fn is_feature_name(text: &str) -> bool {
+    let mut chars = text.chars();
+
+    let starts_identifier = match chars.next() {
+        Some('-') => {
+            matches!(chars.clone().next(), Some(c) if c.is_ascii_alphabetic() || c == '-' || c == '_')
+        }
+        Some(c) => c.is_ascii_alphabetic() || c == '_',
+        None => false,
+    };
+
+    starts_identifier && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
 impl MediaQuery {
     pub fn matches_all_types(&self) -> bool {
         self.media_type.is_none()
@@ -28,7 +428,7 @@ impl MediaQuery {
     }
 
     pub fn condition(
-        conditions: Vec<String>,
+        conditions: Vec<MediaCondition>,
         // default=true
         conjunction: bool,
     ) -> Self {
@@ -43,7 +443,7 @@ impl MediaQuery {
     pub fn media_type(
         media_type: Option<String>,
         modifier: Option<String>,
-        conditions: Option<Vec<String>>,
+        conditions: Option<Vec<MediaCondition>>,
     ) -> Self {
         Self {
             modifier,
@@ -53,16 +453,115 @@ impl MediaQuery {
         }
     }
 
+    /// The minimum viewport width in pixels, if this is a mobile-first query
+    /// like `(min-width: 768px)`, `screen and (min-width: 48em)` or
+    /// `(width >= 768px)` with no other conditions.
+    ///
+    /// `em` and `rem` are converted assuming the default font size of `16px`
+    pub fn min_width(&self) -> Option<f64> {
+        if self.modifier.is_some()
+            || !(self.matches_all_types()
+                || self
+                    .media_type
+                    .as_ref()
+                    .is_some_and(|t| t.eq_ignore_ascii_case("screen")))
+        {
+            return None;
+        }
+
+        let bound = match self.conditions.as_slice() {
+            [MediaCondition::Feature(feature)] => {
+                let (name, value) = feature
+                    .strip_prefix('(')?
+                    .strip_suffix(')')?
+                    .split_once(':')?;
+
+                if !name.trim().eq_ignore_ascii_case("min-width") {
+                    return None;
+                }
+
+                RangeBound {
+                    value: value.trim().to_owned(),
+                    inclusive: true,
+                }
+            }
+            [MediaCondition::Range(MediaRange {
+                name,
+                lower: Some(lower),
+                upper: None,
+                ..
+            })] if name.eq_ignore_ascii_case("width") => lower.clone(),
+            _ => return None,
+        };
+
+        let (value, unit) = bound.as_number()?;
+
+        match unit.as_str() {
+            "px" => Some(value),
+            "em" | "rem" => Some(value * 16.0),
+            "" if value == 0.0 => Some(0.0),
+            _ => None,
+        }
+    }
+
     pub fn parse_list(list: &str, span: Span) -> SassResult<Vec<Self>> {
         let toks = Lexer::new_from_string(list, span);
 
         MediaQueryParser::new(toks).parse()
     }
 
+    /// Combines two lists of conditions that must both hold, simplifying
+    /// ranges of the same feature into one. Returns `None` if the conditions
+    /// can never both hold
+    fn merge_conditions(
+        conditions1: &[MediaCondition],
+        conditions2: &[MediaCondition],
+    ) -> Option<Vec<MediaCondition>> {
+        let mut merged: Vec<MediaCondition> = Vec::new();
+
+        for condition in conditions1.iter().chain(conditions2) {
+            if let MediaCondition::Range(range) = condition {
+                let intersection =
+                    merged
+                        .iter()
+                        .enumerate()
+                        .find_map(|(idx, existing)| match existing {
+                            MediaCondition::Range(existing) => {
+                                existing.intersect(range).map(|range| (idx, range))
+                            }
+                            _ => None,
+                        });
+
+                match intersection {
+                    Some((_, None)) => return None,
+                    Some((idx, Some(range))) => {
+                        merged[idx] = MediaCondition::Range(range);
+                        continue;
+                    }
+                    None => {}
+                }
+            }
+
+            merged.push(condition.clone());
+        }
+
+        Some(merged)
+    }
+
     #[allow(clippy::if_not_else)]
     pub fn merge(&self, other: &Self) -> MediaQueryMergeResult {
+        // A query like `(a) or (b)` is equivalent to the single condition
+        // `((a) or (b))`, which can be combined with other conditions using `and`
         if !self.conjunction || !other.conjunction {
-            return MediaQueryMergeResult::Unrepresentable;
+            let as_conjunction = |query: &Self| {
+                if query.conjunction {
+                    query.clone()
+                } else {
+                    Self::condition(vec![MediaCondition::Or(query.conditions.clone())], true)
+                }
+            };
+
+            return as_conjunction(self).merge(&as_conjunction(other));
         }
 
         let this_modifier = self.modifier.as_ref().map(|m| m.to_ascii_lowercase());
@@ -71,14 +570,12 @@ impl MediaQuery {
         let other_type = other.media_type.as_ref().map(|m| m.to_ascii_lowercase());
 
         if this_type.is_none() && other_type.is_none() {
-            return MediaQueryMergeResult::Success(Self::condition(
-                self.conditions
-                    .iter()
-                    .chain(&other.conditions)
-                    .cloned()
-                    .collect(),
-                true,
-            ));
+            return match Self::merge_conditions(&self.conditions, &other.conditions) {
+                Some(conditions) => {
+                    MediaQueryMergeResult::Success(Self::condition(conditions, true))
+                }
+                None => MediaQueryMergeResult::Empty,
+            };
         }
 
         let modifier;
@@ -172,21 +669,17 @@ impl MediaQuery {
                 &other_type
             };
 
-            conditions = self
-                .conditions
-                .iter()
-                .chain(&other.conditions)
-                .cloned()
-                .collect();
+            conditions = match Self::merge_conditions(&self.conditions, &other.conditions) {
+                Some(conditions) => conditions,
+                None => return MediaQueryMergeResult::Empty,
+            };
         } else if other.matches_all_types() {
             modifier = &this_modifier;
             media_type = &this_type;
-            conditions = self
-                .conditions
-                .iter()
-                .chain(&other.conditions)
-                .cloned()
-                .collect();
+            conditions = match Self::merge_conditions(&self.conditions, &other.conditions) {
+                Some(conditions) => conditions,
+                None => return MediaQueryMergeResult::Empty,
+            };
         } else if this_type != other_type {
             return MediaQueryMergeResult::Empty;
         } else {
@@ -197,12 +690,10 @@ impl MediaQuery {
             }
 
             media_type = &this_type;
-            conditions = self
-                .conditions
-                .iter()
-                .chain(&other.conditions)
-                .cloned()
-                .collect();
+            conditions = match Self::merge_conditions(&self.conditions, &other.conditions) {
+                Some(conditions) => conditions,
+                None => return MediaQueryMergeResult::Empty,
+            };
         }
 
         MediaQueryMergeResult::Success(MediaQuery {
@@ -236,7 +727,15 @@ impl fmt::Display for MediaQuery {
             }
         }
 
-        f.write_str(&self.conditions.join(" and "))
+        // A lone negated condition doesn't need parentheses, as in
+        // `not (color)` or `screen and not (color)`
+        if let [condition @ MediaCondition::Not(..)] = self.conditions.as_slice() {
+            return write!(f, "{}", condition);
+        }
+
+        let operator = if self.conjunction { "and" } else { "or" };
+
+        MediaCondition::write_sequence(&self.conditions, operator, f)
     }
 }
 
@@ -2,6 +2,7 @@ use std::fmt;
 
 use crate::{
     ast::ArgumentResult,
+    common::Identifier,
     error::SassResult,
     evaluate::{Environment, Visitor},
 };
@@ -13,9 +14,48 @@ pub(crate) use crate::ast::AstMixin as UserDefinedMixin;
 #[derive(Clone)]
 pub(crate) enum Mixin {
     UserDefined(UserDefinedMixin, Environment),
-    Builtin(BuiltinMixin),
+    Builtin {
+        mixin: BuiltinMixin,
+        name: Identifier,
+        /// Whether the mixin may be passed a content block. Builtin mixins
+        /// that accept content can access it through [`Environment::content`]
+        accepts_content: bool,
+    },
 }
 
+impl Mixin {
+    /// Used in `inspect()` of mixin references
+    pub fn name(&self) -> Identifier {
+        match self {
+            Self::UserDefined(mixin, ..) => mixin.name,
+            Self::Builtin { name, .. } => *name,
+        }
+    }
+
+    pub fn accepts_content(&self) -> bool {
+        match self {
+            Self::UserDefined(mixin, ..) => mixin.has_content,
+            Self::Builtin {
+                accepts_content, ..
+            } => *accepts_content,
+        }
+    }
+}
+
+impl PartialEq for Mixin {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::UserDefined(mixin1, ..), Self::UserDefined(mixin2, ..)) => mixin1 == mixin2,
+            (Self::Builtin { name: name1, .. }, Self::Builtin { name: name2, .. }) => {
+                name1 == name2
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Mixin {}
+
 impl fmt::Debug for Mixin {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -26,7 +66,9 @@ impl fmt::Debug for Mixin {
                 .field("body", &u.body)
                 .field("has_content", &u.has_content)
                 .finish(),
-            Self::Builtin(..) => f.debug_struct("BuiltinMixin").finish(),
+            Self::Builtin { name, .. } => {
+                f.debug_struct("BuiltinMixin").field("name", name).finish()
+            }
         }
     }
 }
@@ -1,19 +1,25 @@
 pub(crate) use args::*;
+pub(crate) use container::*;
 pub(crate) use css::*;
 pub(crate) use expr::*;
 pub(crate) use interpolation::*;
+pub(crate) use layer::*;
 pub(crate) use media::*;
 pub(crate) use mixin::*;
+pub(crate) use scope::*;
 pub(crate) use stmt::*;
 pub(crate) use style::*;
 pub(crate) use unknown::*;
 
 mod args;
+mod container;
 mod css;
 mod expr;
 mod interpolation;
+mod layer;
 mod media;
 mod mixin;
+mod scope;
 mod stmt;
 mod style;
 mod unknown;
@@ -0,0 +1,52 @@
+use std::fmt;
+
+use codemap::Span;
+
+use crate::{ast::CssStmt, error::SassResult, lexer::Lexer, parse::AtRulePreludeParser};
+
+#[derive(Debug, Clone)]
+pub(crate) struct ScopeRule {
+    pub prelude: ScopePrelude,
+    pub body: Vec<CssStmt>,
+    pub span: Span,
+    /// The number of style rules this rule bubbled out of
+    pub depth: usize,
+}
+
+/// The `(<scope-start>) to (<scope-end>)` prelude of a `@scope` rule. Both
+/// selector lists are optional
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct ScopePrelude {
+    pub start: Option<String>,
+    pub end: Option<String>,
+}
+
+impl ScopePrelude {
+    pub fn parse(prelude: &str, span: Span) -> SassResult<Self> {
+        let toks = Lexer::new_from_string(prelude, span);
+
+        AtRulePreludeParser::new(toks).parse_scope_prelude()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start.is_none() && self.end.is_none()
+    }
+}
+
+impl fmt::Display for ScopePrelude {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(start) = &self.start {
+            write!(f, "({})", start)?;
+
+            if self.end.is_some() {
+                f.write_str(" ")?;
+            }
+        }
+
+        if let Some(end) = &self.end {
+            write!(f, "to ({})", end)?;
+        }
+
+        Ok(())
+    }
+}
@@ -165,8 +165,17 @@ pub(crate) struct AstMixin {
     pub body: Vec<AstStmt>,
     /// Whether the mixin contains a `@content` rule.
     pub has_content: bool,
+    pub span: Span,
 }
 
+impl PartialEq for AstMixin {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.span == other.span
+    }
+}
+
+impl Eq for AstMixin {}
+
 #[derive(Debug, Clone)]
 pub(crate) struct AstContentRule {
     pub args: ArgumentInvocation,
@@ -248,6 +257,9 @@ impl AtRootQuery {
             CssStmt::RuleSet { .. } => self.excludes_style_rules(),
             CssStmt::Media(..) => self.excludes_name("media"),
             CssStmt::Supports(..) => self.excludes_name("supports"),
+            CssStmt::Layer(..) => self.excludes_name("layer"),
+            CssStmt::Container(..) => self.excludes_name("container"),
+            CssStmt::Scope(..) => self.excludes_name("scope"),
             CssStmt::UnknownAtRule(rule, ..) => self.excludes_name(&rule.name.to_ascii_lowercase()),
             _ => false,
         }
@@ -300,7 +312,6 @@ pub(crate) struct ConfiguredVariable {
 #[derive(Debug, Clone)]
 pub(crate) struct Configuration {
     pub values: Arc<dyn MapView<Value = ConfiguredValue>>,
-    #[allow(unused)]
     pub original_config: Option<Arc<RefCell<Self>>>,
     pub span: Option<Span>,
 }
@@ -393,13 +404,21 @@ impl Configuration {
         self.values.is_empty()
     }
 
-    #[allow(unused)]
-    pub fn original_config(config: Arc<RefCell<Configuration>>) -> Arc<RefCell<Configuration>> {
+    pub fn original_config(config: &Arc<RefCell<Configuration>>) -> Arc<RefCell<Configuration>> {
         match (*config).borrow().original_config.as_ref() {
             Some(v) => Arc::clone(v),
-            None => Arc::clone(&config),
+            None => Arc::clone(config),
         }
     }
+
+    /// Whether or not both configurations were derived from the same original
+    /// configuration, e.g. by passing through different `@forward` rules
+    pub fn same_original(config1: &Arc<RefCell<Self>>, config2: &Arc<RefCell<Self>>) -> bool {
+        Arc::ptr_eq(
+            &Self::original_config(config1),
+            &Self::original_config(config2),
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -511,6 +530,32 @@ pub(crate) enum AstSupportsCondition {
     },
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct AstLayerRule {
+    /// `None` for an anonymous layer block
+    pub names: Option<Interpolation>,
+    pub names_span: Span,
+    /// `None` for a statement such as `@layer a, b;`
+    pub children: Option<Vec<AstStmt>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct AstContainerRule {
+    pub query: Interpolation,
+    pub query_span: Span,
+    pub children: Vec<AstStmt>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct AstScopeRule {
+    pub prelude: Interpolation,
+    pub prelude_span: Span,
+    pub children: Vec<AstStmt>,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct AstSupportsRule {
     pub condition: AstSupportsCondition,
@@ -545,6 +590,9 @@ pub(crate) enum AstStmt {
     Use(AstUseRule),
     Forward(AstForwardRule),
     Supports(AstSupportsRule),
+    Layer(AstLayerRule),
+    Container(AstContainerRule),
+    Scope(AstScopeRule),
 }
 
 #[derive(Debug, Clone)]
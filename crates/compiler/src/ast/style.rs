@@ -1,4 +1,4 @@
-use codemap::Spanned;
+use codemap::{Span, Spanned};
 
 use crate::{interner::InternedString, value::Value};
 
@@ -8,4 +8,5 @@ pub(crate) struct Style {
     pub property: InternedString,
     pub value: Box<Spanned<Value>>,
     pub declared_as_custom_property: bool,
+    pub span: Span,
 }
@@ -13,6 +13,7 @@ pub mod hwb;
 pub mod opacity;
 pub mod other;
 pub mod rgb;
+pub mod space;
 
 #[derive(Debug, Clone)]
 pub(crate) enum ParsedChannels {
@@ -37,4 +38,5 @@ pub(crate) fn declare(f: &mut GlobalFunctionMap) {
     opacity::declare(f);
     other::declare(f);
     rgb::declare(f);
+    space::declare(f);
 }
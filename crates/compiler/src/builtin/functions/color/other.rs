@@ -1,5 +1,13 @@
 use crate::{
-    builtin::{builtin_imports::*, color::angle_value},
+    builtin::{
+        builtin_imports::*,
+        color::{
+            angle_value,
+            rgb::percentage_or_unitless,
+            space::{channel_value, parse_space},
+        },
+    },
+    color::{ChannelKind, ColorSpace},
     utils::to_sentence,
     value::fuzzy_round,
 };
@@ -11,6 +19,113 @@ enum UpdateComponents {
     Scale,
 }
 
+fn assert_no_named_args(args: &ArgumentResult, span: Span) -> SassResult<()> {
+    if args.named.is_empty() {
+        return Ok(());
+    }
+
+    let argument_word = if args.named.len() == 1 {
+        "argument"
+    } else {
+        "arguments"
+    };
+
+    let argument_names = to_sentence(
+        args.named
+            .keys()
+            .map(|key| format!("${key}", key = key))
+            .collect(),
+        "or",
+    );
+
+    Err((
+        format!(
+            "No {argument_word} named {argument_names}.",
+            argument_word = argument_word,
+            argument_names = argument_names
+        ),
+        span,
+    )
+        .into())
+}
+
+/// Updates the channels of a color in a non-legacy color space, such as `lab`
+/// or `display-p3`
+fn update_in_space(
+    color: &Color,
+    space: ColorSpace,
+    args: &mut ArgumentResult,
+    visitor: &mut Visitor,
+    update: UpdateComponents,
+) -> SassResult<Color> {
+    let mut channels = color.channels_in(space);
+
+    for (idx, info) in space.channels().iter().enumerate() {
+        let value = match args.get(usize::MAX, info.name) {
+            Some(value) => value,
+            None => continue,
+        };
+
+        let span = value.span;
+        let number = value.node.assert_number_with_name(info.name, span)?;
+        let current = channels[idx].unwrap_or(0.0);
+
+        channels[idx] = Some(match update {
+            UpdateComponents::Change => channel_value(&number, idx, space, span, visitor)?,
+            UpdateComponents::Adjust => {
+                current + channel_value(&number, idx, space, span, visitor)?
+            }
+            UpdateComponents::Scale => {
+                if info.kind == ChannelKind::Hue {
+                    return Err((format!("${}: Channel isn't scalable.", info.name), span).into());
+                }
+
+                number.assert_unit(&Unit::Percent, info.name, span)?;
+                number.assert_bounds(info.name, -100.0, 100.0, span)?;
+
+                let scale = number.num.0 / 100.0;
+
+                if scale > 0.0 {
+                    current + (info.max - current) * scale
+                } else {
+                    current + (current - info.min) * scale
+                }
+            }
+        });
+    }
+
+    let mut alpha = color.alpha().0;
+
+    if let Some(value) = args.get(usize::MAX, "alpha") {
+        let span = value.span;
+        let number = value.node.assert_number_with_name("alpha", span)?;
+
+        alpha = match update {
+            UpdateComponents::Change => {
+                percentage_or_unitless(&number, 1.0, "alpha", span, visitor)?
+            }
+            UpdateComponents::Adjust => {
+                number.assert_bounds("alpha", -1.0, 1.0, span)?;
+                (alpha + number.num.0).clamp(0.0, 1.0)
+            }
+            UpdateComponents::Scale => {
+                number.assert_unit(&Unit::Percent, "alpha", span)?;
+                number.assert_bounds("alpha", -100.0, 100.0, span)?;
+
+                let scale = number.num.0 / 100.0;
+
+                if scale > 0.0 {
+                    alpha + (1.0 - alpha) * scale
+                } else {
+                    alpha + alpha * scale
+                }
+            }
+        };
+    }
+
+    Ok(Color::from_space(space, channels, Number(alpha)))
+}
+
 fn update_components(
     mut args: ArgumentResult,
     visitor: &mut Visitor,
@@ -30,6 +145,27 @@ fn update_components(
             .into());
     }
 
+    let original_space = color.space();
+    let space = match args.get(usize::MAX, "space") {
+        Some(Spanned {
+            node: Value::Null, ..
+        })
+        | None => original_space,
+        Some(space) => parse_space(space.node, "space", space.span)?,
+    };
+
+    if !space.is_legacy() {
+        let color = update_in_space(&color, space, &mut args, visitor, update)?;
+        assert_no_named_args(&args, span)?;
+        return Ok(Value::Color(Arc::new(color.to_space(original_space))));
+    }
+
+    let color = if original_space.is_legacy() {
+        color
+    } else {
+        Arc::new(color.to_space(space))
+    };
+
     let check_num = |num: Spanned<Value>,
                      name: &str,
                      mut max: f64,
@@ -111,31 +247,7 @@ fn update_components(
     let whiteness = get_arg(&mut args, "whiteness", 100.0, true, true)?;
     let blackness = get_arg(&mut args, "blackness", 100.0, true, true)?;
 
-    if !args.named.is_empty() {
-        let argument_word = if args.named.len() == 1 {
-            "argument"
-        } else {
-            "arguments"
-        };
-
-        let argument_names = to_sentence(
-            args.named
-                .keys()
-                .map(|key| format!("${key}", key = key))
-                .collect(),
-            "or",
-        );
-
-        return Err((
-            format!(
-                "No {argument_word} named {argument_names}.",
-                argument_word = argument_word,
-                argument_names = argument_names
-            ),
-            span,
-        )
-            .into());
-    }
+    assert_no_named_args(&args, span)?;
 
     let has_rgb = red.is_some() || green.is_some() || blue.is_some();
     let has_sl = saturation.is_some() || lightness.is_some();
@@ -226,6 +338,10 @@ fn update_components(
         color
     };
 
+    if !original_space.is_legacy() {
+        return Ok(Value::Color(Arc::new(color.to_space(original_space))));
+    }
+
     Ok(Value::Color(color))
 }
 
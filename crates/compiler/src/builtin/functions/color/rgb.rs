@@ -1,6 +1,6 @@
 use crate::{builtin::builtin_imports::*, serializer::inspect_number, value::fuzzy_round};
 
-use super::ParsedChannels;
+use super::{space::parse_space, ParsedChannels};
 
 pub(crate) fn function_string(
     name: &'static str,
@@ -376,7 +376,7 @@ pub(crate) fn blue(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResul
 }
 
 pub(crate) fn mix(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
-    args.max_args(3)?;
+    args.max_args(4)?;
     let color1 = args
         .get_err(0, "color1")?
         .assert_color_with_name("color1", args.span())?;
@@ -407,7 +407,29 @@ pub(crate) fn mix(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult
                 .into())
         }
     };
-    Ok(Value::Color(Arc::new(color1.mix(&color2, weight))))
+
+    match args.get(3, "space") {
+        Some(Spanned {
+            node: Value::Null, ..
+        })
+        | None => {
+            if !color1.is_legacy() || !color2.is_legacy() {
+                return Err((
+                    "$space: To use color.mix() with non-legacy colors, you must provide a $space.",
+                    args.span(),
+                )
+                    .into());
+            }
+
+            Ok(Value::Color(Arc::new(color1.mix(&color2, weight))))
+        }
+        Some(space) => {
+            let space = parse_space(space.node, "space", space.span)?;
+            Ok(Value::Color(Arc::new(
+                color1.mix_in_space(&color2, weight, space),
+            )))
+        }
+    }
 }
 
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
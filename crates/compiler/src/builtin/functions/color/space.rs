@@ -0,0 +1,454 @@
+use crate::{
+    builtin::builtin_imports::*,
+    color::{ChannelKind, ColorSpace},
+    serializer::inspect_number,
+};
+
+use super::{
+    rgb::{function_string, parse_channels, percentage_or_unitless},
+    ParsedChannels,
+};
+
+pub(crate) fn parse_space(value: Value, name: &str, span: Span) -> SassResult<ColorSpace> {
+    let (text, _) = value.assert_string_with_name(name, span)?;
+
+    ColorSpace::from_name(&text).ok_or_else(|| {
+        (
+            format!("${}: Unknown color space \"{}\".", name, text),
+            span,
+        )
+            .into()
+    })
+}
+
+fn optional_space(
+    args: &mut ArgumentResult,
+    position: usize,
+    color: &Color,
+) -> SassResult<ColorSpace> {
+    match args.get(position, "space") {
+        Some(Spanned {
+            node: Value::Null, ..
+        })
+        | None => Ok(color.space()),
+        Some(space) => parse_space(space.node, "space", space.span),
+    }
+}
+
+/// Converts a number passed for a channel to the units that channel is stored
+/// in, e.g. `50%` becomes `0.5` for `oklab` lightness
+pub(crate) fn channel_value(
+    number: &SassNumber,
+    channel: usize,
+    space: ColorSpace,
+    span: Span,
+    visitor: &mut Visitor,
+) -> SassResult<f64> {
+    let info = &space.channels()[channel];
+
+    match info.kind {
+        ChannelKind::Hue => {
+            if number.has_compatible_units(&Unit::Deg) {
+                let factor = crate::value::conversion_factor(&number.unit, &Unit::Deg).unwrap();
+                return Ok(number.num.0 * factor);
+            }
+
+            Ok(number.num.0)
+        }
+        ChannelKind::Percent if number.unit == Unit::None => Ok(number.num.0),
+        ChannelKind::Percent if number.unit == Unit::Percent => Ok(number.num.0 / 100.0 * info.max),
+        ChannelKind::Linear if number.unit == Unit::None => Ok(number.num.0),
+        ChannelKind::Linear if number.unit == Unit::Percent => {
+            Ok(number.num.0 / 100.0 * info.max.abs().max(info.min.abs()))
+        }
+        _ => Err((
+            format!(
+                "${name}: Expected {} to have no units or \"%\".",
+                inspect_number(number, visitor.options, span)?,
+                name = info.name,
+            ),
+            span,
+        )
+            .into()),
+    }
+}
+
+fn channels_to_color(
+    name: &'static str,
+    space: ColorSpace,
+    list: Vec<Value>,
+    visitor: &mut Visitor,
+    span: Span,
+) -> SassResult<Value> {
+    if list.iter().any(Value::is_special_function) {
+        let is_compressed = visitor.options.is_compressed();
+        let channels = list[..3]
+            .iter()
+            .map(|channel| channel.to_css_string(span, is_compressed))
+            .collect::<SassResult<Vec<_>>>()?
+            .join(" ");
+
+        let alpha = match list.get(3) {
+            Some(alpha) => format!(" / {}", alpha.to_css_string(span, is_compressed)?),
+            None => String::new(),
+        };
+
+        let prefix = if space.is_predefined() {
+            format!("{} ", space.name())
+        } else {
+            String::new()
+        };
+
+        return Ok(Value::String(
+            format!("{}({}{}{})", name, prefix, channels, alpha),
+            QuoteKind::None,
+        ));
+    }
+
+    let mut channels = [None; 3];
+
+    for (idx, value) in list.iter().take(3).enumerate() {
+        if matches!(value, Value::String(s, QuoteKind::None) if s.eq_ignore_ascii_case("none")) {
+            continue;
+        }
+
+        let number = value
+            .clone()
+            .assert_number_with_name(space.channels()[idx].name, span)?;
+
+        channels[idx] = Some(channel_value(&number, idx, space, span, visitor)?);
+    }
+
+    let alpha = match list.get(3) {
+        Some(alpha) => percentage_or_unitless(
+            &alpha.clone().assert_number_with_name("alpha", span)?,
+            1.0,
+            "alpha",
+            span,
+            visitor,
+        )?,
+        None => 1.0,
+    };
+
+    Ok(Value::Color(Arc::new(Color::from_space(
+        space,
+        channels,
+        Number(alpha),
+    ))))
+}
+
+fn space_function(
+    name: &'static str,
+    space: ColorSpace,
+    mut args: ArgumentResult,
+    visitor: &mut Visitor,
+) -> SassResult<Value> {
+    args.max_args(1)?;
+    let span = args.span();
+    let arg_names = space.channels().map(|channel| channel.name);
+
+    match parse_channels(
+        name,
+        &arg_names,
+        args.get_err(0, "channels")?,
+        visitor,
+        span,
+    )? {
+        ParsedChannels::String(s) => Ok(Value::String(s, QuoteKind::None)),
+        ParsedChannels::List(list) => channels_to_color(name, space, list, visitor, span),
+    }
+}
+
+pub(crate) fn lab(args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    space_function("lab", ColorSpace::Lab, args, visitor)
+}
+
+pub(crate) fn lch(args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    space_function("lch", ColorSpace::Lch, args, visitor)
+}
+
+pub(crate) fn oklab(args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    space_function("oklab", ColorSpace::Oklab, args, visitor)
+}
+
+pub(crate) fn oklch(args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    space_function("oklch", ColorSpace::Oklch, args, visitor)
+}
+
+/// `color(display-p3 1 0 0 / 0.5)`
+pub(crate) fn color(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    args.max_args(1)?;
+    let span = args.span();
+    let description = args.get_err(0, "description")?;
+
+    if description.is_var() {
+        return Ok(Value::String(
+            function_string("color", &[description], visitor, span)?,
+            QuoteKind::None,
+        ));
+    }
+
+    // separate the color space from the channels that follow it
+    let (space, channels) = match description.clone() {
+        Value::List(mut elems, ListSeparator::Slash, Brackets::None) if elems.len() == 2 => {
+            let alpha = elems.pop().unwrap();
+            let mut inner = elems.pop().unwrap().as_list();
+
+            if inner.is_empty() {
+                (Value::Null, Value::Null)
+            } else {
+                let space = inner.remove(0);
+                (
+                    space,
+                    Value::List(
+                        vec![
+                            Value::List(inner, ListSeparator::Space, Brackets::None),
+                            alpha,
+                        ],
+                        ListSeparator::Slash,
+                        Brackets::None,
+                    ),
+                )
+            }
+        }
+        Value::List(mut elems, ListSeparator::Space, Brackets::None) if !elems.is_empty() => {
+            let space = elems.remove(0);
+            (
+                space,
+                Value::List(elems, ListSeparator::Space, Brackets::None),
+            )
+        }
+        _ => (Value::Null, Value::Null),
+    };
+
+    if space.is_var() {
+        return Ok(Value::String(
+            function_string("color", &[description], visitor, span)?,
+            QuoteKind::None,
+        ));
+    }
+
+    let space = match space {
+        Value::String(text, QuoteKind::None) => match ColorSpace::from_name(&text) {
+            Some(space) if space.is_predefined() => space,
+            _ => {
+                return Err((
+                    format!("$description: Unknown color space \"{}\".", text),
+                    span,
+                )
+                    .into())
+            }
+        },
+        _ => {
+            return Err((
+                format!(
+                    "$description: Expected a color space followed by channels, got {}.",
+                    description.inspect(span)?
+                ),
+                span,
+            )
+                .into())
+        }
+    };
+
+    let arg_names = space.channels().map(|channel| channel.name);
+
+    match parse_channels("color", &arg_names, channels, visitor, span)? {
+        ParsedChannels::String(..) => Ok(Value::String(
+            function_string("color", &[description], visitor, span)?,
+            QuoteKind::None,
+        )),
+        ParsedChannels::List(list) => channels_to_color("color", space, list, visitor, span),
+    }
+}
+
+pub(crate) fn space(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    args.max_args(1)?;
+    let color = args
+        .get_err(0, "color")?
+        .assert_color_with_name("color", args.span())?;
+
+    Ok(Value::String(
+        color.space().name().to_owned(),
+        QuoteKind::None,
+    ))
+}
+
+pub(crate) fn to_space(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    args.max_args(2)?;
+    let span = args.span();
+    let color = args
+        .get_err(0, "color")?
+        .assert_color_with_name("color", span)?;
+    let space = parse_space(args.get_err(1, "space")?, "space", span)?;
+
+    Ok(Value::Color(Arc::new(color.to_space(space))))
+}
+
+pub(crate) fn is_legacy(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    args.max_args(1)?;
+    let color = args
+        .get_err(0, "color")?
+        .assert_color_with_name("color", args.span())?;
+
+    Ok(Value::bool(color.is_legacy()))
+}
+
+pub(crate) fn channel(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    args.max_args(3)?;
+    let span = args.span();
+    let color = args
+        .get_err(0, "color")?
+        .assert_color_with_name("color", span)?;
+    let (name, _) = args
+        .get_err(1, "channel")?
+        .assert_string_with_name("channel", span)?;
+    let space = optional_space(&mut args, 2, &color)?;
+
+    if name == "alpha" {
+        return Ok(Value::Dimension(SassNumber::new_unitless(color.alpha())));
+    }
+
+    let idx = match space.channel_index(&name) {
+        Some(idx) => idx,
+        None => {
+            return Err((
+                format!(
+                    "$channel: Color {} has no channel named {}.",
+                    Value::Color(Arc::clone(&color)).inspect(span)?,
+                    name
+                ),
+                span,
+            )
+                .into())
+        }
+    };
+
+    let value = color.channels_in(space)[idx].unwrap_or(0.0);
+    let info = &space.channels()[idx];
+
+    let (value, unit) = match info.kind {
+        ChannelKind::Linear => (value, Unit::None),
+        ChannelKind::Percent => (value * 100.0 / info.max, Unit::Percent),
+        ChannelKind::Hue => (value, Unit::Deg),
+    };
+
+    Ok(Value::Dimension(SassNumber {
+        num: Number(value),
+        unit,
+        as_slash: None,
+    }))
+}
+
+pub(crate) fn is_missing(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    args.max_args(2)?;
+    let span = args.span();
+    let color = args
+        .get_err(0, "color")?
+        .assert_color_with_name("color", span)?;
+    let (name, _) = args
+        .get_err(1, "channel")?
+        .assert_string_with_name("channel", span)?;
+
+    if name == "alpha" {
+        return Ok(Value::False);
+    }
+
+    match color.space().channel_index(&name) {
+        Some(idx) => Ok(Value::bool(color.is_missing(idx))),
+        None => Err((
+            format!(
+                "$channel: Color {} has no channel named {}.",
+                Value::Color(Arc::clone(&color)).inspect(span)?,
+                name
+            ),
+            span,
+        )
+            .into()),
+    }
+}
+
+pub(crate) fn is_in_gamut(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    args.max_args(2)?;
+    let color = args
+        .get_err(0, "color")?
+        .assert_color_with_name("color", args.span())?;
+    let space = optional_space(&mut args, 1, &color)?;
+
+    let channels = color
+        .channels_in(space)
+        .map(|channel| channel.unwrap_or(0.0));
+
+    Ok(Value::bool(space.is_in_gamut(channels)))
+}
+
+pub(crate) fn to_gamut(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    args.max_args(3)?;
+    let span = args.span();
+    let color = args
+        .get_err(0, "color")?
+        .assert_color_with_name("color", span)?;
+    let space = optional_space(&mut args, 1, &color)?;
+
+    let method = match args.get(2, "method") {
+        Some(Spanned {
+            node: Value::Null, ..
+        })
+        | None => {
+            return Err((
+                "$method: color.to-gamut() requires a $method argument for forwards-compatibility with changes in the CSS spec. Suggestion:\n\n$method: local-minde",
+                span,
+            )
+                .into())
+        }
+        Some(method) => method.node.assert_string_with_name("method", span)?.0,
+    };
+
+    if !space.is_bounded() {
+        return Ok(Value::Color(color));
+    }
+
+    let mapped = match method.as_str() {
+        "clip" => space.clip(
+            color
+                .channels_in(space)
+                .map(|channel| channel.unwrap_or(0.0)),
+        ),
+        "local-minde" => space.local_minde(
+            color
+                .channels_in(ColorSpace::Oklch)
+                .map(|channel| channel.unwrap_or(0.0)),
+        ),
+        _ => {
+            return Err((
+                format!("$method: Unknown gamut map method \"{}\".", method),
+                span,
+            )
+                .into())
+        }
+    };
+
+    Ok(Value::Color(Arc::new(
+        Color::from_space(space, mapped.map(Some), color.alpha()).to_space(color.space()),
+    )))
+}
+
+pub(crate) fn same(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    args.max_args(2)?;
+    let color1 = args
+        .get_err(0, "color1")?
+        .assert_color_with_name("color1", args.span())?;
+    let color2 = args
+        .get_err(1, "color2")?
+        .assert_color_with_name("color2", args.span())?;
+
+    Ok(Value::bool(color1.same(&color2)))
+}
+
+pub(crate) fn declare(f: &mut GlobalFunctionMap) {
+    f.insert("lab", Builtin::new(lab));
+    f.insert("lch", Builtin::new(lch));
+    f.insert("oklab", Builtin::new(oklab));
+    f.insert("oklch", Builtin::new(oklch));
+    f.insert("color", Builtin::new(color));
+}
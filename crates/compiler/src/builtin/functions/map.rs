@@ -1,55 +1,145 @@
 use crate::builtin::builtin_imports::*;
 
+/// Interprets `value` as a map, treating empty lists as empty maps
+fn try_map(value: &Value) -> Option<SassMap> {
+    match value {
+        Value::Map(m) => Some(m.clone()),
+        Value::List(v, ..) if v.is_empty() => Some(SassMap::new()),
+        Value::ArgList(v) if v.is_empty() => Some(SassMap::new()),
+        _ => None,
+    }
+}
+
+fn assert_map(value: Value, name: &str, span: Span) -> SassResult<SassMap> {
+    match try_map(&value) {
+        Some(map) => Ok(map),
+        None => Err((
+            format!("${}: {} is not a map.", name, value.inspect(span)?),
+            span,
+        )
+            .into()),
+    }
+}
+
+/// Updates the value at the path `keys` within `map` using `modify`, and
+/// returns the new map.
+///
+/// If any key along the path doesn't refer to a nested map, it is replaced
+/// with a new map when `add_nesting` is true. Otherwise, `map` is returned
+/// unchanged, as it is when the final key is missing. If `keys` is empty,
+/// `modify` is called with `map` itself.
+fn modify_nested_map(
+    map: SassMap,
+    keys: &[Spanned<Value>],
+    modify: impl FnOnce(Value) -> Value,
+    add_nesting: bool,
+) -> Value {
+    fn modify_nested(
+        mut map: SassMap,
+        key: &Spanned<Value>,
+        rest: &[Spanned<Value>],
+        modify: impl FnOnce(Value) -> Value,
+        add_nesting: bool,
+    ) -> SassMap {
+        let (next_key, rest) = match rest.split_first() {
+            Some(next) => next,
+            None => {
+                let old = match map.get_ref(&key.node) {
+                    Some(old) => old.clone(),
+                    None if add_nesting => Value::Null,
+                    None => return map,
+                };
+                map.insert(key.clone(), modify(old));
+                return map;
+            }
+        };
+
+        let nested = match map.get_ref(&key.node).and_then(try_map) {
+            Some(nested) => nested,
+            None if add_nesting => SassMap::new(),
+            None => return map,
+        };
+
+        let nested = modify_nested(nested, next_key, rest, modify, add_nesting);
+        map.insert(key.clone(), Value::Map(nested));
+        map
+    }
+
+    match keys.split_first() {
+        Some((key, rest)) => Value::Map(modify_nested(map, key, rest, modify, add_nesting)),
+        None => modify(Value::Map(map)),
+    }
+}
+
+/// Merges `map2` into `map1`, recursively merging values that are maps in both
+fn deep_merge_impl(map1: SassMap, map2: SassMap) -> SassMap {
+    if map1.is_empty() {
+        return map2;
+    }
+
+    if map2.is_empty() {
+        return map1;
+    }
+
+    let mut result = map1;
+
+    for (key, value) in map2 {
+        match (result.get_ref(&key.node).and_then(try_map), try_map(&value)) {
+            // merging an empty map leaves the original value untouched
+            (Some(..), Some(value_map)) if value_map.is_empty() => {}
+            (Some(result_map), Some(value_map)) => {
+                result.insert(key, Value::Map(deep_merge_impl(result_map, value_map)));
+            }
+            _ => {
+                result.insert(key, value);
+            }
+        }
+    }
+
+    result
+}
+
+/// Follows `keys` through nested maps starting at `map`, returning the
+/// innermost map and the final key, or `None` if a key along the path
+/// doesn't refer to a map
+fn resolve_nested_key(
+    mut map: SassMap,
+    mut key: Value,
+    keys: Vec<Spanned<Value>>,
+) -> Option<(SassMap, Value)> {
+    for next_key in keys {
+        map = try_map(&map.get(&key)?)?;
+        key = next_key.node;
+    }
+
+    Some((map, key))
+}
+
 pub(crate) fn map_get(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
-    args.max_args(2)?;
+    let span = args.span();
     let key = args.get_err(1, "key")?;
-    let map = match args.get_err(0, "map")? {
-        Value::Map(m) => m,
-        Value::List(v, ..) if v.is_empty() => SassMap::new(),
-        Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
-    Ok(map.get(&key).unwrap_or(Value::Null))
+    let map = assert_map(args.get_err(0, "map")?, "map", span)?;
+    let keys = args.get_variadic()?;
+
+    Ok(resolve_nested_key(map, key, keys)
+        .and_then(|(map, key)| map.get(&key))
+        .unwrap_or(Value::Null))
 }
 
 pub(crate) fn map_has_key(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
-    args.max_args(2)?;
+    let span = args.span();
     let key = args.get_err(1, "key")?;
-    let map = match args.get_err(0, "map")? {
-        Value::Map(m) => m,
-        Value::List(v, ..) if v.is_empty() => SassMap::new(),
-        Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
-    Ok(Value::bool(map.get(&key).is_some()))
+    let map = assert_map(args.get_err(0, "map")?, "map", span)?;
+    let keys = args.get_variadic()?;
+
+    Ok(Value::bool(
+        resolve_nested_key(map, key, keys).is_some_and(|(map, key)| map.get_ref(&key).is_some()),
+    ))
 }
 
 pub(crate) fn map_keys(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
     args.max_args(1)?;
-    let map = match args.get_err(0, "map")? {
-        Value::Map(m) => m,
-        Value::List(v, ..) if v.is_empty() => SassMap::new(),
-        Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
+    let map = assert_map(args.get_err(0, "map")?, "map", args.span())?;
     Ok(Value::List(
         map.keys(),
         ListSeparator::Comma,
@@ -59,18 +149,7 @@ pub(crate) fn map_keys(mut args: ArgumentResult, visitor: &mut Visitor) -> SassR
 
 pub(crate) fn map_values(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
     args.max_args(1)?;
-    let map = match args.get_err(0, "map")? {
-        Value::Map(m) => m,
-        Value::List(v, ..) if v.is_empty() => SassMap::new(),
-        Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
+    let map = assert_map(args.get_err(0, "map")?, "map", args.span())?;
     Ok(Value::List(
         map.values(),
         ListSeparator::Comma,
@@ -83,91 +162,30 @@ pub(crate) fn map_merge(mut args: ArgumentResult, visitor: &mut Visitor) -> Sass
         return Err(("Expected $args to contain a key.", args.span()).into());
     }
 
+    let span = args.span();
     let map2_position = args.len().saturating_sub(1);
 
-    let mut map1 = match args.get_err(0, "map1")? {
-        Value::Map(m) => m,
-        Value::List(v, ..) if v.is_empty() => SassMap::new(),
-        Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map1: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
-
-    let map2 = match args.get_err(map2_position, "map2")? {
-        Value::Map(m) => m,
-        Value::List(v, ..) if v.is_empty() => SassMap::new(),
-        Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map2: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
+    let map1 = assert_map(args.get_err(0, "map1")?, "map1", span)?;
+    let map2 = assert_map(args.get_err(map2_position, "map2")?, "map2", span)?;
 
     let keys = args.get_variadic()?;
 
-    if keys.is_empty() {
-        map1.merge(map2);
-    } else {
-        let mut current_map = map1.clone();
-        let mut map_queue = Vec::new();
-
-        for key in keys {
-            match current_map.get(&key) {
-                Some(Value::Map(m1)) => {
-                    current_map = m1.clone();
-                    map_queue.push((key, m1));
-                }
-                Some(..) | None => {
-                    current_map = SassMap::new();
-                    map_queue.push((key, SassMap::new()));
-                }
-            }
-        }
-
-        match map_queue.last_mut() {
-            Some((_, m)) => {
-                m.merge(map2);
+    Ok(modify_nested_map(
+        map1,
+        &keys,
+        |old| match try_map(&old) {
+            Some(mut old) => {
+                old.merge(map2);
+                Value::Map(old)
             }
-            None => unreachable!(),
-        };
-
-        while let Some((key, queued_map)) = map_queue.pop() {
-            match map_queue.last_mut() {
-                Some((_, map)) => {
-                    map.insert(key, Value::Map(queued_map));
-                }
-                None => {
-                    map1.insert(key, Value::Map(queued_map));
-                    break;
-                }
-            }
-        }
-    }
-
-    Ok(Value::Map(map1))
+            None => Value::Map(map2),
+        },
+        true,
+    ))
 }
 
 pub(crate) fn map_remove(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
-    let mut map = match args.get_err(0, "map")? {
-        Value::Map(m) => m,
-        Value::List(v, ..) if v.is_empty() => SassMap::new(),
-        Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
+    let mut map = assert_map(args.get_err(0, "map")?, "map", args.span())?;
     let keys = args.get_variadic()?;
     for key in keys {
         map.remove(&key);
@@ -176,68 +194,68 @@ pub(crate) fn map_remove(mut args: ArgumentResult, visitor: &mut Visitor) -> Sas
 }
 
 pub(crate) fn map_set(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    match args.len() {
+        1 => return Err(("Expected $args to contain a key.", args.span()).into()),
+        2 => return Err(("Expected $args to contain a value.", args.span()).into()),
+        _ => {}
+    }
+
+    let span = args.span();
     let key_position = args.len().saturating_sub(2);
     let value_position = args.len().saturating_sub(1);
 
-    let mut map = match args.get_err(0, "map")? {
-        Value::Map(m) => m,
-        Value::List(v, ..) if v.is_empty() => SassMap::new(),
-        Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
-    };
+    let map = assert_map(args.get_err(0, "map")?, "map", span)?;
 
     let key = Spanned {
         node: args.get_err(key_position, "key")?,
-        span: args.span(),
+        span,
     };
     let value = args.get_err(value_position, "value")?;
 
-    let keys = args.get_variadic()?;
+    let mut keys = args.get_variadic()?;
+    keys.push(key);
 
-    if keys.is_empty() {
-        map.insert(key, value);
-    } else {
-        let mut current_map = map.clone();
-        let mut map_queue = Vec::new();
-
-        for key in keys {
-            match current_map.get(&key) {
-                Some(Value::Map(m1)) => {
-                    current_map = m1.clone();
-                    map_queue.push((key, m1));
-                }
-                Some(..) | None => {
-                    current_map = SassMap::new();
-                    map_queue.push((key, SassMap::new()));
-                }
-            }
-        }
+    Ok(modify_nested_map(map, &keys, |_| value, true))
+}
 
-        match map_queue.last_mut() {
-            Some((_, m)) => m.insert(key, value),
-            None => unreachable!(),
-        };
+pub(crate) fn map_deep_merge(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    args.max_args(2)?;
+    let span = args.span();
 
-        while let Some((key, queued_map)) = map_queue.pop() {
-            match map_queue.last_mut() {
-                Some((_, next_map)) => {
-                    next_map.insert(key, Value::Map(queued_map));
-                }
-                None => {
-                    map.insert(key, Value::Map(queued_map));
-                    break;
-                }
-            }
-        }
-    }
+    let map1 = assert_map(args.get_err(0, "map1")?, "map1", span)?;
+    let map2 = assert_map(args.get_err(1, "map2")?, "map2", span)?;
 
-    Ok(Value::Map(map))
+    Ok(Value::Map(deep_merge_impl(map1, map2)))
+}
+
+pub(crate) fn map_deep_remove(
+    mut args: ArgumentResult,
+    visitor: &mut Visitor,
+) -> SassResult<Value> {
+    let span = args.span();
+    let map = assert_map(args.get_err(0, "map")?, "map", span)?;
+    let key = Spanned {
+        node: args.get_err(1, "key")?,
+        span,
+    };
+
+    let mut keys = args.get_variadic()?;
+    keys.insert(0, key);
+
+    let last = keys.pop().unwrap();
+
+    Ok(modify_nested_map(
+        map,
+        &keys,
+        |value| match try_map(&value) {
+            Some(mut nested) if nested.get_ref(&last.node).is_some() => {
+                nested.remove(&last.node);
+                Value::Map(nested)
+            }
+            _ => value,
+        },
+        false,
+    ))
 }
 
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
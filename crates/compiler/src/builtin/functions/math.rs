@@ -86,16 +86,13 @@ pub(crate) fn comparable(mut args: ArgumentResult, visitor: &mut Visitor) -> Sas
     Ok(Value::bool(unit1.comparable(&unit2)))
 }
 
-// TODO: write tests for this
-#[cfg(feature = "random")]
 pub(crate) fn random(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
     args.max_args(1)?;
     let limit = args.default_arg(0, "limit", Value::Null);
 
     if matches!(limit, Value::Null) {
-        let mut rng = rand::thread_rng();
         return Ok(Value::Dimension(SassNumber::new_unitless(
-            rng.gen_range(0.0..1.0),
+            visitor.random.next_f64(),
         )));
     }
 
@@ -115,9 +112,8 @@ pub(crate) fn random(mut args: ArgumentResult, visitor: &mut Visitor) -> SassRes
             .into());
     }
 
-    let mut rng = rand::thread_rng();
     Ok(Value::Dimension(SassNumber::new_unitless(
-        rng.gen_range(0..limit_int) + 1,
+        visitor.random.next_below(limit_int as u64) as i64 + 1,
     )))
 }
 
@@ -232,6 +228,5 @@ pub(crate) fn declare(f: &mut GlobalFunctionMap) {
     f.insert("min", Builtin::new(min));
     f.insert("max", Builtin::new(max));
     f.insert("comparable", Builtin::new(comparable));
-    #[cfg(feature = "random")]
     f.insert("random", Builtin::new(random));
 }
@@ -72,6 +72,11 @@ pub(crate) static DISALLOWED_PLAIN_CSS_FUNCTION_NAMES: Lazy<BTreeSet<&str>> = La
                     | "alpha"
                     | "opacity"
                     | "saturate"
+                    | "lab"
+                    | "lch"
+                    | "oklab"
+                    | "oklch"
+                    | "color"
             )
         })
         .collect()
@@ -154,48 +154,89 @@ pub(crate) fn str_insert(mut args: ArgumentResult, visitor: &mut Visitor) -> Sas
     index.assert_no_units("index", span)?;
     let index_int = index.assert_int_with_name("index", span)?;
 
-    if s1.is_empty() {
-        return Ok(Value::String(substr, quotes));
-    }
-
     let len = s1.chars().count();
 
-    // Insert substring at char position, rather than byte position
-    let insert = |idx, s1: String, s2| {
-        s1.chars()
-            .enumerate()
-            .map(|(i, c)| {
-                if i + 1 == idx {
-                    c.to_string() + s2
-                } else if idx == 0 && i == 0 {
-                    s2.to_owned() + &c.to_string()
-                } else {
-                    c.to_string()
-                }
-            })
-            .collect::<String>()
-    };
-
-    let string = if index_int > 0 {
-        insert((index_int as usize - 1).min(len), s1, &substr)
+    // Indices count code points, rather than bytes
+    let code_point_index = if index_int > 0 {
+        (index_int as usize - 1).min(len)
     } else if index_int == 0 {
-        insert(0, s1, &substr)
+        0
     } else {
-        let idx = (len as i64 + index_int + 1).max(0) as usize;
-        insert(idx, s1, &substr)
+        (len as i64 + index_int + 1).max(0) as usize
     };
 
+    let byte_index = s1
+        .char_indices()
+        .nth(code_point_index)
+        .map_or(s1.len(), |(i, _)| i);
+
+    let mut string = s1;
+    string.insert_str(byte_index, &substr);
+
     Ok(Value::String(string, quotes))
 }
 
-#[cfg(feature = "random")]
+pub(crate) fn split(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    args.max_args(3)?;
+    let span = args.span();
+
+    let (string, quotes) = args
+        .get_err(0, "string")?
+        .assert_string_with_name("string", span)?;
+
+    let separator = args
+        .get_err(1, "separator")?
+        .assert_string_with_name("separator", span)?
+        .0;
+
+    let limit = match args.default_arg(2, "limit", Value::Null) {
+        Value::Null => None,
+        v => {
+            let limit = v.assert_number_with_name("limit", span)?;
+            let limit = limit.assert_int_with_name("limit", span)?;
+
+            if limit < 1 {
+                return Err((
+                    format!("$limit: Must be 1 or greater, was {}.", limit),
+                    span,
+                )
+                    .into());
+            }
+
+            Some(limit as usize)
+        }
+    };
+
+    let chunks: Vec<Value> = if string.is_empty() {
+        Vec::new()
+    } else if separator.is_empty() {
+        string
+            .chars()
+            .map(|c| Value::String(c.to_string(), quotes))
+            .collect()
+    } else {
+        let to_chunk = |chunk: &str| Value::String(chunk.to_owned(), quotes);
+
+        match limit {
+            Some(limit) => string
+                .splitn(limit + 1, &*separator)
+                .map(to_chunk)
+                .collect(),
+            None => string.split(&*separator).map(to_chunk).collect(),
+        }
+    };
+
+    Ok(Value::List(
+        chunks,
+        ListSeparator::Comma,
+        Brackets::Bracketed,
+    ))
+}
+
 #[allow(clippy::needless_pass_by_value)]
-pub(crate) fn unique_id(args: ArgumentResult, _: &mut Visitor) -> SassResult<Value> {
+pub(crate) fn unique_id(args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
     args.max_args(0)?;
-    let mut rng = thread_rng();
-    let string: String = std::iter::repeat(())
-        .map(|()| rng.sample(Alphanumeric))
-        .map(char::from)
+    let string: String = std::iter::repeat_with(|| visitor.random.next_alphanumeric())
         .take(12)
         .collect();
     Ok(Value::String(format!("id-{}", string), QuoteKind::None))
@@ -210,6 +251,5 @@ pub(crate) fn declare(f: &mut GlobalFunctionMap) {
     f.insert("str-slice", Builtin::new(str_slice));
     f.insert("str-index", Builtin::new(str_index));
     f.insert("str-insert", Builtin::new(str_insert));
-    #[cfg(feature = "random")]
     f.insert("unique-id", Builtin::new(unique_id));
 }
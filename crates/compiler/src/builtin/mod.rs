@@ -12,9 +12,6 @@ mod builtin_imports {
 
     pub(crate) use codemap::{Span, Spanned};
 
-    #[cfg(feature = "random")]
-    pub(crate) use rand::{distributions::Alphanumeric, thread_rng, Rng};
-
     pub(crate) use crate::{
         ast::{Argument, ArgumentDeclaration, ArgumentResult, MaybeEvaledArguments},
         color::Color,
@@ -1,13 +1,11 @@
 use crate::builtin::builtin_imports::*;
 
 use crate::builtin::{
-    math::{abs, ceil, comparable, divide, floor, max, min, percentage, round},
+    math::{abs, ceil, comparable, divide, floor, max, min, percentage, random, round},
     meta::{unit, unitless},
     modules::Module,
 };
 
-#[cfg(feature = "random")]
-use crate::builtin::math::random;
 use crate::value::{conversion_factor, SassNumber};
 
 fn coerce_to_rad(num: f64, unit: Unit) -> f64 {
@@ -469,7 +467,6 @@ pub(crate) fn declare(f: &mut Module) {
     f.insert_builtin("hypot", hypot);
     f.insert_builtin("div", divide);
     f.insert_builtin("atan2", atan2);
-    #[cfg(feature = "random")]
     f.insert_builtin("random", random);
 
     f.insert_builtin_var(
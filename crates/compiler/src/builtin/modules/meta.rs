@@ -2,7 +2,7 @@ use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use crate::ast::{Configuration, ConfiguredValue};
+use crate::ast::{Configuration, ConfiguredValue, Mixin};
 use crate::builtin::builtin_imports::*;
 
 use crate::builtin::{
@@ -32,50 +32,34 @@ fn load_css(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<()> {
         v => return Err((format!("$with: {} is not a map.", v.inspect(span)?), span).into()),
     };
 
-    let mut configuration = Configuration::empty();
-
-    if let Some(with) = with {
-        visitor.emit_warning("`grass` does not currently support the $with parameter of load-css. This file will be imported the same way it would using `@import`.", args.span());
-
-        let mut values = BTreeMap::new();
-        for (key, value) in with {
-            let name =
-                Identifier::from(key.node.assert_string_with_name("with key", args.span())?.0);
+    let configuration = match with {
+        Some(with) => {
+            let mut values = BTreeMap::new();
+            for (key, value) in with {
+                let name =
+                    Identifier::from(key.node.assert_string_with_name("with key", args.span())?.0);
+
+                if values.contains_key(&name) {
+                    return Err((
+                        format!("The variable ${name} was configured twice.", name = name),
+                        key.span,
+                    )
+                        .into());
+                }
 
-            if values.contains_key(&name) {
-                // todo: write test for this
-                return Err((
-                    format!("The variable {name} was configured twice.", name = name),
-                    key.span,
-                )
-                    .into());
+                values.insert(name, ConfiguredValue::explicit(value, args.span()));
             }
 
-            values.insert(name, ConfiguredValue::explicit(value, args.span()));
+            Configuration::explicit(values, args.span())
         }
+        None => Configuration::empty(),
+    };
 
-        configuration = Configuration::explicit(values, args.span());
-    }
-
-    let _configuration = Arc::new(RefCell::new(configuration));
-
-    let style_sheet = visitor.load_style_sheet(url.as_ref(), false, args.span())?;
-
-    visitor.visit_stylesheet(style_sheet)?;
+    let configuration = Arc::new(RefCell::new(configuration));
 
-    // todo: support the $with argument to load-css
-    // visitor.load_module(
-    //     url.as_ref(),
-    //     Some(Arc::clone(&configuration)),
-    //     true,
-    //     args.span(),
-    //     |visitor, module, stylesheet| {
-    //         // (*module).borrow()
-    //         Ok(())
-    //     },
-    // )?;
+    visitor.load_css(url.as_ref(), Arc::clone(&configuration), args.span())?;
 
-    // Visitor::assert_configuration_is_empty(&configuration, true)?;
+    Visitor::assert_configuration_is_empty(&configuration, true)?;
 
     Ok(())
 }
@@ -96,6 +80,90 @@ fn module_functions(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResu
     ))
 }
 
+fn module_mixins(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    args.max_args(1)?;
+
+    let module = Identifier::from(
+        args.get_err(0, "module")?
+            .assert_string_with_name("module", args.span())?
+            .0,
+    );
+
+    Ok(Value::Map(
+        (*(*visitor.env.modules).borrow().get(module, args.span())?)
+            .borrow()
+            .mixins(args.span()),
+    ))
+}
+
+fn get_mixin(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
+    args.max_args(2)?;
+
+    let span = args.span();
+
+    let name = Identifier::from(
+        args.get_err(0, "name")?
+            .assert_string_with_name("name", span)?
+            .0,
+    );
+
+    let module = match args.default_arg(1, "module", Value::Null) {
+        Value::String(s, ..) => Some(Spanned {
+            node: Identifier::from(s),
+            span,
+        }),
+        Value::Null => None,
+        v => {
+            return Err((
+                format!("$module: {} is not a string.", v.inspect(span)?),
+                span,
+            )
+                .into())
+        }
+    };
+
+    match visitor.env.get_mixin(Spanned { node: name, span }, module) {
+        Ok(mixin) => Ok(Value::MixinRef(Box::new(mixin))),
+        Err(..) if module.is_none() => Err((format!("Mixin not found: {}", name), span).into()),
+        Err(e) => Err(e),
+    }
+}
+
+fn assert_mixin(value: Value, name: &str, span: Span) -> SassResult<Mixin> {
+    match value {
+        Value::MixinRef(mixin) => Ok(*mixin),
+        v => Err((
+            format!("${}: {} is not a mixin reference.", name, v.inspect(span)?),
+            span,
+        )
+            .into()),
+    }
+}
+
+fn accepts_content(mut args: ArgumentResult, _visitor: &mut Visitor) -> SassResult<Value> {
+    args.max_args(1)?;
+
+    let mixin = assert_mixin(args.get_err(0, "mixin")?, "mixin", args.span())?;
+
+    Ok(Value::bool(mixin.accepts_content()))
+}
+
+fn apply(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<()> {
+    let span = args.span();
+
+    let mixin = assert_mixin(args.get_err(0, "mixin")?, "mixin", span)?;
+
+    args.remove_positional(0);
+
+    let content = visitor.env.content.clone();
+
+    if content.is_some() && !mixin.accepts_content() {
+        return Err(("Mixin doesn't accept a content block.", span).into());
+    }
+
+    visitor.run_mixin(mixin, MaybeEvaledArguments::Evaled(args), content, span)
+}
+
 fn module_variables(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
     args.max_args(1)?;
 
@@ -176,10 +244,14 @@ pub(crate) fn declare(f: &mut Module) {
     f.insert_builtin("content-exists", content_exists);
     f.insert_builtin("module-variables", module_variables);
     f.insert_builtin("module-functions", module_functions);
+    f.insert_builtin("module-mixins", module_mixins);
     f.insert_builtin("get-function", get_function);
     f.insert_builtin("call", call);
+    f.insert_builtin("get-mixin", get_mixin);
+    f.insert_builtin("accepts-content", accepts_content);
     f.insert_builtin("calc-args", calc_args);
     f.insert_builtin("calc-name", calc_name);
 
     f.insert_builtin_mixin("load-css", load_css);
+    f.insert_builtin_content_mixin("apply", apply);
 }
@@ -8,7 +8,7 @@ use std::{
 use codemap::{Span, Spanned};
 
 use crate::{
-    ast::{ArgumentResult, AstForwardRule, BuiltinMixin, Mixin},
+    ast::{ArgumentResult, AstForwardRule, BuiltinMixin, CssStmt, Mixin},
     builtin::Builtin,
     common::Identifier,
     error::SassResult,
@@ -138,10 +138,13 @@ impl ModuleScope {
 pub(crate) enum Module {
     Environment {
         scope: ModuleScope,
-        #[allow(dead_code)]
-        upstream: Vec<Module>,
-        #[allow(dead_code)]
+        /// The user-defined modules this module loaded with `@use` or `@forward`
+        upstream: Vec<Arc<RefCell<Module>>>,
+        /// The extensions defined in this module and, once the stylesheet has been
+        /// fully evaluated, in the modules downstream of it
         extension_store: ExtensionStore,
+        /// The CSS emitted by this module, not including that of its upstream modules
+        css: Vec<CssStmt>,
         #[allow(dead_code)]
         env: Environment,
     },
@@ -231,7 +234,12 @@ fn member_map<V: fmt::Debug + Clone + 'static>(
 }
 
 impl Module {
-    pub fn new_env(env: Environment, extension_store: ExtensionStore) -> Self {
+    pub fn new_env(
+        env: Environment,
+        extension_store: ExtensionStore,
+        upstream: Vec<Arc<RefCell<Module>>>,
+        css: Vec<CssStmt>,
+    ) -> Self {
         let variables = {
             let variables = (*env.forwarded_modules).borrow();
             let variables = variables
@@ -267,12 +275,47 @@ impl Module {
 
         Module::Environment {
             scope,
-            upstream: Vec::new(),
+            upstream,
             extension_store,
+            css,
             env,
         }
     }
 
+    /// The user-defined modules this module loaded with `@use` or `@forward`
+    pub fn upstream(&self) -> &[Arc<RefCell<Module>>] {
+        match self {
+            Self::Environment { upstream, .. } => upstream,
+            Self::Builtin { .. } | Self::Forwarded(..) => &[],
+        }
+    }
+
+    pub fn extension_store(&self) -> Option<&ExtensionStore> {
+        match self {
+            Self::Environment {
+                extension_store, ..
+            } => Some(extension_store),
+            Self::Builtin { .. } | Self::Forwarded(..) => None,
+        }
+    }
+
+    pub fn extension_store_mut(&mut self) -> Option<&mut ExtensionStore> {
+        match self {
+            Self::Environment {
+                extension_store, ..
+            } => Some(extension_store),
+            Self::Builtin { .. } | Self::Forwarded(..) => None,
+        }
+    }
+
+    /// Takes the CSS emitted by this module, leaving it empty
+    pub fn take_css(&mut self) -> Vec<CssStmt> {
+        match self {
+            Self::Environment { css, .. } => std::mem::take(css),
+            Self::Builtin { .. } | Self::Forwarded(..) => Vec::new(),
+        }
+    }
+
     pub fn new_builtin() -> Self {
         Module::Builtin {
             scope: ModuleScope::new(),
@@ -342,7 +385,29 @@ impl Module {
     pub fn insert_builtin_mixin(&mut self, name: &'static str, mixin: BuiltinMixin) {
         let scope = self.scope();
 
-        scope.mixins.insert(name.into(), Mixin::Builtin(mixin));
+        scope.mixins.insert(
+            name.into(),
+            Mixin::Builtin {
+                mixin,
+                name: name.into(),
+                accepts_content: false,
+            },
+        );
+    }
+
+    /// Like [`Module::insert_builtin_mixin`], but for mixins that may be passed
+    /// a content block
+    pub fn insert_builtin_content_mixin(&mut self, name: &'static str, mixin: BuiltinMixin) {
+        let scope = self.scope();
+
+        scope.mixins.insert(
+            name.into(),
+            Mixin::Builtin {
+                mixin,
+                name: name.into(),
+                accepts_content: true,
+            },
+        );
     }
 
     pub fn insert_builtin_var(&mut self, name: &'static str, value: Value) {
@@ -411,6 +476,23 @@ impl Module {
         )
     }
 
+    pub fn mixins(&self, span: Span) -> SassMap {
+        SassMap::new_with(
+            self.scope()
+                .mixins
+                .iter()
+                .into_iter()
+                .filter(|(key, _)| !key.as_str().starts_with('-'))
+                .map(|(key, value)| {
+                    (
+                        Value::String(key.to_string(), QuoteKind::Quoted).span(span),
+                        Value::MixinRef(Box::new(value)),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+
     pub fn variables(&self, span: Span) -> SassMap {
         SassMap::new_with(
             self.scope()
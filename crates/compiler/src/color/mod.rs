@@ -14,11 +14,18 @@
 //!
 //! Named colors retain their original casing,
 //! so `rEd` should be emitted as `rEd`.
+//!
+//! Colors in the non-legacy spaces added by CSS Color Level 4, such as `lab`,
+//! `oklch` or `color(display-p3 ...)`, additionally store their channels in
+//! that space. Their RGBA values are the closest color in sRGB, so that
+//! functions which only understand legacy colors continue to work.
 
-use crate::value::{fuzzy_round, Number};
+use crate::value::{fuzzy_equals, fuzzy_round, Number};
 pub(crate) use name::NAMED_COLORS;
+pub(crate) use space::{ChannelKind, ColorSpace};
 
 mod name;
+mod space;
 
 // todo: only store alpha once on color
 #[derive(Debug, Clone)]
@@ -27,6 +34,15 @@ pub(crate) struct Color {
     hsla: Option<Hsl>,
     alpha: Number,
     pub format: ColorFormat,
+    channels: Option<SpaceChannels>,
+}
+
+/// The channels of a color in a non-legacy color space. `None` represents a
+/// missing channel, written as `none` in CSS
+#[derive(Debug, Clone)]
+struct SpaceChannels {
+    space: ColorSpace,
+    channels: [Option<f64>; 3],
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -48,7 +64,24 @@ impl PartialEq for Color {
             return false;
         }
 
-        self.rgba == other.rgba
+        match (&self.channels, &other.channels) {
+            (None, None) => self.rgba == other.rgba,
+            // colors in different spaces are never equal, even if they
+            // represent the same color. `color.same()` can be used to compare them
+            (Some(channels1), Some(channels2)) => {
+                channels1.space == channels2.space
+                    && channels1
+                        .channels
+                        .iter()
+                        .zip(channels2.channels.iter())
+                        .all(|(a, b)| match (a, b) {
+                            (Some(a), Some(b)) => fuzzy_equals(*a, *b),
+                            (None, None) => true,
+                            _ => false,
+                        })
+            }
+            _ => false,
+        }
     }
 }
 
@@ -67,6 +100,7 @@ impl Color {
             alpha,
             hsla: None,
             format,
+            channels: None,
         }
     }
 
@@ -76,6 +110,7 @@ impl Color {
             alpha,
             hsla: Some(hsla),
             format: ColorFormat::Infer,
+            channels: None,
         }
     }
 }
@@ -149,6 +184,7 @@ impl Color {
             hsla: None,
             alpha: alpha.into(),
             format: ColorFormat::Literal(format),
+            channels: None,
         }
     }
 
@@ -447,6 +483,13 @@ impl Color {
 
     /// Change `alpha` to value given
     pub fn with_alpha(&self, alpha: Number) -> Self {
+        if self.channels.is_some() {
+            return Color {
+                alpha: alpha.clamp(0.0, 1.0),
+                ..self.clone()
+            };
+        }
+
         Color::from_rgba(self.red(), self.green(), self.blue(), alpha)
     }
 
@@ -454,14 +497,14 @@ impl Color {
     /// Takes a color and a number between 0 and 1,
     /// and returns a color with the opacity increased by that amount.
     pub fn fade_in(&self, amount: Number) -> Self {
-        Color::from_rgba(self.red(), self.green(), self.blue(), self.alpha() + amount)
+        self.with_alpha(self.alpha() + amount)
     }
 
     /// Makes a color more transparent.
     /// Takes a color and a number between 0 and 1,
     /// and returns a color with the opacity decreased by that amount.
     pub fn fade_out(&self, amount: Number) -> Self {
-        Color::from_rgba(self.red(), self.green(), self.blue(), self.alpha() - amount)
+        self.with_alpha(self.alpha() - amount)
     }
 }
 
@@ -515,3 +558,167 @@ impl Color {
         Number(1.0) - (self.red().max(self.green()).max(self.blue()) / Number(255.0))
     }
 }
+
+/// Color space functions
+impl Color {
+    /// Create a color from channels in any color space. Colors in legacy spaces
+    /// are converted to their RGBA representation, with missing channels
+    /// treated as zero
+    pub fn from_space(space: ColorSpace, channels: [Option<f64>; 3], alpha: Number) -> Self {
+        let alpha = alpha.clamp(0.0, 1.0);
+        let value = |idx: usize| Number(channels[idx].unwrap_or(0.0));
+
+        match space {
+            ColorSpace::Rgb => return Color::from_rgba(value(0), value(1), value(2), alpha),
+            ColorSpace::Hsl => {
+                return Color::from_hsla(
+                    value(0),
+                    value(1) / Number(100.0),
+                    value(2) / Number(100.0),
+                    alpha,
+                )
+            }
+            ColorSpace::Hwb => return Color::from_hwb(value(0), value(1), value(2), alpha),
+            _ => {}
+        }
+
+        let mut channels = channels;
+
+        for (channel, info) in channels.iter_mut().zip(space.channels()) {
+            if let Some(channel) = channel {
+                match info.kind {
+                    ChannelKind::Hue => *channel = channel.rem_euclid(360.0),
+                    ChannelKind::Percent if info.name == "lightness" => {
+                        *channel = channel.clamp(0.0, info.max);
+                    }
+                    ChannelKind::Linear if info.name == "chroma" => {
+                        *channel = channel.max(0.0);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let [red, green, blue] = space
+            .convert(ColorSpace::Rgb, channels)
+            .map(|channel| Number(channel.unwrap_or(0.0)).clamp(0.0, 255.0));
+
+        Color {
+            rgba: Rgb::new(red, green, blue),
+            hsla: None,
+            alpha,
+            format: ColorFormat::Infer,
+            channels: Some(SpaceChannels { space, channels }),
+        }
+    }
+
+    /// The space this color was defined in
+    pub fn space(&self) -> ColorSpace {
+        match &self.channels {
+            Some(channels) => channels.space,
+            None if self.format == ColorFormat::Hsl => ColorSpace::Hsl,
+            None => ColorSpace::Rgb,
+        }
+    }
+
+    pub fn is_legacy(&self) -> bool {
+        self.channels.is_none()
+    }
+
+    /// The channels of a color in a non-legacy space, or `None` for legacy colors
+    pub fn space_channels(&self) -> Option<(ColorSpace, [Option<f64>; 3])> {
+        self.channels
+            .as_ref()
+            .map(|channels| (channels.space, channels.channels))
+    }
+
+    /// The channels of this color, converted to `space` if necessary
+    pub fn channels_in(&self, space: ColorSpace) -> [Option<f64>; 3] {
+        if let Some(channels) = &self.channels {
+            return channels.space.convert(space, channels.channels);
+        }
+
+        match space {
+            ColorSpace::Rgb => [self.rgba.red.0, self.rgba.green.0, self.rgba.blue.0].map(Some),
+            ColorSpace::Hsl => {
+                let (hue, saturation, lightness, _) = self.as_hsla();
+                [hue.0, saturation.0 * 100.0, lightness.0 * 100.0].map(Some)
+            }
+            ColorSpace::Hwb => [
+                self.hue().0,
+                self.whiteness().0 * 100.0,
+                self.blackness().0 * 100.0,
+            ]
+            .map(Some),
+            _ => ColorSpace::Rgb.convert(
+                space,
+                [self.rgba.red.0, self.rgba.green.0, self.rgba.blue.0].map(Some),
+            ),
+        }
+    }
+
+    pub fn to_space(&self, space: ColorSpace) -> Self {
+        if self.space() == space {
+            return self.clone();
+        }
+
+        Color::from_space(space, self.channels_in(space), self.alpha())
+    }
+
+    pub fn is_missing(&self, channel: usize) -> bool {
+        self.channels
+            .as_ref()
+            .is_some_and(|channels| channels.channels[channel].is_none())
+    }
+
+    /// Whether two colors represent the same visual color, regardless of the
+    /// space they are defined in
+    pub fn same(&self, other: &Self) -> bool {
+        let xyz1 = self.channels_in(ColorSpace::XyzD65);
+        let xyz2 = other.channels_in(ColorSpace::XyzD65);
+
+        fuzzy_equals(self.alpha().0, other.alpha().0)
+            && xyz1
+                .iter()
+                .zip(xyz2.iter())
+                .all(|(a, b)| fuzzy_equals(a.unwrap_or(0.0), b.unwrap_or(0.0)))
+    }
+
+    /// Mix two colors by interpolating their channels in `space`, using
+    /// premultiplied alpha and the shorter hue interpolation method. The result
+    /// is converted back to the space of `self`
+    pub fn mix_in_space(&self, other: &Color, weight: Number, space: ColorSpace) -> Self {
+        let weight = weight.0.clamp(0.0, 1.0);
+        let channels1 = self.channels_in(space);
+        let channels2 = other.channels_in(space);
+        let alpha1 = self.alpha().0;
+        let alpha2 = other.alpha().0;
+        let alpha = alpha1 * weight + alpha2 * (1.0 - weight);
+
+        let mut mixed = [None; 3];
+
+        for (idx, info) in space.channels().iter().enumerate() {
+            mixed[idx] = match (channels1[idx], channels2[idx]) {
+                (None, None) => None,
+                (Some(value), None) | (None, Some(value)) => Some(value),
+                (Some(mut value1), Some(mut value2)) if info.kind == ChannelKind::Hue => {
+                    if value2 - value1 > 180.0 {
+                        value1 += 360.0;
+                    } else if value2 - value1 < -180.0 {
+                        value2 += 360.0;
+                    }
+
+                    Some(value1 * weight + value2 * (1.0 - weight))
+                }
+                (Some(value1), Some(value2)) if alpha == 0.0 => {
+                    Some(value1 * weight + value2 * (1.0 - weight))
+                }
+                (Some(value1), Some(value2)) => {
+                    Some((value1 * alpha1 * weight + value2 * alpha2 * (1.0 - weight)) / alpha)
+                }
+            };
+        }
+
+        Color::from_space(space, mixed, Number(alpha)).to_space(self.space())
+    }
+}
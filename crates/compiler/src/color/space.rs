@@ -0,0 +1,924 @@
+//! Color spaces from CSS Color Level 4, and conversions between them
+//!
+//! Channels are stored in the same units `dart-sass` uses: `rgb` channels are
+//! between 0 and 255, `hsl` and `hwb` saturation, lightness, whiteness and
+//! blackness are percentages between 0 and 100, `lab` and `lch` lightness is
+//! between 0 and 100, `oklab` and `oklch` lightness is between 0 and 1, and
+//! channels of the predefined RGB and XYZ spaces used by `color()` are
+//! (nominally) between 0 and 1. Hues are always in degrees.
+//!
+//! Conversions go through XYZ with a D65 white point. Matrices and transfer
+//! functions are taken from the sample code in
+//! <https://www.w3.org/TR/css-color-4/#color-conversion-code>
+
+use std::f64::consts::PI;
+
+use crate::value::fuzzy_equals;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum ColorSpace {
+    Rgb,
+    Hsl,
+    Hwb,
+    Srgb,
+    SrgbLinear,
+    DisplayP3,
+    A98Rgb,
+    ProphotoRgb,
+    Rec2020,
+    XyzD50,
+    XyzD65,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum ChannelKind {
+    /// A channel which is usually written as a unitless number, but which may
+    /// also be written as a percentage of `max`
+    Linear,
+    /// A channel which is conventionally written as a percentage of `max`,
+    /// such as `lab` lightness or `hsl` saturation
+    Percent,
+    /// A hue angle, in degrees
+    Hue,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct ChannelInfo {
+    pub name: &'static str,
+    /// The minimum value of this channel. Only enforced for bounded spaces,
+    /// but also used as the range for `color.scale()`
+    pub min: f64,
+    pub max: f64,
+    pub kind: ChannelKind,
+}
+
+const fn linear(name: &'static str, min: f64, max: f64) -> ChannelInfo {
+    ChannelInfo {
+        name,
+        min,
+        max,
+        kind: ChannelKind::Linear,
+    }
+}
+
+const fn percent(name: &'static str, max: f64) -> ChannelInfo {
+    ChannelInfo {
+        name,
+        min: 0.0,
+        max,
+        kind: ChannelKind::Percent,
+    }
+}
+
+const HUE: ChannelInfo = ChannelInfo {
+    name: "hue",
+    min: 0.0,
+    max: 360.0,
+    kind: ChannelKind::Hue,
+};
+
+const RGB_CHANNELS: [ChannelInfo; 3] = [
+    linear("red", 0.0, 255.0),
+    linear("green", 0.0, 255.0),
+    linear("blue", 0.0, 255.0),
+];
+
+const HSL_CHANNELS: [ChannelInfo; 3] = [
+    HUE,
+    percent("saturation", 100.0),
+    percent("lightness", 100.0),
+];
+
+const HWB_CHANNELS: [ChannelInfo; 3] = [
+    HUE,
+    percent("whiteness", 100.0),
+    percent("blackness", 100.0),
+];
+
+const PREDEFINED_RGB_CHANNELS: [ChannelInfo; 3] = [
+    linear("red", 0.0, 1.0),
+    linear("green", 0.0, 1.0),
+    linear("blue", 0.0, 1.0),
+];
+
+const XYZ_CHANNELS: [ChannelInfo; 3] = [
+    linear("x", 0.0, 1.0),
+    linear("y", 0.0, 1.0),
+    linear("z", 0.0, 1.0),
+];
+
+const LAB_CHANNELS: [ChannelInfo; 3] = [
+    percent("lightness", 100.0),
+    linear("a", -125.0, 125.0),
+    linear("b", -125.0, 125.0),
+];
+
+const LCH_CHANNELS: [ChannelInfo; 3] = [
+    percent("lightness", 100.0),
+    linear("chroma", 0.0, 150.0),
+    HUE,
+];
+
+const OKLAB_CHANNELS: [ChannelInfo; 3] = [
+    percent("lightness", 1.0),
+    linear("a", -0.4, 0.4),
+    linear("b", -0.4, 0.4),
+];
+
+const OKLCH_CHANNELS: [ChannelInfo; 3] =
+    [percent("lightness", 1.0), linear("chroma", 0.0, 0.4), HUE];
+
+impl ColorSpace {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "rgb" => Self::Rgb,
+            "hsl" => Self::Hsl,
+            "hwb" => Self::Hwb,
+            "srgb" => Self::Srgb,
+            "srgb-linear" => Self::SrgbLinear,
+            "display-p3" => Self::DisplayP3,
+            "a98-rgb" => Self::A98Rgb,
+            "prophoto-rgb" => Self::ProphotoRgb,
+            "rec2020" => Self::Rec2020,
+            "xyz-d50" => Self::XyzD50,
+            "xyz" | "xyz-d65" => Self::XyzD65,
+            "lab" => Self::Lab,
+            "lch" => Self::Lch,
+            "oklab" => Self::Oklab,
+            "oklch" => Self::Oklch,
+            _ => return None,
+        })
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Rgb => "rgb",
+            Self::Hsl => "hsl",
+            Self::Hwb => "hwb",
+            Self::Srgb => "srgb",
+            Self::SrgbLinear => "srgb-linear",
+            Self::DisplayP3 => "display-p3",
+            Self::A98Rgb => "a98-rgb",
+            Self::ProphotoRgb => "prophoto-rgb",
+            Self::Rec2020 => "rec2020",
+            Self::XyzD50 => "xyz-d50",
+            Self::XyzD65 => "xyz",
+            Self::Lab => "lab",
+            Self::Lch => "lch",
+            Self::Oklab => "oklab",
+            Self::Oklch => "oklch",
+        }
+    }
+
+    /// Whether colors in this space can be represented using the `rgb()`,
+    /// `hsl()` and hex syntaxes understood by older browsers
+    pub const fn is_legacy(self) -> bool {
+        matches!(self, Self::Rgb | Self::Hsl | Self::Hwb)
+    }
+
+    /// Whether this space has a gamut, outside of which colors can't be
+    /// displayed
+    pub const fn is_bounded(self) -> bool {
+        !matches!(
+            self,
+            Self::XyzD50 | Self::XyzD65 | Self::Lab | Self::Lch | Self::Oklab | Self::Oklch
+        )
+    }
+
+    /// Whether this space is serialized using the `color()` function
+    pub const fn is_predefined(self) -> bool {
+        matches!(
+            self,
+            Self::Srgb
+                | Self::SrgbLinear
+                | Self::DisplayP3
+                | Self::A98Rgb
+                | Self::ProphotoRgb
+                | Self::Rec2020
+                | Self::XyzD50
+                | Self::XyzD65
+        )
+    }
+
+    pub const fn channels(self) -> &'static [ChannelInfo; 3] {
+        match self {
+            Self::Rgb => &RGB_CHANNELS,
+            Self::Hsl => &HSL_CHANNELS,
+            Self::Hwb => &HWB_CHANNELS,
+            Self::Srgb
+            | Self::SrgbLinear
+            | Self::DisplayP3
+            | Self::A98Rgb
+            | Self::ProphotoRgb
+            | Self::Rec2020 => &PREDEFINED_RGB_CHANNELS,
+            Self::XyzD50 | Self::XyzD65 => &XYZ_CHANNELS,
+            Self::Lab => &LAB_CHANNELS,
+            Self::Lch => &LCH_CHANNELS,
+            Self::Oklab => &OKLAB_CHANNELS,
+            Self::Oklch => &OKLCH_CHANNELS,
+        }
+    }
+
+    pub fn channel_index(self, name: &str) -> Option<usize> {
+        self.channels()
+            .iter()
+            .position(|channel| channel.name == name)
+    }
+
+    /// Convert channels in this space to `target`. Missing channels are treated
+    /// as zero. When converting to `lch` or `oklch`, the hue of a color with
+    /// (almost) no chroma is powerless, so it becomes missing
+    pub fn convert(self, target: Self, channels: [Option<f64>; 3]) -> [Option<f64>; 3] {
+        if self == target {
+            return channels;
+        }
+
+        let values = channels.map(|channel| channel.unwrap_or(0.0));
+        let mut converted = target.from_xyz_d65(self.to_xyz_d65(values)).map(Some);
+
+        // Hue is powerless when there is no chroma
+        if matches!(target, Self::Lch | Self::Oklch) {
+            let chroma_epsilon = if target == Self::Lch {
+                0.0015
+            } else {
+                0.000_004
+            };
+            if converted[1].is_some_and(|chroma| chroma.abs() < chroma_epsilon) {
+                converted[1] = Some(0.0);
+                converted[2] = None;
+            }
+        }
+
+        converted
+    }
+
+    fn to_xyz_d65(self, channels: [f64; 3]) -> [f64; 3] {
+        match self {
+            Self::Rgb => srgb_to_xyz_d65(channels.map(|channel| channel / 255.0)),
+            Self::Hsl => srgb_to_xyz_d65(hsl_to_srgb(channels)),
+            Self::Hwb => srgb_to_xyz_d65(hwb_to_srgb(channels)),
+            Self::Srgb => srgb_to_xyz_d65(channels),
+            Self::SrgbLinear => multiply(&LINEAR_SRGB_TO_XYZ_D65, channels),
+            Self::DisplayP3 => {
+                multiply(&LINEAR_DISPLAY_P3_TO_XYZ_D65, channels.map(srgb_to_linear))
+            }
+            Self::A98Rgb => multiply(&LINEAR_A98_RGB_TO_XYZ_D65, channels.map(a98_rgb_to_linear)),
+            Self::ProphotoRgb => multiply(
+                &XYZ_D50_TO_XYZ_D65,
+                multiply(
+                    &LINEAR_PROPHOTO_RGB_TO_XYZ_D50,
+                    channels.map(prophoto_rgb_to_linear),
+                ),
+            ),
+            Self::Rec2020 => multiply(&LINEAR_REC2020_TO_XYZ_D65, channels.map(rec2020_to_linear)),
+            Self::XyzD50 => multiply(&XYZ_D50_TO_XYZ_D65, channels),
+            Self::XyzD65 => channels,
+            Self::Lab => multiply(&XYZ_D50_TO_XYZ_D65, lab_to_xyz_d50(channels)),
+            Self::Lch => multiply(&XYZ_D50_TO_XYZ_D65, lab_to_xyz_d50(polar_to_lab(channels))),
+            Self::Oklab => oklab_to_xyz_d65(channels),
+            Self::Oklch => oklab_to_xyz_d65(polar_to_lab(channels)),
+        }
+    }
+
+    #[allow(clippy::wrong_self_convention)]
+    fn from_xyz_d65(self, xyz: [f64; 3]) -> [f64; 3] {
+        match self {
+            Self::Rgb => xyz_d65_to_srgb(xyz).map(|channel| channel * 255.0),
+            Self::Hsl => srgb_to_hsl(xyz_d65_to_srgb(xyz)),
+            Self::Hwb => srgb_to_hwb(xyz_d65_to_srgb(xyz)),
+            Self::Srgb => xyz_d65_to_srgb(xyz),
+            Self::SrgbLinear => multiply(&XYZ_D65_TO_LINEAR_SRGB, xyz),
+            Self::DisplayP3 => multiply(&XYZ_D65_TO_LINEAR_DISPLAY_P3, xyz).map(linear_to_srgb),
+            Self::A98Rgb => multiply(&XYZ_D65_TO_LINEAR_A98_RGB, xyz).map(linear_to_a98_rgb),
+            Self::ProphotoRgb => multiply(
+                &XYZ_D50_TO_LINEAR_PROPHOTO_RGB,
+                multiply(&XYZ_D65_TO_XYZ_D50, xyz),
+            )
+            .map(linear_to_prophoto_rgb),
+            Self::Rec2020 => multiply(&XYZ_D65_TO_LINEAR_REC2020, xyz).map(linear_to_rec2020),
+            Self::XyzD50 => multiply(&XYZ_D65_TO_XYZ_D50, xyz),
+            Self::XyzD65 => xyz,
+            Self::Lab => xyz_d50_to_lab(multiply(&XYZ_D65_TO_XYZ_D50, xyz)),
+            Self::Lch => lab_to_polar(xyz_d50_to_lab(multiply(&XYZ_D65_TO_XYZ_D50, xyz))),
+            Self::Oklab => xyz_d65_to_oklab(xyz),
+            Self::Oklch => lab_to_polar(xyz_d65_to_oklab(xyz)),
+        }
+    }
+
+    /// Whether the given channels are within the gamut of this space
+    pub fn is_in_gamut(self, channels: [f64; 3]) -> bool {
+        match self {
+            Self::Hsl => Self::Rgb.is_in_gamut(hsl_to_srgb(channels).map(|c| c * 255.0)),
+            Self::Hwb => Self::Rgb.is_in_gamut(hwb_to_srgb(channels).map(|c| c * 255.0)),
+            _ if !self.is_bounded() => true,
+            _ => self.channels().iter().zip(channels).all(|(info, channel)| {
+                (channel > info.min || fuzzy_equals(channel, info.min))
+                    && (channel < info.max || fuzzy_equals(channel, info.max))
+            }),
+        }
+    }
+
+    /// Clamp each channel to the gamut of this space
+    pub fn clip(self, channels: [f64; 3]) -> [f64; 3] {
+        match self {
+            Self::Hsl => srgb_to_hsl(hsl_to_srgb(channels).map(|c| c.clamp(0.0, 1.0))),
+            Self::Hwb => srgb_to_hwb(hwb_to_srgb(channels).map(|c| c.clamp(0.0, 1.0))),
+            _ if !self.is_bounded() => channels,
+            _ => {
+                let info = self.channels();
+                [0, 1, 2].map(|idx| channels[idx].clamp(info[idx].min, info[idx].max))
+            }
+        }
+    }
+
+    /// Map a color into the gamut of this space by reducing its chroma in
+    /// `oklch`, as described in <https://www.w3.org/TR/css-color-4/#css-gamut-mapping>
+    pub fn local_minde(self, oklch: [f64; 3]) -> [f64; 3] {
+        const JND: f64 = 0.02;
+        const EPSILON: f64 = 0.0001;
+
+        let [lightness, _, hue] = oklch;
+
+        if lightness > 1.0 || fuzzy_equals(lightness, 1.0) {
+            return Self::Oklch.to(self, [1.0, 0.0, 0.0]);
+        }
+
+        if lightness < 0.0 || fuzzy_equals(lightness, 0.0) {
+            return Self::Oklch.to(self, [0.0, 0.0, 0.0]);
+        }
+
+        let origin = Self::Oklch.to(self, oklch);
+
+        if self.is_in_gamut(origin) {
+            return origin;
+        }
+
+        let clipped = self.clip(origin);
+
+        if delta_eok(
+            Self::Oklch.to(Self::Oklab, oklch),
+            self.to(Self::Oklab, clipped),
+        ) < JND
+        {
+            return clipped;
+        }
+
+        let mut min = 0.0;
+        let mut max = oklch[1];
+        let mut min_in_gamut = true;
+        let mut current = clipped;
+
+        while max - min > EPSILON {
+            let chroma = (min + max) / 2.0;
+            let candidate = Self::Oklch.to(self, [lightness, chroma, hue]);
+
+            if min_in_gamut && self.is_in_gamut(candidate) {
+                min = chroma;
+                continue;
+            }
+
+            current = self.clip(candidate);
+            let error = delta_eok(
+                self.to(Self::Oklab, current),
+                Self::Oklch.to(Self::Oklab, [lightness, chroma, hue]),
+            );
+
+            if error < JND {
+                if JND - error < EPSILON {
+                    break;
+                }
+
+                min_in_gamut = false;
+                min = chroma;
+            } else {
+                max = chroma;
+            }
+        }
+
+        current
+    }
+
+    fn to(self, target: Self, channels: [f64; 3]) -> [f64; 3] {
+        target.from_xyz_d65(self.to_xyz_d65(channels))
+    }
+}
+
+fn delta_eok(a: [f64; 3], b: [f64; 3]) -> f64 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+type Matrix = [[f64; 3]; 3];
+
+fn multiply(matrix: &Matrix, vector: [f64; 3]) -> [f64; 3] {
+    matrix.map(|row| row[0] * vector[0] + row[1] * vector[1] + row[2] * vector[2])
+}
+
+const LINEAR_SRGB_TO_XYZ_D65: Matrix = [
+    [
+        0.412_390_799_265_959_34,
+        0.357_584_339_383_878,
+        0.180_480_788_401_834_3,
+    ],
+    [
+        0.212_639_005_871_510_27,
+        0.715_168_678_767_756,
+        0.072_192_315_360_733_71,
+    ],
+    [
+        0.019_330_818_715_591_82,
+        0.119_194_779_794_625_98,
+        0.950_532_152_249_660_7,
+    ],
+];
+
+const XYZ_D65_TO_LINEAR_SRGB: Matrix = [
+    [
+        3.240_969_941_904_522_6,
+        -1.537_383_177_570_094,
+        -0.498_610_760_293_003_4,
+    ],
+    [
+        -0.969_243_636_280_879_6,
+        1.875_967_501_507_720_2,
+        0.041_555_057_407_175_59,
+    ],
+    [
+        0.055_630_079_696_993_66,
+        -0.203_976_958_888_976_52,
+        1.056_971_514_242_878_6,
+    ],
+];
+
+const LINEAR_DISPLAY_P3_TO_XYZ_D65: Matrix = [
+    [
+        0.486_570_948_648_216_2,
+        0.265_667_693_169_093_06,
+        0.198_217_285_234_362_5,
+    ],
+    [
+        0.228_974_564_069_748_8,
+        0.691_738_521_836_506_4,
+        0.079_286_914_093_745,
+    ],
+    [0.0, 0.045_113_381_858_902_64, 1.043_944_368_900_976],
+];
+
+const XYZ_D65_TO_LINEAR_DISPLAY_P3: Matrix = [
+    [
+        2.493_496_911_941_425,
+        -0.931_383_617_919_123_9,
+        -0.402_710_784_450_716_84,
+    ],
+    [
+        -0.829_488_969_561_574_7,
+        1.762_664_060_318_346_3,
+        0.023_624_685_841_943_577,
+    ],
+    [
+        0.035_845_830_243_784_47,
+        -0.076_172_389_268_041_82,
+        0.956_884_524_007_687_2,
+    ],
+];
+
+const LINEAR_A98_RGB_TO_XYZ_D65: Matrix = [
+    [
+        0.576_669_042_910_130_5,
+        0.185_558_237_906_546_3,
+        0.188_228_646_234_994_7,
+    ],
+    [
+        0.297_344_975_250_536_05,
+        0.627_363_566_255_466_1,
+        0.075_291_458_493_997_88,
+    ],
+    [
+        0.027_031_361_386_412_34,
+        0.070_688_852_535_827_23,
+        0.991_337_536_837_638_8,
+    ],
+];
+
+const XYZ_D65_TO_LINEAR_A98_RGB: Matrix = [
+    [
+        2.041_587_903_810_746_5,
+        -0.565_006_974_278_859_6,
+        -0.344_731_350_778_329_56,
+    ],
+    [
+        -0.969_243_636_280_879_5,
+        1.875_967_501_507_720_2,
+        0.041_555_057_407_175_57,
+    ],
+    [
+        0.013_444_280_632_031_142,
+        -0.118_362_392_231_018_38,
+        1.015_174_994_391_205_4,
+    ],
+];
+
+const LINEAR_PROPHOTO_RGB_TO_XYZ_D50: Matrix = [
+    [
+        0.797_760_489_672_302_7,
+        0.135_185_837_175_740_31,
+        0.031_349_349_581_524_8,
+    ],
+    [
+        0.288_071_128_229_293_4,
+        0.711_843_217_810_101_4,
+        0.000_085_653_960_605_259,
+    ],
+    [0.0, 0.0, 0.825_104_602_510_460_1],
+];
+
+const XYZ_D50_TO_LINEAR_PROPHOTO_RGB: Matrix = [
+    [
+        1.345_798_973_102_828_1,
+        -0.255_580_100_079_975_34,
+        -0.051_106_285_067_534_01,
+    ],
+    [
+        -0.544_622_493_902_834_7,
+        1.508_232_741_313_278_1,
+        0.020_536_032_391_479_73,
+    ],
+    [0.0, 0.0, 1.211_967_545_638_945_4],
+];
+
+const LINEAR_REC2020_TO_XYZ_D65: Matrix = [
+    [
+        0.636_958_048_301_291_4,
+        0.144_616_903_586_208_32,
+        0.168_880_975_164_172_1,
+    ],
+    [
+        0.262_700_212_011_267_1,
+        0.677_998_071_518_870_8,
+        0.059_301_716_469_861_96,
+    ],
+    [0.0, 0.028_072_693_049_087_428, 1.060_985_057_710_791],
+];
+
+const XYZ_D65_TO_LINEAR_REC2020: Matrix = [
+    [
+        1.716_651_187_971_268,
+        -0.355_670_783_776_392,
+        -0.253_366_281_373_66,
+    ],
+    [
+        -0.666_684_351_832_489,
+        1.616_481_236_634_939,
+        0.015_768_545_813_911_1,
+    ],
+    [
+        0.017_639_857_445_311,
+        -0.042_770_613_257_809,
+        0.942_103_121_235_474,
+    ],
+];
+
+/// Bradford chromatic adaptation from D65 to D50
+const XYZ_D65_TO_XYZ_D50: Matrix = [
+    [
+        1.047_929_820_840_548_8,
+        0.022_946_793_341_019_088,
+        -0.050_192_229_543_135_57,
+    ],
+    [
+        0.029_627_815_688_159_344,
+        0.990_434_484_573_249,
+        -0.017_073_825_029_385_14,
+    ],
+    [
+        -0.009_243_058_152_591_178,
+        0.015_055_144_896_577_895,
+        0.751_874_289_958_000_8,
+    ],
+];
+
+/// Bradford chromatic adaptation from D50 to D65. This is the exact inverse of
+/// the matrix above, so that colors survive a round trip through `lab`
+const XYZ_D50_TO_XYZ_D65: Matrix = [
+    [
+        0.955_473_394_204_897_7,
+        -0.023_098_374_726_038_654,
+        0.063_259_194_989_114_96,
+    ],
+    [
+        -0.028_369_712_866_394_44,
+        1.009_995_337_455_560_4,
+        0.021_041_475_607_354_32,
+    ],
+    [
+        0.012_314_034_948_960_155,
+        -0.020_507_584_814_405_57,
+        1.330_365_912_644_437_2,
+    ],
+];
+
+const XYZ_D65_TO_LMS: Matrix = [
+    [
+        0.819_022_437_996_703,
+        0.361_906_260_052_890_4,
+        -0.128_873_781_520_987_9,
+    ],
+    [
+        0.032_983_653_932_388_5,
+        0.929_286_861_586_343_4,
+        0.036_144_666_350_642_4,
+    ],
+    [
+        0.048_177_189_359_624_2,
+        0.264_239_531_752_730_8,
+        0.633_547_828_469_430_9,
+    ],
+];
+
+const LMS_TO_OKLAB: Matrix = [
+    [
+        0.210_454_268_309_314,
+        0.793_617_774_702_305_4,
+        -0.004_072_043_011_619_3,
+    ],
+    [
+        1.977_998_532_431_168_4,
+        -2.428_592_242_048_58,
+        0.450_593_709_617_411,
+    ],
+    [
+        0.025_904_042_465_547_8,
+        0.782_771_712_457_529_6,
+        -0.808_675_754_923_077_4,
+    ],
+];
+
+const OKLAB_TO_LMS: Matrix = [
+    [1.0, 0.396_337_777_376_174_9, 0.215_803_757_309_913_6],
+    [1.0, -0.105_561_345_815_658_6, -0.063_854_172_825_813_3],
+    [1.0, -0.089_484_177_529_811_9, -1.291_485_548_019_409_2],
+];
+
+const LMS_TO_XYZ_D65: Matrix = [
+    [
+        1.226_879_875_845_924_3,
+        -0.557_814_994_460_217_1,
+        0.281_391_045_665_964_7,
+    ],
+    [
+        -0.040_575_745_214_800_8,
+        1.112_286_803_280_317,
+        -0.071_711_058_065_516_4,
+    ],
+    [
+        -0.076_372_936_674_660_1,
+        -0.421_493_332_402_243_2,
+        1.586_924_019_836_781_6,
+    ],
+];
+
+/// The D50 reference white, used by `lab` and `lch`
+const D50: [f64; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+
+const LAB_EPSILON: f64 = 216.0 / 24389.0;
+const LAB_KAPPA: f64 = 24389.0 / 27.0;
+
+fn srgb_to_linear(channel: f64) -> f64 {
+    let abs = channel.abs();
+
+    if abs <= 0.04045 {
+        channel / 12.92
+    } else {
+        channel.signum() * ((abs + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(channel: f64) -> f64 {
+    let abs = channel.abs();
+
+    if abs > 0.003_130_8 {
+        channel.signum() * (1.055 * abs.powf(1.0 / 2.4) - 0.055)
+    } else {
+        12.92 * channel
+    }
+}
+
+fn a98_rgb_to_linear(channel: f64) -> f64 {
+    channel.signum() * channel.abs().powf(563.0 / 256.0)
+}
+
+fn linear_to_a98_rgb(channel: f64) -> f64 {
+    channel.signum() * channel.abs().powf(256.0 / 563.0)
+}
+
+fn prophoto_rgb_to_linear(channel: f64) -> f64 {
+    let abs = channel.abs();
+
+    if abs <= 16.0 / 512.0 {
+        channel / 16.0
+    } else {
+        channel.signum() * abs.powf(1.8)
+    }
+}
+
+fn linear_to_prophoto_rgb(channel: f64) -> f64 {
+    let abs = channel.abs();
+
+    if abs >= 1.0 / 512.0 {
+        channel.signum() * abs.powf(1.0 / 1.8)
+    } else {
+        16.0 * channel
+    }
+}
+
+const REC2020_ALPHA: f64 = 1.099_296_826_809_44;
+const REC2020_BETA: f64 = 0.018_053_968_510_807;
+
+fn rec2020_to_linear(channel: f64) -> f64 {
+    let abs = channel.abs();
+
+    if abs < REC2020_BETA * 4.5 {
+        channel / 4.5
+    } else {
+        channel.signum() * ((abs + REC2020_ALPHA - 1.0) / REC2020_ALPHA).powf(1.0 / 0.45)
+    }
+}
+
+fn linear_to_rec2020(channel: f64) -> f64 {
+    let abs = channel.abs();
+
+    if abs > REC2020_BETA {
+        channel.signum() * (REC2020_ALPHA * abs.powf(0.45) - (REC2020_ALPHA - 1.0))
+    } else {
+        4.5 * channel
+    }
+}
+
+fn srgb_to_xyz_d65(channels: [f64; 3]) -> [f64; 3] {
+    multiply(&LINEAR_SRGB_TO_XYZ_D65, channels.map(srgb_to_linear))
+}
+
+fn xyz_d65_to_srgb(xyz: [f64; 3]) -> [f64; 3] {
+    multiply(&XYZ_D65_TO_LINEAR_SRGB, xyz).map(linear_to_srgb)
+}
+
+fn hue_to_rgb(m1: f64, m2: f64, mut hue: f64) -> f64 {
+    if hue < 0.0 {
+        hue += 1.0;
+    }
+    if hue > 1.0 {
+        hue -= 1.0;
+    }
+
+    if hue < 1.0 / 6.0 {
+        ((m2 - m1) * hue).mul_add(6.0, m1)
+    } else if hue < 1.0 / 2.0 {
+        m2
+    } else if hue < 2.0 / 3.0 {
+        ((m2 - m1) * (2.0 / 3.0 - hue)).mul_add(6.0, m1)
+    } else {
+        m1
+    }
+}
+
+fn hsl_to_srgb([hue, saturation, lightness]: [f64; 3]) -> [f64; 3] {
+    let hue = hue.rem_euclid(360.0) / 360.0;
+    let saturation = saturation / 100.0;
+    let lightness = lightness / 100.0;
+
+    let m2 = if lightness <= 0.5 {
+        lightness * (saturation + 1.0)
+    } else {
+        lightness.mul_add(-saturation, lightness + saturation)
+    };
+    let m1 = lightness.mul_add(2.0, -m2);
+
+    [
+        hue_to_rgb(m1, m2, hue + 1.0 / 3.0),
+        hue_to_rgb(m1, m2, hue),
+        hue_to_rgb(m1, m2, hue - 1.0 / 3.0),
+    ]
+}
+
+fn rgb_hue(red: f64, green: f64, blue: f64, max: f64, delta: f64) -> f64 {
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == red {
+        60.0 * (green - blue) / delta
+    } else if max == green {
+        60.0 * (blue - red) / delta + 120.0
+    } else {
+        60.0 * (red - green) / delta + 240.0
+    };
+
+    hue.rem_euclid(360.0)
+}
+
+fn srgb_to_hsl([red, green, blue]: [f64; 3]) -> [f64; 3] {
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+    let delta = max - min;
+
+    let lightness = (max + min) / 2.0;
+
+    let saturation = if fuzzy_equals(lightness, 0.0) || fuzzy_equals(lightness, 1.0) {
+        0.0
+    } else {
+        (max - lightness) / lightness.min(1.0 - lightness)
+    };
+
+    [
+        rgb_hue(red, green, blue, max, delta),
+        saturation * 100.0,
+        lightness * 100.0,
+    ]
+}
+
+fn hwb_to_srgb([hue, whiteness, blackness]: [f64; 3]) -> [f64; 3] {
+    let mut whiteness = whiteness / 100.0;
+    let mut blackness = blackness / 100.0;
+
+    if whiteness + blackness >= 1.0 {
+        let sum = whiteness + blackness;
+        whiteness /= sum;
+        blackness /= sum;
+    }
+
+    hsl_to_srgb([hue, 100.0, 50.0])
+        .map(|channel| channel * (1.0 - whiteness - blackness) + whiteness)
+}
+
+fn srgb_to_hwb([red, green, blue]: [f64; 3]) -> [f64; 3] {
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+
+    [
+        rgb_hue(red, green, blue, max, max - min),
+        min * 100.0,
+        (1.0 - max) * 100.0,
+    ]
+}
+
+fn xyz_d50_to_lab(xyz: [f64; 3]) -> [f64; 3] {
+    let [fx, fy, fz] = [0, 1, 2].map(|idx| {
+        let value = xyz[idx] / D50[idx];
+
+        if value > LAB_EPSILON {
+            value.cbrt()
+        } else {
+            (LAB_KAPPA * value + 16.0) / 116.0
+        }
+    });
+
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+fn lab_to_xyz_d50([lightness, a, b]: [f64; 3]) -> [f64; 3] {
+    let fy = (lightness + 16.0) / 116.0;
+    let fx = a / 500.0 + fy;
+    let fz = fy - b / 200.0;
+
+    let from_f = |f: f64| {
+        let cubed = f.powi(3);
+        if cubed > LAB_EPSILON {
+            cubed
+        } else {
+            (116.0 * f - 16.0) / LAB_KAPPA
+        }
+    };
+
+    let y = if lightness > LAB_KAPPA * LAB_EPSILON {
+        fy.powi(3)
+    } else {
+        lightness / LAB_KAPPA
+    };
+
+    [from_f(fx) * D50[0], y * D50[1], from_f(fz) * D50[2]]
+}
+
+fn xyz_d65_to_oklab(xyz: [f64; 3]) -> [f64; 3] {
+    multiply(&LMS_TO_OKLAB, multiply(&XYZ_D65_TO_LMS, xyz).map(f64::cbrt))
+}
+
+fn oklab_to_xyz_d65(oklab: [f64; 3]) -> [f64; 3] {
+    multiply(
+        &LMS_TO_XYZ_D65,
+        multiply(&OKLAB_TO_LMS, oklab).map(|channel| channel.powi(3)),
+    )
+}
+
+fn lab_to_polar([lightness, a, b]: [f64; 3]) -> [f64; 3] {
+    [
+        lightness,
+        (a * a + b * b).sqrt(),
+        (b.atan2(a) * 180.0 / PI).rem_euclid(360.0),
+    ]
+}
+
+fn polar_to_lab([lightness, chroma, hue]: [f64; 3]) -> [f64; 3] {
+    let hue = hue * PI / 180.0;
+    [lightness, chroma * hue.cos(), chroma * hue.sin()]
+}
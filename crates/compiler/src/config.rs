@@ -0,0 +1,247 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{error::SassResult, Options, OutputStyle, PkgImporter};
+
+/// Project-wide settings read from a `grass.toml` file
+///
+/// The file is shared by the commandline interface and the library, so that
+/// build scripts can compile stylesheets exactly the way the CLI would.
+///
+/// ```toml
+/// load-paths = ["node_modules", "vendor/styles"]
+/// style = "compressed"
+/// pkg-importer = "node"
+///
+/// [entrypoints]
+/// "src/main.scss" = "dist/main.css"
+///
+/// [deprecations]
+/// silence = ["slash-div"]
+/// fatal = ["import"]
+///
+/// [source-map]
+/// enabled = true
+/// urls = "absolute"
+/// ```
+///
+/// Relative paths are resolved against the directory containing the config file.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// Paths to use when resolving imports
+    pub load_paths: Vec<PathBuf>,
+    /// The output style, if one was specified
+    pub style: Option<OutputStyle>,
+    /// Whether or not to silence warnings, if specified
+    pub quiet: Option<bool>,
+    /// The importer to use for `pkg:` URLs, if any
+    pub pkg_importer: Option<PkgImporter>,
+    /// Stylesheets to compile, mapped to the file their CSS should be written to
+    pub entrypoints: Vec<(PathBuf, PathBuf)>,
+    /// The `[deprecations]` section of the config file
+    ///
+    /// This is currently ignored by [`Config::apply`]
+    pub deprecations: DeprecationConfig,
+    /// The `[source-map]` section of the config file
+    ///
+    /// This is currently ignored by [`Config::apply`]
+    pub source_map: SourceMapConfig,
+}
+
+/// The `[deprecations]` section of a config file
+///
+/// These mirror the `--silence-deprecation` and `--fatal-deprecation` flags of
+/// `dart-sass`. `grass` does not currently emit deprecation warnings, so these
+/// settings are recorded but otherwise ignored.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default)]
+pub struct DeprecationConfig {
+    /// Deprecations that should not emit warnings
+    pub silence: Vec<String>,
+    /// Deprecations that should be treated as errors
+    pub fatal: Vec<String>,
+}
+
+/// The `[source-map]` section of a config file
+///
+/// `grass` does not currently generate source maps, so these settings are
+/// recorded but otherwise ignored.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default)]
+pub struct SourceMapConfig {
+    /// Whether to generate source maps
+    pub enabled: bool,
+    /// How to link from source maps to source files
+    pub urls: SourceMapUrls,
+    /// Embed source file contents in source maps
+    pub embed_sources: bool,
+    /// Embed source map contents in CSS
+    pub embed_source_map: bool,
+}
+
+/// How a source map should refer to the source files it was generated from
+///
+/// `grass` does not currently generate source maps, so this setting is
+/// ignored.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceMapUrls {
+    /// Use URLs relative to the location of the source map
+    #[default]
+    Relative,
+    /// Use absolute `file:` URLs
+    Absolute,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct RawConfig {
+    #[serde(default)]
+    load_paths: Vec<PathBuf>,
+    style: Option<String>,
+    quiet: Option<bool>,
+    pkg_importer: Option<String>,
+    #[serde(default)]
+    entrypoints: BTreeMap<PathBuf, PathBuf>,
+    #[serde(default)]
+    deprecations: RawDeprecationConfig,
+    #[serde(default)]
+    source_map: RawSourceMapConfig,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct RawDeprecationConfig {
+    #[serde(default)]
+    silence: Vec<String>,
+    #[serde(default)]
+    fatal: Vec<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct RawSourceMapConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    urls: SourceMapUrls,
+    #[serde(default)]
+    embed_sources: bool,
+    #[serde(default)]
+    embed_source_map: bool,
+}
+
+fn invalid_config(path: &Path, message: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        ErrorKind::InvalidData,
+        format!("{}: {}", path.to_string_lossy(), message),
+    )
+}
+
+pub(crate) fn parse_pkg_importer(pkg_importer: &str) -> Option<PkgImporter> {
+    match pkg_importer {
+        #[cfg(feature = "pkg-importer")]
+        "node" => Some(PkgImporter::Node),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_style(style: &str) -> Option<OutputStyle> {
+    match style.to_ascii_lowercase().as_str() {
+        "expanded" => Some(OutputStyle::Expanded),
+        "compressed" => Some(OutputStyle::Compressed),
+        "nested" => Some(OutputStyle::Nested),
+        "compact" => Some(OutputStyle::Compact),
+        _ => None,
+    }
+}
+
+impl Config {
+    /// Read and parse a config file from disk
+    pub fn from_path<P: AsRef<Path>>(path: P) -> SassResult<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+
+        Self::from_str_relative_to(&contents, path.parent().unwrap_or_else(|| Path::new("")))
+            .map_err(|e| invalid_config(path, e).into())
+    }
+
+    fn from_str_relative_to(contents: &str, base: &Path) -> Result<Self, String> {
+        let raw: RawConfig = toml::from_str(contents).map_err(|e| e.to_string())?;
+
+        let style = match raw.style {
+            Some(style) => Some(
+                parse_style(&style).ok_or_else(|| format!("unknown output style \"{}\"", style))?,
+            ),
+            None => None,
+        };
+
+        let pkg_importer = match raw.pkg_importer {
+            Some(pkg_importer) => Some(
+                parse_pkg_importer(&pkg_importer)
+                    .ok_or_else(|| format!("unknown package importer \"{}\"", pkg_importer))?,
+            ),
+            None => None,
+        };
+
+        let resolve = |path: PathBuf| {
+            if path.is_absolute() {
+                path
+            } else {
+                base.join(path)
+            }
+        };
+
+        Ok(Self {
+            load_paths: raw.load_paths.into_iter().map(resolve).collect(),
+            style,
+            quiet: raw.quiet,
+            pkg_importer,
+            entrypoints: raw
+                .entrypoints
+                .into_iter()
+                .map(|(input, output)| (resolve(input), resolve(output)))
+                .collect(),
+            deprecations: DeprecationConfig {
+                silence: raw.deprecations.silence,
+                fatal: raw.deprecations.fatal,
+            },
+            source_map: SourceMapConfig {
+                enabled: raw.source_map.enabled,
+                urls: raw.source_map.urls,
+                embed_sources: raw.source_map.embed_sources,
+                embed_source_map: raw.source_map.embed_source_map,
+            },
+        })
+    }
+
+    /// Apply the settings in this config on top of an existing set of options
+    ///
+    /// The [`Config::deprecations`] and [`Config::source_map`] sections have
+    /// no corresponding options, and are not applied
+    #[must_use]
+    pub fn apply<'a>(&self, mut options: Options<'a>) -> Options<'a> {
+        options = options.load_paths(&self.load_paths);
+
+        if let Some(style) = self.style {
+            options = options.style(style);
+        }
+
+        if let Some(quiet) = self.quiet {
+            options = options.quiet(quiet);
+        }
+
+        if let Some(pkg_importer) = self.pkg_importer {
+            options = options.pkg_importer(pkg_importer);
+        }
+
+        options
+    }
+}
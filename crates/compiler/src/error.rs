@@ -38,6 +38,7 @@ impl SassError {
                 message,
                 loc,
                 unicode,
+                ..
             } => PublicSassErrorKind::ParseError {
                 message,
                 loc,
@@ -49,18 +50,35 @@ impl SassError {
         }
     }
 
-    pub(crate) fn raw(self) -> (String, Span) {
+    pub(crate) fn raw(self) -> (String, Span, Vec<(Span, &'static str)>) {
         match self.kind {
-            SassErrorKind::Raw(string, span) => (string, span),
+            SassErrorKind::Raw(string, span, related) => (string, span, related),
             e => unreachable!("unable to get raw of {:?}", e),
         }
     }
 
-    pub(crate) const fn from_loc(message: String, loc: SpanLoc, unicode: bool) -> Self {
+    /// Attaches an additional span to a raw error, which is shown along with a
+    /// short `label` after the span the error occurred at
+    pub(crate) fn with_related(mut self: Box<Self>, span: Span, label: &'static str) -> Box<Self> {
+        match &mut self.kind {
+            SassErrorKind::Raw(_, _, related) => related.push((span, label)),
+            e => unreachable!("unable to add related span to {:?}", e),
+        }
+
+        self
+    }
+
+    pub(crate) fn from_loc(
+        message: String,
+        loc: SpanLoc,
+        related: Vec<(SpanLoc, &'static str)>,
+        unicode: bool,
+    ) -> Self {
         SassError {
             kind: SassErrorKind::ParseError {
                 message,
                 loc,
+                related,
                 unicode,
             },
         }
@@ -102,12 +120,14 @@ pub enum PublicSassErrorKind {
 #[derive(Debug, Clone)]
 enum SassErrorKind {
     /// A raw error with no additional metadata
-    /// It contains only a `String` message and
-    /// a span
-    Raw(String, Span),
+    /// It contains only a `String` message, the
+    /// span it occurred at, and any labelled spans
+    /// related to it
+    Raw(String, Span, Vec<(Span, &'static str)>),
     ParseError {
         message: String,
         loc: SpanLoc,
+        related: Vec<(SpanLoc, &'static str)>,
         unicode: bool,
     },
     // we put `IoError`s in an `Arc` to allow them to be cloneable
@@ -121,47 +141,45 @@ impl Display for SassError {
     // TODO: integrate with codemap-diagnostics
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (message, loc, unicode) = match &self.kind {
+        let (message, loc, related, unicode) = match &self.kind {
             SassErrorKind::ParseError {
                 message,
                 loc,
+                related,
                 unicode,
-            } => (message, loc, *unicode),
+            } => (message, loc, related, *unicode),
             SassErrorKind::FromUtf8Error(..) => return writeln!(f, "Error: Invalid UTF-8."),
             SassErrorKind::IoError(s) => return writeln!(f, "Error: {}", s),
             SassErrorKind::Raw(..) => unreachable!(),
         };
 
-        let first_bar = if unicode { '╷' } else { ',' };
-        let second_bar = if unicode { '│' } else { '|' };
-        let third_bar = if unicode { '│' } else { '|' };
-        let fourth_bar = if unicode { '╵' } else { '\'' };
-
         let line = loc.begin.line + 1;
         let col = loc.begin.column + 1;
         writeln!(f, "Error: {}", message)?;
-        let padding = vec![' '; format!("{}", line).len() + 1]
+
+        let line_number_width = related
             .iter()
-            .collect::<String>();
-        writeln!(f, "{}{}", padding, first_bar)?;
-        writeln!(
-            f,
-            "{} {} {}",
-            line,
-            second_bar,
-            loc.file.source_line(loc.begin.line)
-        )?;
-        writeln!(
-            f,
-            "{}{} {}{}",
-            padding,
-            third_bar,
-            vec![' '; loc.begin.column].iter().collect::<String>(),
-            vec!['^'; loc.end.column.max(loc.begin.column) - loc.begin.column.min(loc.end.column)]
-                .iter()
-                .collect::<String>()
-        )?;
-        writeln!(f, "{}{}", padding, fourth_bar)?;
+            .map(|(loc, ..)| loc.begin.line + 1)
+            .chain(std::iter::once(line))
+            .max()
+            .unwrap_or(line)
+            .to_string()
+            .len();
+
+        write_snippet(f, loc, '^', None, line_number_width, unicode)?;
+
+        let related_marker = if unicode { '─' } else { '-' };
+
+        for (loc, label) in related {
+            write_snippet(
+                f,
+                loc,
+                related_marker,
+                Some(label),
+                line_number_width,
+                unicode,
+            )?;
+        }
 
         if unicode {
             writeln!(f, "./{}:{}:{}", loc.file.name(), line, col)?;
@@ -172,6 +190,49 @@ impl Display for SassError {
     }
 }
 
+/// Writes the source line `loc` begins on, with the span underlined using
+/// `marker` and followed by an optional `label`
+fn write_snippet(
+    f: &mut fmt::Formatter<'_>,
+    loc: &SpanLoc,
+    marker: char,
+    label: Option<&str>,
+    line_number_width: usize,
+    unicode: bool,
+) -> fmt::Result {
+    let first_bar = if unicode { '╷' } else { ',' };
+    let second_bar = if unicode { '│' } else { '|' };
+    let third_bar = if unicode { '│' } else { '|' };
+    let fourth_bar = if unicode { '╵' } else { '\'' };
+
+    let line = loc.begin.line + 1;
+    let padding = vec![' '; line_number_width + 1].iter().collect::<String>();
+    writeln!(f, "{}{}", padding, first_bar)?;
+    writeln!(
+        f,
+        "{:>width$} {} {}",
+        line,
+        second_bar,
+        loc.file.source_line(loc.begin.line),
+        width = line_number_width
+    )?;
+    write!(
+        f,
+        "{}{} {}{}",
+        padding,
+        third_bar,
+        vec![' '; loc.begin.column].iter().collect::<String>(),
+        vec![marker; loc.end.column.max(loc.begin.column) - loc.begin.column.min(loc.end.column)]
+            .iter()
+            .collect::<String>()
+    )?;
+    match label {
+        Some(label) => writeln!(f, " {}", label)?,
+        None => writeln!(f)?,
+    }
+    writeln!(f, "{}{}", padding, fourth_bar)
+}
+
 impl From<io::Error> for Box<SassError> {
     #[inline]
     fn from(error: io::Error) -> Box<SassError> {
@@ -197,7 +258,7 @@ impl From<(&str, Span)> for Box<SassError> {
     #[inline]
     fn from(error: (&str, Span)) -> Box<SassError> {
         Box::new(SassError {
-            kind: SassErrorKind::Raw(error.0.to_owned(), error.1),
+            kind: SassErrorKind::Raw(error.0.to_owned(), error.1, Vec::new()),
         })
     }
 }
@@ -206,7 +267,7 @@ impl From<(String, Span)> for Box<SassError> {
     #[inline]
     fn from(error: (String, Span)) -> Box<SassError> {
         Box::new(SassError {
-            kind: SassErrorKind::Raw(error.0, error.1),
+            kind: SassErrorKind::Raw(error.0, error.1, Vec::new()),
         })
     }
 }
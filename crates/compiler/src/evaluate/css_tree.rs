@@ -93,6 +93,15 @@ impl CssTree {
                 Some(CssStmt::Supports(supports, ..)) => {
                     supports.body.push(child);
                 }
+                Some(CssStmt::Layer(layer, ..)) => {
+                    layer.body.push(child);
+                }
+                Some(CssStmt::Container(container, ..)) => {
+                    container.body.push(child);
+                }
+                Some(CssStmt::Scope(scope, ..)) => {
+                    scope.body.push(child);
+                }
                 Some(CssStmt::KeyframesRuleSet(keyframes)) => {
                     keyframes.body.push(child);
                 }
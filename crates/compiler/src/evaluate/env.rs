@@ -1,7 +1,7 @@
 use codemap::{Span, Spanned};
 
 use crate::{
-    ast::{AstForwardRule, Configuration, Mixin},
+    ast::{AstForwardRule, Configuration, CssStmt, Mixin},
     builtin::modules::{ForwardedModule, Module, ModuleScope, Modules},
     common::Identifier,
     error::SassResult,
@@ -57,6 +57,7 @@ impl Environment {
             scope: ModuleScope::new(),
             upstream: Vec::new(),
             extension_store: ExtensionStore::new(span),
+            css: Vec::new(),
             env: self.clone(),
         }
     }
@@ -391,9 +392,19 @@ impl Environment {
         Ok(())
     }
 
-    pub fn to_module(self, extension_store: ExtensionStore) -> Arc<RefCell<Module>> {
+    pub fn to_module(
+        self,
+        extension_store: ExtensionStore,
+        upstream: Vec<Arc<RefCell<Module>>>,
+        css: Vec<CssStmt>,
+    ) -> Arc<RefCell<Module>> {
         debug_assert!(self.at_root());
 
-        Arc::new(RefCell::new(Module::new_env(self, extension_store)))
+        Arc::new(RefCell::new(Module::new_env(
+            self,
+            extension_store,
+            upstream,
+            css,
+        )))
     }
 }
@@ -1,9 +1,9 @@
 use std::{
     cell::{Cell, RefCell},
-    collections::{BTreeMap, BTreeSet, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     ffi::OsStr,
     fmt,
-    iter::FromIterator,
+    iter::{self, FromIterator},
     mem,
     path::{Path, PathBuf},
     sync::Arc,
@@ -31,16 +31,18 @@ use crate::{
         StylesheetParser,
     },
     selector::{
-        ComplexSelectorComponent, ExtendRule, ExtendedSelector, ExtensionStore, SelectorList,
-        SelectorParser,
+        ComplexSelector, ComplexSelectorComponent, ExtendRule, ExtendedSelector, Extension,
+        ExtensionStore, SelectorList, SelectorParser, SimpleSelector,
     },
-    utils::{to_sentence, trim_ascii},
+    utils::{to_sentence, trim_ascii, Random},
     value::{
         ArgList, CalculationArg, CalculationName, Number, SassCalculation, SassFunction, SassMap,
         SassNumber, UserDefinedFunction, Value,
     },
-    ContextFlags, InputSyntax, Options,
+    ContextFlags, InputSyntax, Nesting, Options,
 };
+#[cfg(feature = "pkg-importer")]
+use crate::{importer::NodePackageImporter, PkgImporter};
 
 use super::{
     bin_op::{add, cmp, div, mul, rem, single_eq, sub},
@@ -99,15 +101,45 @@ pub(crate) struct CallableContentBlock {
     env: Environment,
 }
 
+/// The configuration a module was first loaded with
+#[derive(Debug)]
+struct LoadedConfiguration {
+    configuration: Arc<RefCell<Configuration>>,
+    /// The configured values as they were before the module was executed.
+    /// Executing a module removes the values it uses from `configuration`.
+    values: BTreeMap<Identifier, ConfiguredValue>,
+}
+
+impl LoadedConfiguration {
+    fn new(configuration: Arc<RefCell<Configuration>>) -> Self {
+        let values = (*configuration)
+            .borrow()
+            .values
+            .iter()
+            .into_iter()
+            .collect();
+
+        Self {
+            configuration,
+            values,
+        }
+    }
+}
+
 pub(crate) struct Visitor<'a> {
     pub declaration_name: Option<String>,
     pub flags: ContextFlags,
     pub env: Environment,
     pub style_rule_ignoring_at_root: Option<ExtendedSelector>,
+    /// The number of style rules enclosing the current position in the source
+    style_rule_depth: usize,
     // avoid emitting duplicate warnings for the same span
     pub warnings_emitted: HashSet<Span>,
     pub media_queries: Option<Vec<MediaQuery>>,
     pub media_query_sources: Option<IndexSet<MediaQuery>>,
+    /// The span of the innermost `@media` rule being evaluated, up to the end of
+    /// its query, which is used to point to it in `@extend` errors
+    media_query_span: Option<Span>,
     pub extender: ExtensionStore,
     pub current_import_path: PathBuf,
     pub is_plain_css: bool,
@@ -124,6 +156,19 @@ pub(crate) struct Visitor<'a> {
     /// has been seen in the past. In the majority of cases, files are imported
     /// at most once.
     files_seen: BTreeSet<PathBuf>,
+    /// Modules that have already been executed, keyed by their resolved path.
+    /// Each module is executed at most once, no matter how many times it is loaded.
+    modules: BTreeMap<PathBuf, Arc<RefCell<Module>>>,
+    /// The configuration each module in `modules` was first loaded with
+    module_configurations: BTreeMap<PathBuf, LoadedConfiguration>,
+    /// The chain of stylesheets currently being loaded, starting with the
+    /// entrypoint. Used to detect `@use`, `@forward`, and `@import` cycles.
+    active_modules: Vec<PathBuf>,
+    /// The user-defined modules loaded by the module currently being executed
+    upstream_modules: Vec<Arc<RefCell<Module>>>,
+    /// The generator backing `random()` and `unique-id()`, shared by every
+    /// module in the compilation
+    pub random: Random,
 }
 
 impl<'a> Visitor<'a> {
@@ -143,10 +188,12 @@ impl<'a> Visitor<'a> {
         Self {
             declaration_name: None,
             style_rule_ignoring_at_root: None,
+            style_rule_depth: 0,
             flags,
             warnings_emitted: HashSet::new(),
             media_queries: None,
             media_query_sources: None,
+            media_query_span: None,
             env: Environment::new(),
             extender,
             css_tree: CssTree::new(),
@@ -160,6 +207,11 @@ impl<'a> Visitor<'a> {
             map,
             import_cache: BTreeMap::new(),
             files_seen: BTreeSet::new(),
+            modules: BTreeMap::new(),
+            module_configurations: BTreeMap::new(),
+            active_modules: vec![path.to_path_buf()],
+            upstream_modules: Vec::new(),
+            random: Random::from_seed(options.random_seed),
         }
     }
 
@@ -179,14 +231,209 @@ impl<'a> Visitor<'a> {
         Ok(())
     }
 
-    pub fn finish(mut self) -> Vec<CssStmt> {
-        let mut finished_tree = self.css_tree.finish();
-        if self.import_nodes.is_empty() {
+    pub fn finish(mut self) -> SassResult<Vec<CssStmt>> {
+        let css_tree = mem::replace(&mut self.css_tree, CssTree::new());
+        let root_css = Self::module_css(css_tree, mem::take(&mut self.import_nodes));
+
+        let mut finished_tree = self.combine_css(root_css)?;
+
+        if self.options.nesting == Nesting::Preserve {
+            finished_tree = CssStmt::unnest_extended_rules(finished_tree);
+        }
+
+        Ok(finished_tree)
+    }
+
+    /// Flattens the CSS emitted by a single module, with its plain CSS imports first
+    fn module_css(css_tree: CssTree, mut import_nodes: Vec<CssStmt>) -> Vec<CssStmt> {
+        let mut finished_tree = css_tree.finish();
+
+        if import_nodes.is_empty() {
             finished_tree
         } else {
-            self.import_nodes.append(&mut finished_tree);
-            self.import_nodes
+            import_nodes.append(&mut finished_tree);
+            import_nodes
+        }
+    }
+
+    /// Combines the CSS of the entrypoint and every module it transitively uses
+    /// into a single list of statements.
+    ///
+    /// Each module's CSS is emitted exactly once, after the CSS of all the modules
+    /// it uses, and plain CSS imports from every module are hoisted to the top.
+    fn combine_css(&mut self, root_css: Vec<CssStmt>) -> SassResult<Vec<CssStmt>> {
+        let sorted = self.topological_modules();
+
+        self.extend_modules(&sorted)?;
+
+        let mut imports = Vec::new();
+        let mut css = Vec::new();
+
+        for mut module_css in sorted
+            .iter()
+            .map(|module| (**module).borrow_mut().take_css())
+            .chain(iter::once(root_css))
+        {
+            let index_after_imports = module_css
+                .iter()
+                .position(|stmt| !matches!(stmt, CssStmt::Import(..)))
+                .unwrap_or(module_css.len());
+
+            css.extend(module_css.drain(index_after_imports..));
+            imports.append(&mut module_css);
+        }
+
+        imports.append(&mut css);
+        Ok(imports)
+    }
+
+    /// Returns all modules transitively used by the entrypoint, such that each
+    /// module comes after all the modules it uses
+    fn topological_modules(&self) -> Vec<Arc<RefCell<Module>>> {
+        fn visit_modules(
+            modules: &[Arc<RefCell<Module>>],
+            seen: &mut HashSet<*const RefCell<Module>>,
+            sorted: &mut Vec<Arc<RefCell<Module>>>,
+        ) {
+            for module in modules {
+                if seen.insert(Arc::as_ptr(module)) {
+                    visit_modules((**module).borrow().upstream(), seen, sorted);
+                    sorted.push(Arc::clone(module));
+                }
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut sorted = Vec::new();
+
+        visit_modules(&self.upstream_modules, &mut seen, &mut sorted);
+
+        sorted
+    }
+
+    /// Applies the extensions defined in each module to the selectors of all the
+    /// modules upstream of it.
+    ///
+    /// `sorted` must be ordered such that each module comes after all the modules
+    /// it uses, as returned by `Visitor::topological_modules`.
+    ///
+    /// Returns an error if a mandatory extension's target doesn't appear in the
+    /// module that defines it or in any module upstream of it.
+    fn extend_modules(&self, sorted: &[Arc<RefCell<Module>>]) -> SassResult<()> {
+        // The modules whose extensions apply to each module, keyed by its address
+        let mut downstream_modules: HashMap<*const RefCell<Module>, Vec<Arc<RefCell<Module>>>> =
+            HashMap::new();
+
+        let extended_by_root: HashSet<*const RefCell<Module>> = if self.extender.is_empty() {
+            HashSet::new()
+        } else {
+            self.upstream_modules.iter().map(Arc::as_ptr).collect()
+        };
+
+        // Each module's extensions are added to all the modules upstream of it, so
+        // an extension is satisfied if its target appears in any of them
+        let mut unsatisfied_extensions = Vec::new();
+        let mut satisfied_extensions = HashSet::new();
+
+        Self::check_extensions(
+            &self.extender,
+            &mut unsatisfied_extensions,
+            &mut satisfied_extensions,
+        );
+
+        for module in sorted.iter().rev() {
+            let key = Arc::as_ptr(module);
+            let downstream = downstream_modules.remove(&key).unwrap_or_default();
+
+            {
+                let downstream = downstream
+                    .iter()
+                    .map(|module| (**module).borrow())
+                    .collect::<Vec<_>>();
+
+                let mut extension_stores = Vec::new();
+
+                if extended_by_root.contains(&key) {
+                    extension_stores.push(&self.extender);
+                }
+
+                extension_stores.extend(downstream.iter().filter_map(|m| m.extension_store()));
+
+                if !extension_stores.is_empty() {
+                    if let Some(extension_store) = (**module).borrow_mut().extension_store_mut() {
+                        extension_store.add_extensions(&extension_stores)?;
+                    }
+                }
+            }
+
+            let module_ref = (**module).borrow();
+
+            let extension_store = match module_ref.extension_store() {
+                Some(extension_store) if !extension_store.is_empty() => extension_store,
+                Some(..) | None => continue,
+            };
+
+            Self::check_extensions(
+                extension_store,
+                &mut unsatisfied_extensions,
+                &mut satisfied_extensions,
+            );
+
+            for upstream in module_ref.upstream() {
+                downstream_modules
+                    .entry(Arc::as_ptr(upstream))
+                    .or_default()
+                    .push(Arc::clone(module));
+            }
         }
+
+        let unsatisfied_extension = unsatisfied_extensions
+            .into_iter()
+            .filter(|extension| !satisfied_extensions.contains(&Self::extension_key(extension)))
+            .min_by_key(|extension| extension.span.low());
+
+        match unsatisfied_extension {
+            Some(extension) => Err(extension.with_media_span(
+                (
+                    format!(
+                        "The target selector was not found.\nUse \"@extend {} !optional\" to avoid this error.",
+                        extension.target.as_ref().unwrap()
+                    ),
+                    extension.span,
+                )
+                    .into(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Records which of the mandatory extensions in `extension_store` have a
+    /// target that appears in its selectors
+    fn check_extensions(
+        extension_store: &ExtensionStore,
+        unsatisfied_extensions: &mut Vec<Extension>,
+        satisfied_extensions: &mut HashSet<(Span, ComplexSelector, Option<SimpleSelector>)>,
+    ) {
+        for extension in extension_store
+            .extensions_where_target(|target| extension_store.contains_simple_selector(target))
+        {
+            satisfied_extensions.insert(Self::extension_key(&extension));
+        }
+
+        unsatisfied_extensions.extend(
+            extension_store.extensions_where_target(|target| {
+                !extension_store.contains_simple_selector(target)
+            }),
+        );
+    }
+
+    /// Identifies an extension across the extension stores it's been added to
+    fn extension_key(extension: &Extension) -> (Span, ComplexSelector, Option<SimpleSelector>) {
+        (
+            extension.span,
+            extension.extender.clone(),
+            extension.target.clone(),
+        )
     }
 
     fn visit_return_rule(&mut self, ret: AstReturn) -> SassResult<Option<Value>> {
@@ -241,6 +488,18 @@ impl<'a> Visitor<'a> {
                 self.visit_supports_rule(supports_rule)?;
                 Ok(None)
             }
+            AstStmt::Layer(layer_rule) => {
+                self.visit_layer_rule(layer_rule)?;
+                Ok(None)
+            }
+            AstStmt::Container(container_rule) => {
+                self.visit_container_rule(container_rule)?;
+                Ok(None)
+            }
+            AstStmt::Scope(scope_rule) => {
+                self.visit_scope_rule(scope_rule)?;
+                Ok(None)
+            }
         }
     }
 
@@ -474,17 +733,20 @@ impl<'a> Visitor<'a> {
             SupportsRule {
                 params: condition,
                 body: Vec::new(),
+                span: supports_rule.span,
+                depth: self.bubbling_depth(),
             },
             false,
         );
 
         let children = supports_rule.children;
+        let preserves_nesting = self.preserves_nesting();
 
         self.with_parent(
             css_supports_rule,
             true,
             |visitor| {
-                if !visitor.style_rule_exists() {
+                if !visitor.style_rule_exists() || preserves_nesting {
                     for stmt in children {
                         let result = visitor.visit_stmt(stmt)?;
                         debug_assert!(result.is_none());
@@ -495,12 +757,7 @@ impl<'a> Visitor<'a> {
                     //
                     // For example, "a {@supports (a: b) {b: c}}" should produce "@supports
                     // (a: b) {a {b: c}}".
-                    let selector = visitor.style_rule_ignoring_at_root.clone().unwrap();
-                    let ruleset = CssStmt::RuleSet {
-                        selector,
-                        body: Vec::new(),
-                        is_group_end: false,
-                    };
+                    let ruleset = visitor.copy_parent_style_rule();
 
                     visitor.with_parent(
                         ruleset,
@@ -519,12 +776,231 @@ impl<'a> Visitor<'a> {
 
                 Ok(())
             },
-            CssStmt::is_style_rule,
+            |stmt| !preserves_nesting && stmt.is_style_rule(),
         )?;
 
         Ok(())
     }
 
+    /// Visits the children of an at-rule that bubbles out of style rules,
+    /// allowing declarations directly inside of it
+    fn visit_bubbling_at_rule_children(&mut self, children: Vec<AstStmt>) -> SassResult<()> {
+        let was_in_unknown_at_rule = self.flags.in_unknown_at_rule();
+        self.flags.set(ContextFlags::IN_UNKNOWN_AT_RULE, true);
+
+        let result = if !self.style_rule_exists() || self.preserves_nesting() {
+            children.into_iter().try_for_each(|stmt| {
+                self.visit_stmt(stmt)
+                    .map(|result| debug_assert!(result.is_none()))
+            })
+        } else {
+            // If we're in a style rule, copy it into the at-rule so that
+            // declarations immediately inside it have somewhere to go.
+            //
+            // For example, "a {@layer b {c: d}}" should produce "@layer b {a {c: d}}".
+            let ruleset = self.copy_parent_style_rule();
+
+            self.with_parent(
+                ruleset,
+                false,
+                |visitor| {
+                    for stmt in children {
+                        let result = visitor.visit_stmt(stmt)?;
+                        debug_assert!(result.is_none());
+                    }
+
+                    Ok(())
+                },
+                |_| false,
+            )
+        };
+
+        self.flags
+            .set(ContextFlags::IN_UNKNOWN_AT_RULE, was_in_unknown_at_rule);
+
+        result
+    }
+
+    /// The name of the `@layer` block that a layer block added at the current
+    /// position would be nested within, ignoring any style rules in between.
+    ///
+    /// Returns `None` if there is no such block, or if it is anonymous
+    fn enclosing_layer_name(&self) -> Option<String> {
+        let mut parent = self.parent?;
+
+        while parent != CssTree::ROOT {
+            match self.css_tree.get(parent).as_ref() {
+                Some(CssStmt::RuleSet { .. }) => {
+                    parent = *self.css_tree.child_to_parent.get(&parent)?;
+                }
+                Some(CssStmt::Layer(layer_rule, ..)) => {
+                    return layer_rule.block_name().map(ToOwned::to_owned)
+                }
+                _ => return None,
+            }
+        }
+
+        None
+    }
+
+    fn visit_layer_rule(&mut self, layer_rule: AstLayerRule) -> SassResult<()> {
+        if self.declaration_name.is_some() {
+            return Err((
+                "Layer rules may not be used within nested declarations.",
+                layer_rule.span,
+            )
+                .into());
+        }
+
+        let mut names = match layer_rule.names {
+            Some(names) => {
+                let resolved = self.perform_interpolation(names, true)?;
+                LayerRule::parse_names(&resolved, layer_rule.names_span)?
+            }
+            None => Vec::new(),
+        };
+
+        let children = match layer_rule.children {
+            Some(children) => children,
+            None => {
+                // A statement only declares the order of the given layers, so it
+                // just needs to escape any enclosing style rules
+                let stmt = CssStmt::Layer(
+                    LayerRule {
+                        names,
+                        body: Vec::new(),
+                        has_body: false,
+                        span: layer_rule.span,
+                        depth: self.bubbling_depth(),
+                    },
+                    false,
+                );
+
+                let preserves_nesting = self.preserves_nesting();
+                self.add_child(
+                    stmt,
+                    Some(|stmt: &CssStmt| !preserves_nesting && stmt.is_style_rule()),
+                );
+
+                return Ok(());
+            }
+        };
+
+        if names.len() > 1 {
+            return Err((
+                "@layer blocks may only declare a single name.",
+                layer_rule.names_span,
+            )
+                .into());
+        }
+
+        // A named layer nested within another named layer is equivalent to
+        // a single layer with the names joined by a period. For example,
+        // "@layer a {@layer b {c {d: e}}}" should produce "@layer a.b {c {d: e}}"
+        let preserves_nesting = self.preserves_nesting();
+
+        let enclosing_name = match names.first() {
+            Some(..) if !preserves_nesting => self.enclosing_layer_name(),
+            Some(..) | None => None,
+        };
+
+        if let Some(enclosing_name) = &enclosing_name {
+            names[0] = format!("{}.{}", enclosing_name, names[0]);
+        }
+
+        let css_layer_rule = CssStmt::Layer(
+            LayerRule {
+                names,
+                body: Vec::new(),
+                has_body: true,
+                span: layer_rule.span,
+                depth: self.bubbling_depth(),
+            },
+            false,
+        );
+
+        self.with_parent(
+            css_layer_rule,
+            true,
+            |visitor| visitor.visit_bubbling_at_rule_children(children),
+            |stmt| match stmt {
+                CssStmt::RuleSet { .. } => !preserves_nesting,
+                CssStmt::Layer(layer_rule, ..) => {
+                    enclosing_name.is_some() && layer_rule.block_name() == enclosing_name.as_deref()
+                }
+                _ => false,
+            },
+        )
+    }
+
+    fn visit_container_rule(&mut self, container_rule: AstContainerRule) -> SassResult<()> {
+        if self.declaration_name.is_some() {
+            return Err((
+                "Container rules may not be used within nested declarations.",
+                container_rule.span,
+            )
+                .into());
+        }
+
+        let resolved = self.perform_interpolation(container_rule.query, true)?;
+        let query = ContainerQuery::parse_list(&resolved, container_rule.query_span)?;
+
+        let children = container_rule.children;
+
+        let css_container_rule = CssStmt::Container(
+            ContainerRule {
+                query,
+                body: Vec::new(),
+                span: container_rule.span,
+                depth: self.bubbling_depth(),
+            },
+            false,
+        );
+
+        let preserves_nesting = self.preserves_nesting();
+
+        self.with_parent(
+            css_container_rule,
+            true,
+            |visitor| visitor.visit_bubbling_at_rule_children(children),
+            |stmt| !preserves_nesting && stmt.is_style_rule(),
+        )
+    }
+
+    fn visit_scope_rule(&mut self, scope_rule: AstScopeRule) -> SassResult<()> {
+        if self.declaration_name.is_some() {
+            return Err((
+                "Scope rules may not be used within nested declarations.",
+                scope_rule.span,
+            )
+                .into());
+        }
+
+        let resolved = self.perform_interpolation(scope_rule.prelude, true)?;
+        let prelude = ScopePrelude::parse(&resolved, scope_rule.prelude_span)?;
+
+        let children = scope_rule.children;
+
+        let css_scope_rule = CssStmt::Scope(
+            ScopeRule {
+                prelude,
+                body: Vec::new(),
+                span: scope_rule.span,
+                depth: self.bubbling_depth(),
+            },
+            false,
+        );
+
+        let preserves_nesting = self.preserves_nesting();
+
+        self.with_parent(
+            css_scope_rule,
+            true,
+            |visitor| visitor.visit_bubbling_at_rule_children(children),
+            |stmt| !preserves_nesting && stmt.is_style_rule(),
+        )
+    }
+
     fn execute(
         &mut self,
         stylesheet: StyleSheet,
@@ -534,12 +1010,20 @@ impl<'a> Visitor<'a> {
     ) -> SassResult<Arc<RefCell<Module>>> {
         let env = Environment::new();
         let mut extension_store = ExtensionStore::new(self.span_before);
+        let mut css_tree = CssTree::new();
+        let mut import_nodes = Vec::new();
+        let mut upstream = Vec::new();
 
         self.with_environment::<SassResult<()>, _>(env.new_closure(), |visitor| {
             let old_parent = visitor.parent;
             mem::swap(&mut visitor.extender, &mut extension_store);
+            mem::swap(&mut visitor.css_tree, &mut css_tree);
+            mem::swap(&mut visitor.import_nodes, &mut import_nodes);
+            mem::swap(&mut visitor.upstream_modules, &mut upstream);
             let old_style_rule = visitor.style_rule_ignoring_at_root.take();
+            let old_style_rule_depth = mem::take(&mut visitor.style_rule_depth);
             let old_media_queries = visitor.media_queries.take();
+            let old_media_query_span = visitor.media_query_span.take();
             let old_declaration_name = visitor.declaration_name.take();
             let old_in_unknown_at_rule = visitor.flags.in_unknown_at_rule();
             let old_at_root_excluding_style_rule = visitor.flags.at_root_excluding_style_rule();
@@ -556,7 +1040,7 @@ impl<'a> Visitor<'a> {
                 .set(ContextFlags::AT_ROOT_EXCLUDING_STYLE_RULE, false);
             visitor.flags.set(ContextFlags::IN_KEYFRAMES, false);
 
-            visitor.visit_stylesheet(stylesheet)?;
+            let result = visitor.visit_stylesheet(stylesheet);
 
             // visitor.importer = old_importer;
             // visitor.stylesheet = old_stylesheet;
@@ -565,8 +1049,13 @@ impl<'a> Visitor<'a> {
             // visitor.end_of_imports = old_end_of_imports;
             // visitor.out_of_order_imports = old_out_of_order_imports;
             mem::swap(&mut visitor.extender, &mut extension_store);
+            mem::swap(&mut visitor.css_tree, &mut css_tree);
+            mem::swap(&mut visitor.import_nodes, &mut import_nodes);
+            mem::swap(&mut visitor.upstream_modules, &mut upstream);
             visitor.style_rule_ignoring_at_root = old_style_rule;
+            visitor.style_rule_depth = old_style_rule_depth;
             visitor.media_queries = old_media_queries;
+            visitor.media_query_span = old_media_query_span;
             visitor.declaration_name = old_declaration_name;
             visitor
                 .flags
@@ -582,23 +1071,17 @@ impl<'a> Visitor<'a> {
                 visitor.configuration = old_config;
             }
 
-            Ok(())
+            result
         })?;
 
-        let module = env.to_module(extension_store);
+        let css = Self::module_css(css_tree, import_nodes);
+        let module = env.to_module(extension_store, upstream, css);
 
         Ok(module)
     }
 
-    pub fn load_module(
-        &mut self,
-        url: &Path,
-        configuration: Option<Arc<RefCell<Configuration>>>,
-        names_in_errors: bool,
-        span: Span,
-        callback: impl Fn(&mut Self, Arc<RefCell<Module>>, StyleSheet) -> SassResult<()>,
-    ) -> SassResult<()> {
-        let builtin = match url.to_string_lossy().as_ref() {
+    fn builtin_module(url: &str) -> Option<Module> {
+        match url {
             "sass:color" => Some(declare_module_color()),
             "sass:list" => Some(declare_module_list()),
             "sass:map" => Some(declare_module_map()),
@@ -607,28 +1090,102 @@ impl<'a> Visitor<'a> {
             "sass:selector" => Some(declare_module_selector()),
             "sass:string" => Some(declare_module_string()),
             _ => None,
+        }
+    }
+
+    fn assert_builtin_not_configured(
+        url: &str,
+        configuration: Option<&Arc<RefCell<Configuration>>>,
+        names_in_errors: bool,
+    ) -> SassResult<()> {
+        let configuration = match configuration {
+            Some(configuration) => (**configuration).borrow(),
+            None => return Ok(()),
         };
 
-        if let Some(builtin) = builtin {
-            // todo: lots of ugly unwraps here
-            if configuration.is_some()
-                && !(**configuration.as_ref().unwrap()).borrow().is_implicit()
-            {
-                let msg = if names_in_errors {
-                    format!(
-                        "Built-in module {} can't be configured.",
-                        url.to_string_lossy()
-                    )
-                } else {
-                    "Built-in modules can't be configured.".to_owned()
-                };
+        let span = match configuration.span {
+            Some(span) => span,
+            // implicit configurations may be passed to built-in modules
+            None => return Ok(()),
+        };
 
-                return Err((
-                    msg,
-                    (**configuration.as_ref().unwrap()).borrow().span.unwrap(),
-                )
-                    .into());
-            }
+        let msg = if names_in_errors {
+            format!("Built-in module {} can't be configured.", url)
+        } else {
+            "Built-in modules can't be configured.".to_owned()
+        };
+
+        Err((msg, span).into())
+    }
+
+    fn assert_not_module_loop(
+        &self,
+        url: &Path,
+        names_in_errors: bool,
+        span: Span,
+    ) -> SassResult<()> {
+        if !self.active_modules.iter().any(|active| active == url) {
+            return Ok(());
+        }
+
+        let message = if names_in_errors {
+            format!(
+                "Module loop: {} is already being loaded.",
+                url.to_string_lossy()
+            )
+        } else {
+            "Module loop: this module is already being loaded.".to_owned()
+        };
+
+        Err(self.load_loop_error(&message, url, span))
+    }
+
+    /// A module is only executed once, so an explicit configuration is an error
+    /// if the module has already been loaded with a different one
+    fn assert_not_reconfigured(
+        &self,
+        url: &Path,
+        configuration: &Arc<RefCell<Configuration>>,
+        names_in_errors: bool,
+        span: Span,
+    ) -> SassResult<()> {
+        let loaded_configuration = match self.module_configurations.get(url) {
+            Some(loaded) => &loaded.configuration,
+            None => return Ok(()),
+        };
+
+        if (**configuration).borrow().is_implicit()
+            || Configuration::same_original(loaded_configuration, configuration)
+        {
+            return Ok(());
+        }
+
+        let message = if names_in_errors {
+            format!(
+                "{} was already loaded, so it can't be configured using \"with\".",
+                url.to_string_lossy()
+            )
+        } else {
+            "This module was already loaded, so it can't be configured using \"with\".".to_owned()
+        };
+
+        Err((message, span).into())
+    }
+
+    pub fn load_module(
+        &mut self,
+        url: &Path,
+        configuration: Option<Arc<RefCell<Configuration>>>,
+        names_in_errors: bool,
+        span: Span,
+        callback: impl Fn(&mut Self, Arc<RefCell<Module>>, StyleSheet) -> SassResult<()>,
+    ) -> SassResult<()> {
+        if let Some(builtin) = Self::builtin_module(url.to_string_lossy().as_ref()) {
+            Self::assert_builtin_not_configured(
+                url.to_string_lossy().as_ref(),
+                configuration.as_ref(),
+                names_in_errors,
+            )?;
 
             callback(
                 self,
@@ -641,13 +1198,135 @@ impl<'a> Visitor<'a> {
         // todo: decide on naming convention for style_sheet vs stylesheet
         let stylesheet = self.load_style_sheet(url.to_string_lossy().as_ref(), false, span)?;
 
-        let module = self.execute(stylesheet.clone(), configuration, names_in_errors)?;
+        self.assert_not_module_loop(&stylesheet.url, names_in_errors, span)?;
+
+        let current_configuration = configuration
+            .clone()
+            .unwrap_or_else(|| Arc::clone(&self.configuration));
+
+        if let Some(module) = self.modules.get(&stylesheet.url).cloned() {
+            self.assert_not_reconfigured(
+                &stylesheet.url,
+                &current_configuration,
+                names_in_errors,
+                span,
+            )?;
+
+            self.add_upstream_module(&module);
+
+            callback(self, module, stylesheet)?;
+
+            return Ok(());
+        }
+
+        self.module_configurations.insert(
+            stylesheet.url.clone(),
+            LoadedConfiguration::new(current_configuration),
+        );
+
+        self.active_modules.push(stylesheet.url.clone());
+        let module = self.execute(stylesheet.clone(), configuration, names_in_errors);
+        self.active_modules.pop();
+        let module = module?;
+
+        self.modules
+            .insert(stylesheet.url.clone(), Arc::clone(&module));
+        self.add_upstream_module(&module);
 
         callback(self, module, stylesheet)?;
 
         Ok(())
     }
 
+    /// Records that the module currently being executed uses `module`, so that
+    /// its extensions apply to `module` and `module`'s CSS is emitted before its own
+    fn add_upstream_module(&mut self, module: &Arc<RefCell<Module>>) {
+        if !self
+            .upstream_modules
+            .iter()
+            .any(|upstream| Arc::ptr_eq(upstream, module))
+        {
+            self.upstream_modules.push(Arc::clone(module));
+        }
+    }
+
+    /// Loads the module at `url` and emits its CSS at the current position,
+    /// as is done by `meta.load-css(..)`
+    ///
+    /// Unlike `@use`, the module's CSS is emitted every time it is loaded, nested
+    /// within the current style rule.
+    pub fn load_css(
+        &mut self,
+        url: &str,
+        configuration: Arc<RefCell<Configuration>>,
+        span: Span,
+    ) -> SassResult<()> {
+        if Self::builtin_module(url).is_some() {
+            return Self::assert_builtin_not_configured(url, Some(&configuration), true);
+        }
+
+        let stylesheet = self.load_style_sheet(url, false, span)?;
+
+        self.assert_not_module_loop(&stylesheet.url, true, span)?;
+        self.assert_not_reconfigured(&stylesheet.url, &configuration, true, span)?;
+
+        let is_loaded = self.modules.contains_key(&stylesheet.url);
+
+        let configuration = match self.module_configurations.get(&stylesheet.url) {
+            // The module has already been executed with these values, so its CSS
+            // must be reproduced using the same ones
+            Some(loaded) => Arc::new(RefCell::new(Configuration::implicit(loaded.values.clone()))),
+            None => {
+                self.module_configurations.insert(
+                    stylesheet.url.clone(),
+                    LoadedConfiguration::new(Arc::clone(&configuration)),
+                );
+                configuration
+            }
+        };
+
+        let url = stylesheet.url.clone();
+
+        self.active_modules.push(url.clone());
+        let module = self.execute_in_place(stylesheet, configuration);
+        self.active_modules.pop();
+        let module = module?;
+
+        if !is_loaded {
+            self.modules.insert(url, module);
+        }
+
+        Ok(())
+    }
+
+    /// Executes a module in its own environment, but emits its CSS at the current
+    /// position rather than at the root of the stylesheet
+    fn execute_in_place(
+        &mut self,
+        stylesheet: StyleSheet,
+        configuration: Arc<RefCell<Configuration>>,
+    ) -> SassResult<Arc<RefCell<Module>>> {
+        let env = Environment::new();
+
+        self.with_environment::<SassResult<()>, _>(env.new_closure(), |visitor| {
+            let old_configuration = mem::replace(&mut visitor.configuration, configuration);
+            let old_declaration_name = visitor.declaration_name.take();
+
+            let result = visitor.visit_stylesheet(stylesheet);
+
+            visitor.declaration_name = old_declaration_name;
+            visitor.configuration = old_configuration;
+
+            result
+        })?;
+
+        Ok(env.to_module(
+            ExtensionStore::new(self.span_before),
+            Vec::new(),
+            Vec::new(),
+        ))
+    }
+
     fn visit_use_rule(&mut self, use_rule: AstUseRule) -> SassResult<()> {
         let configuration = if use_rule.configuration.is_empty() {
             Arc::new(RefCell::new(Configuration::empty()))
@@ -733,7 +1412,6 @@ impl<'a> Visitor<'a> {
     ///
     /// <https://sass-lang.com/documentation/at-rules/import#finding-the-file>
     /// <https://sass-lang.com/documentation/at-rules/import#load-paths>
-    #[allow(clippy::cognitive_complexity)]
     fn find_import(&self, path: &Path) -> Option<PathBuf> {
         let path_buf = if path.is_absolute() {
             path.into()
@@ -744,6 +1422,23 @@ impl<'a> Visitor<'a> {
                 .join(path)
         };
 
+        if let Some(path) = self.resolve_import_path(&path_buf) {
+            return Some(path);
+        }
+
+        for load_path in &self.options.load_paths {
+            if let Some(path) = self.resolve_import_path(&load_path.join(path)) {
+                return Some(path);
+            }
+        }
+
+        None
+    }
+
+    /// Resolves a path to a file on disk, trying import-only files, partials,
+    /// each of the valid file extensions, and index files
+    #[allow(clippy::cognitive_complexity)]
+    fn resolve_import_path(&self, path_buf: &Path) -> Option<PathBuf> {
         macro_rules! try_path {
             ($path:expr) => {
                 let path = $path;
@@ -769,7 +1464,6 @@ impl<'a> Visitor<'a> {
             let extension = path_buf.extension().unwrap();
             try_path!(path_buf.with_extension(format!(".import{}", extension.to_str().unwrap())));
             try_path!(path_buf);
-            // todo: consider load paths
             return None;
         }
 
@@ -785,23 +1479,46 @@ impl<'a> Visitor<'a> {
             };
         }
 
-        try_path_with_extensions!(path_buf.clone());
+        try_path_with_extensions!(path_buf);
 
-        if self.options.fs.is_dir(&path_buf) {
+        if self.options.fs.is_dir(path_buf) {
             try_path_with_extensions!(path_buf.join("index"));
         }
 
-        for load_path in &self.options.load_paths {
-            let path_buf = load_path.join(path);
-
-            try_path_with_extensions!(&path_buf);
+        None
+    }
 
-            if self.options.fs.is_dir(&path_buf) {
-                try_path_with_extensions!(path_buf.join("index"));
+    /// Resolves `pkg:` URLs, as well as webpack-style `~` URLs, using the
+    /// configured package importer
+    ///
+    /// Returns `None` if the URL is not a package URL
+    #[cfg_attr(not(feature = "pkg-importer"), allow(unused_variables))]
+    fn find_package_import(&self, url: &str, span: Span) -> Option<SassResult<Option<PathBuf>>> {
+        match self.options.pkg_importer {
+            #[cfg(feature = "pkg-importer")]
+            Some(PkgImporter::Node) => {
+                let package_url = url.strip_prefix("pkg:").or_else(|| url.strip_prefix('~'))?;
+
+                let from = self
+                    .current_import_path
+                    .parent()
+                    .unwrap_or_else(|| Path::new(""));
+
+                Some(
+                    NodePackageImporter::new(self.options.fs)
+                        .resolve(package_url, from, |path| self.resolve_import_path(path))
+                        .map_err(|e| (e, span).into()),
+                )
+            }
+            // without a package importer, `pkg:` URLs can never be resolved
+            _ => {
+                if url.starts_with("pkg:") {
+                    Some(Ok(None))
+                } else {
+                    None
+                }
             }
         }
-
-        None
     }
 
     fn parse_file(
@@ -829,7 +1546,12 @@ impl<'a> Visitor<'a> {
         _for_import: bool,
         span: Span,
     ) -> SassResult<StyleSheet> {
-        if let Some(name) = self.find_import(url.as_ref()) {
+        let name = match self.find_package_import(url, span) {
+            Some(resolved) => resolved?,
+            None => self.find_import(url.as_ref()),
+        };
+
+        if let Some(name) = name {
             if let Some(style_sheet) = self.import_cache.get(&name) {
                 return Ok(style_sheet.clone());
             }
@@ -871,73 +1593,82 @@ impl<'a> Visitor<'a> {
         self.import_like_node(url, for_import, span)
     }
 
+    /// Creates an error for a stylesheet that is loaded while it is already being
+    /// loaded, listing each stylesheet that participates in the cycle
+    fn load_loop_error(&self, message: &str, url: &Path, span: Span) -> Box<SassError> {
+        let cycle_start = self
+            .active_modules
+            .iter()
+            .position(|active| active == url)
+            .unwrap_or(0);
+
+        let mut message = format!("{}\n\nLoad chain:", message);
+
+        for path in self.active_modules[cycle_start..]
+            .iter()
+            .map(PathBuf::as_path)
+            .chain(std::iter::once(url))
+        {
+            message.push_str("\n  ");
+            message.push_str(&path.to_string_lossy());
+        }
+
+        (message, span).into()
+    }
+
     fn visit_dynamic_import_rule(&mut self, dynamic_import: &AstSassImport) -> SassResult<()> {
         let stylesheet = self.load_style_sheet(&dynamic_import.url, true, dynamic_import.span)?;
 
+        if self.active_modules.contains(&stylesheet.url) {
+            return Err(self.load_loop_error(
+                "This file is already being loaded.",
+                &stylesheet.url,
+                dynamic_import.span,
+            ));
+        }
+
+        self.active_modules.push(stylesheet.url.clone());
+        let result = self.visit_loaded_import(stylesheet);
+        self.active_modules.pop();
+
+        result
+    }
+
+    fn visit_loaded_import(&mut self, stylesheet: StyleSheet) -> SassResult<()> {
         // If the imported stylesheet doesn't use any modules, we can inject its
-        // CSS directly into the current stylesheet. If it does use modules, we
-        // need to put its CSS into an intermediate [ModifiableCssStylesheet] so
-        // that we can hermetically resolve `@extend`s before injecting it.
+        // CSS directly into the current stylesheet.
         if stylesheet.uses.is_empty() && stylesheet.forwards.is_empty() {
             self.visit_stylesheet(stylesheet)?;
             return Ok(());
         }
 
-        // todo:
-        let loads_user_defined_modules = true;
-
-        // this todo should be unreachable, as we currently do not push
-        // to stylesheet.uses or stylesheet.forwards
-        // let mut children = Vec::new();
+        // Otherwise, its members are evaluated in an import environment. The
+        // modules it loads are treated as upstream of the current module, so
+        // their CSS is emitted once alongside the rest of the module graph and the
+        // current module's extensions apply to them.
         let env = self.env.for_import();
 
         self.with_environment::<SassResult<()>, _>(env.clone(), |visitor| {
-            let old_parent = visitor.parent;
             let old_configuration = Arc::clone(&visitor.configuration);
 
-            if loads_user_defined_modules {
-                visitor.parent = Some(CssTree::ROOT);
-            }
-
             // This configuration is only used if it passes through a `@forward`
             // rule, so we avoid creating unnecessary ones for performance reasons.
             if !stylesheet.forwards.is_empty() {
                 visitor.configuration = Arc::new(RefCell::new(env.to_implicit_configuration()));
             }
 
-            visitor.visit_stylesheet(stylesheet)?;
+            let result = visitor.visit_stylesheet(stylesheet);
 
-            if loads_user_defined_modules {
-                visitor.parent = old_parent;
-            }
             visitor.configuration = old_configuration;
 
-            Ok(())
+            result
         })?;
 
         // Create a dummy module with empty CSS and no extensions to make forwarded
-        // members available in the current import context and to combine all the
-        // CSS from modules used by [stylesheet].
+        // members available in the current import context.
         let module = env.to_dummy_module(self.span_before);
         self.env.import_forwards(module);
 
-        if loads_user_defined_modules {
-            //     if (module.transitivelyContainsCss) {
-            //       // If any transitively used module contains extensions, we need to
-            //       // clone all modules' CSS. Otherwise, it's possible that they'll be
-            //       // used or imported from another location that shouldn't have the same
-            //       // extensions applied.
-            //       await _combineCss(module,
-            //               clone: module.transitivelyContainsExtensions)
-            //           .accept(this);
-            //     }
-
-            //     var visitor = _ImportedCssVisitor(this);
-            //     for (var child in children) {
-            //       child.accept(visitor);
-            //     }
-        }
-
         Ok(())
     }
 
@@ -949,7 +1680,7 @@ impl<'a> Visitor<'a> {
             .map(|modifiers| self.interpolation_to_value(modifiers, false, false))
             .transpose()?;
 
-        let node = CssStmt::Import(import, modifiers);
+        let node = CssStmt::Import(import, modifiers, static_import.span);
 
         if self.parent.is_some() && self.parent != Some(CssTree::ROOT) {
             self.css_tree.add_stmt(node, self.parent);
@@ -1154,7 +1885,11 @@ impl<'a> Visitor<'a> {
         }
 
         let old_media_query_info = if self.media_queries.is_some() && query.excludes_name("media") {
-            Some((self.media_queries.take(), self.media_query_sources.take()))
+            Some((
+                self.media_queries.take(),
+                self.media_query_sources.take(),
+                self.media_query_span.take(),
+            ))
         } else {
             None
         };
@@ -1179,9 +1914,12 @@ impl<'a> Visitor<'a> {
             old_at_root_excluding_style_rule,
         );
 
-        if let Some((old_media_queries, old_media_query_sources)) = old_media_query_info {
+        if let Some((old_media_queries, old_media_query_sources, old_media_query_span)) =
+            old_media_query_info
+        {
             self.media_queries = old_media_queries;
             self.media_query_sources = old_media_query_sources;
+            self.media_query_span = old_media_query_span;
         }
 
         self.flags.set(ContextFlags::IN_KEYFRAMES, was_in_keyframes);
@@ -1254,10 +1992,11 @@ impl<'a> Visitor<'a> {
                 compound.components.first().unwrap(),
                 &ExtendRule {
                     is_optional: extend_rule.is_optional,
+                    media_span: self.media_query_span,
                 },
                 &self.media_queries,
                 extend_rule.span,
-            );
+            )?;
         }
 
         Ok(None)
@@ -1310,6 +2049,14 @@ impl<'a> Visitor<'a> {
         }
 
         let queries1 = self.visit_media_queries(media_rule.query, media_rule.query_span)?;
+        let media_query_span = {
+            let span = media_rule
+                .span
+                .subspan(0, media_rule.query_span.high() - media_rule.span.low());
+            let text = self.map.find_file(span.low()).source_slice(span);
+            span.subspan(0, text.trim_end().len() as u64)
+        };
+        let preserves_nesting = self.preserves_nesting();
         // todo: superfluous clone?
         let queries2 = self.media_queries.clone();
         let merged_queries = queries2
@@ -1330,12 +2077,19 @@ impl<'a> Visitor<'a> {
 
         let children = media_rule.body;
 
-        let query = merged_queries.clone().unwrap_or_else(|| queries1.clone());
+        // A media rule kept nested within a style rule is still nested within
+        // any enclosing media rule, so it only needs its own queries
+        let query = match &merged_queries {
+            Some(merged_queries) if !preserves_nesting => merged_queries.clone(),
+            Some(..) | None => queries1.clone(),
+        };
 
         let media_rule = CssStmt::Media(
             MediaRule {
                 query,
                 body: Vec::new(),
+                span: media_rule.span,
+                depth: self.bubbling_depth(),
             },
             false,
         );
@@ -1347,8 +2101,9 @@ impl<'a> Visitor<'a> {
                 visitor.with_media_queries(
                     Some(merged_queries.unwrap_or(queries1)),
                     Some(merged_sources.clone()),
+                    Some(media_query_span),
                     |visitor| {
-                        if !visitor.style_rule_exists() {
+                        if !visitor.style_rule_exists() || preserves_nesting {
                             for stmt in children {
                                 let result = visitor.visit_stmt(stmt)?;
                                 debug_assert!(result.is_none());
@@ -1359,12 +2114,7 @@ impl<'a> Visitor<'a> {
                             //
                             // For example, "a {@media screen {b: c}}" should produce
                             // "@media screen {a {b: c}}".
-                            let selector = visitor.style_rule_ignoring_at_root.clone().unwrap();
-                            let ruleset = CssStmt::RuleSet {
-                                selector,
-                                body: Vec::new(),
-                                is_group_end: false,
-                            };
+                            let ruleset = visitor.copy_parent_style_rule();
 
                             visitor.with_parent(
                                 ruleset,
@@ -1386,7 +2136,7 @@ impl<'a> Visitor<'a> {
                 )
             },
             |stmt| match stmt {
-                CssStmt::RuleSet { .. } => true,
+                CssStmt::RuleSet { .. } => !preserves_nesting,
                 // todo: node.queries.every(mergedSources.contains))
                 CssStmt::Media(media_rule, ..) => {
                     !merged_sources.is_empty()
@@ -1428,6 +2178,8 @@ impl<'a> Visitor<'a> {
                     params: value.unwrap_or_default(),
                     body: Vec::new(),
                     has_body: false,
+                    span: unknown_at_rule.span,
+                    depth: 0,
                 },
                 false,
             );
@@ -1454,6 +2206,8 @@ impl<'a> Visitor<'a> {
                 params: value.unwrap_or_default(),
                 body: Vec::new(),
                 has_body: true,
+                span: unknown_at_rule.span,
+                depth: self.bubbling_depth(),
             },
             false,
         );
@@ -1472,13 +2226,7 @@ impl<'a> Visitor<'a> {
                     // declarations immediately inside it have somewhere to go.
                     //
                     // For example, "a {@foo {b: c}}" should produce "@foo {a {b: c}}".
-                    let selector = visitor.style_rule_ignoring_at_root.clone().unwrap();
-
-                    let style_rule = CssStmt::RuleSet {
-                        selector,
-                        body: Vec::new(),
-                        is_group_end: false,
-                    };
+                    let style_rule = visitor.copy_parent_style_rule();
 
                     visitor.with_parent(
                         style_rule,
@@ -1535,15 +2283,19 @@ impl<'a> Visitor<'a> {
         &mut self,
         queries: Option<Vec<MediaQuery>>,
         sources: Option<IndexSet<MediaQuery>>,
+        span: Option<Span>,
         callback: impl FnOnce(&mut Self) -> T,
     ) -> T {
         let old_media_queries = self.media_queries.take();
         let old_media_query_sources = self.media_query_sources.take();
+        let old_media_query_span = self.media_query_span.take();
         self.media_queries = queries;
         self.media_query_sources = sources;
+        self.media_query_span = span;
         let result = callback(self);
         self.media_queries = old_media_queries;
         self.media_query_sources = old_media_query_sources;
+        self.media_query_span = old_media_query_span;
         result
     }
 
@@ -1664,41 +2416,53 @@ impl<'a> Visitor<'a> {
             .env
             .get_mixin(include_stmt.name, include_stmt.namespace)?;
 
-        match mixin {
-            Mixin::Builtin(mixin) => {
-                if include_stmt.content.is_some() {
-                    return Err(("Mixin doesn't accept a content block.", include_stmt.span).into());
-                }
+        if include_stmt.content.is_some() && !mixin.accepts_content() {
+            return Err(("Mixin doesn't accept a content block.", include_stmt.span).into());
+        }
 
-                let args = self.eval_args(include_stmt.args, include_stmt.name.span)?;
-                mixin(args, self)?;
+        let AstInclude { args, content, .. } = include_stmt;
 
-                Ok(None)
-            }
-            Mixin::UserDefined(mixin, env) => {
-                if include_stmt.content.is_some() && !mixin.has_content {
-                    return Err(("Mixin doesn't accept a content block.", include_stmt.span).into());
-                }
+        let callable_content = content.map(|c| {
+            Arc::new(CallableContentBlock {
+                content: c,
+                env: self.env.new_closure(),
+            })
+        });
 
-                let AstInclude { args, content, .. } = include_stmt;
+        self.run_mixin(
+            mixin,
+            MaybeEvaledArguments::Invocation(args),
+            callable_content,
+            include_stmt.name.span,
+        )?;
+
+        Ok(None)
+    }
 
+    /// Includes `mixin`, passing it `content` as its content block
+    pub(crate) fn run_mixin(
+        &mut self,
+        mixin: Mixin,
+        args: MaybeEvaledArguments,
+        content: Option<Arc<CallableContentBlock>>,
+        span: Span,
+    ) -> SassResult<()> {
+        match mixin {
+            Mixin::Builtin { mixin, .. } => {
+                let args = self.eval_maybe_args(args, span)?;
+                self.with_content(content, |visitor| mixin(args, visitor))
+            }
+            Mixin::UserDefined(mixin, env) => {
                 let old_in_mixin = self.flags.in_mixin();
                 self.flags.set(ContextFlags::IN_MIXIN, true);
 
-                let callable_content = content.map(|c| {
-                    Arc::new(CallableContentBlock {
-                        content: c,
-                        env: self.env.new_closure(),
-                    })
-                });
-
                 self.run_user_defined_callable::<_, (), _>(
-                    MaybeEvaledArguments::Invocation(args),
+                    args,
                     mixin,
                     &env,
-                    include_stmt.name.span,
+                    span,
                     |mixin, visitor| {
-                        visitor.with_content(callable_content, |visitor| {
+                        visitor.with_content(content, |visitor| {
                             for stmt in mixin.body {
                                 let result = visitor.visit_stmt(stmt)?;
                                 debug_assert!(result.is_none());
@@ -1710,7 +2474,7 @@ impl<'a> Visitor<'a> {
 
                 self.flags.set(ContextFlags::IN_MIXIN, old_in_mixin);
 
-                Ok(None)
+                Ok(())
             }
         }
     }
@@ -2515,7 +3279,7 @@ impl<'a> Visitor<'a> {
     fn visit_calculation_value(
         &mut self,
         expr: AstExpr,
-        in_min_or_max: bool,
+        in_legacy_sass_function: bool,
         span: Span,
     ) -> SassResult<CalculationArg> {
         Ok(match expr {
@@ -2523,8 +3287,11 @@ impl<'a> Visitor<'a> {
                 AstExpr::FunctionCall(FunctionCallExpr { ref name, .. })
                     if name.as_str().to_ascii_lowercase() == "var" =>
                 {
-                    let result =
-                        self.visit_calculation_value((*inner).clone(), in_min_or_max, span)?;
+                    let result = self.visit_calculation_value(
+                        (*inner).clone(),
+                        in_legacy_sass_function,
+                        span,
+                    )?;
 
                     if let CalculationArg::String(text) = result {
                         CalculationArg::String(format!("({})", text))
@@ -2532,7 +3299,9 @@ impl<'a> Visitor<'a> {
                         result
                     }
                 }
-                _ => self.visit_calculation_value((*inner).clone(), in_min_or_max, span)?,
+                _ => {
+                    self.visit_calculation_value((*inner).clone(), in_legacy_sass_function, span)?
+                }
             },
             AstExpr::String(string_expr, _span) => {
                 debug_assert!(string_expr.1 == QuoteKind::None);
@@ -2540,9 +3309,9 @@ impl<'a> Visitor<'a> {
             }
             AstExpr::BinaryOp(binop) => SassCalculation::operate_internal(
                 binop.op,
-                self.visit_calculation_value(binop.lhs.clone(), in_min_or_max, span)?,
-                self.visit_calculation_value(binop.rhs.clone(), in_min_or_max, span)?,
-                in_min_or_max,
+                self.visit_calculation_value(binop.lhs.clone(), in_legacy_sass_function, span)?,
+                self.visit_calculation_value(binop.rhs.clone(), in_legacy_sass_function, span)?,
+                in_legacy_sass_function,
                 !self.flags.in_supports_declaration(),
                 self.options,
                 span,
@@ -2589,9 +3358,36 @@ impl<'a> Visitor<'a> {
         args: Vec<AstExpr>,
         span: Span,
     ) -> SassResult<Value> {
+        // Math functions other than calc(), min(), max() and clamp() defer to
+        // a Sass function of the same name if one is in scope
+        if !matches!(
+            name,
+            CalculationName::Calc
+                | CalculationName::Min
+                | CalculationName::Max
+                | CalculationName::Clamp
+        ) {
+            let fn_name = Identifier::from(name.as_str());
+
+            if self.env.get_fn(fn_name, None)?.is_some() {
+                return self.visit_function_call_expr(FunctionCallExpr {
+                    namespace: None,
+                    name: fn_name,
+                    arguments: Arc::new(ArgumentInvocation {
+                        positional: args,
+                        named: BTreeMap::new(),
+                        rest: None,
+                        keyword_rest: None,
+                        span,
+                    }),
+                    span,
+                });
+            }
+        }
+
         let mut args = args
             .into_iter()
-            .map(|arg| self.visit_calculation_value(arg, name.in_min_or_max(), span))
+            .map(|arg| self.visit_calculation_value(arg, name.in_legacy_sass_function(), span))
             .collect::<SassResult<Vec<_>>>()?;
 
         if self.flags.in_supports_declaration() {
@@ -2621,6 +3417,27 @@ impl<'a> Visitor<'a> {
                 };
                 SassCalculation::clamp(min, value, max, self.options, span)
             }
+            CalculationName::Round => SassCalculation::round(args, self.options, span),
+            CalculationName::Mod | CalculationName::Rem => {
+                SassCalculation::modulo(name, args, self.options, span)
+            }
+            CalculationName::Sin
+            | CalculationName::Cos
+            | CalculationName::Tan
+            | CalculationName::Asin
+            | CalculationName::Acos
+            | CalculationName::Atan
+            | CalculationName::Sqrt
+            | CalculationName::Exp
+            | CalculationName::Abs
+            | CalculationName::Sign => {
+                debug_assert_eq!(args.len(), 1);
+                SassCalculation::single_argument(name, args.remove(0), self.options, span)
+            }
+            CalculationName::Atan2 => SassCalculation::atan2(args, self.options, span),
+            CalculationName::Pow => SassCalculation::pow(args, self.options, span),
+            CalculationName::Log => SassCalculation::log(args, self.options, span),
+            CalculationName::Hypot => SassCalculation::hypot(args, self.options, span),
         }
     }
 
@@ -2856,6 +3673,7 @@ impl<'a> Visitor<'a> {
             let keyframes_ruleset = CssStmt::KeyframesRuleSet(KeyframesRuleSet {
                 selector: parsed_selector,
                 body: Vec::new(),
+                span: ruleset.span,
             });
 
             self.with_parent(
@@ -2875,14 +3693,32 @@ impl<'a> Visitor<'a> {
             return Ok(None);
         }
 
-        let mut parsed_selector = self.parse_selector_from_string(
-            &selector_text,
-            !self.is_plain_css,
-            !self.is_plain_css,
-            ruleset.selector_span,
-        )?;
+        let parsed_selector = if self.is_plain_css {
+            let sel_toks = Lexer::new_from_string(&selector_text, ruleset.selector_span);
+
+            SelectorParser::new(sel_toks, true, false, ruleset.selector_span)
+                .css_nesting()
+                .parse()?
+        } else {
+            self.parse_selector_from_string(&selector_text, true, true, ruleset.selector_span)?
+        };
+
+        // Selectors that use Sass-only features, such as `&-suffix`, can't be
+        // written as-is within their parent, so those rules are flattened instead
+        let preserves_nesting = self.preserves_nesting()
+            && parsed_selector.is_css_nestable()
+            && self
+                .style_rule_ignoring_at_root
+                .as_ref()
+                .is_some_and(|parent| parent.as_selector_list().is_css_nestable());
 
-        parsed_selector = parsed_selector.resolve_parent_selectors(
+        let nested_selector = if preserves_nesting {
+            Some(parsed_selector.clone())
+        } else {
+            None
+        };
+
+        let parsed_selector = parsed_selector.resolve_parent_selectors(
             self.style_rule_ignoring_at_root
                 .as_ref()
                 // todo: this clone should be superfluous(?)
@@ -2890,15 +3726,24 @@ impl<'a> Visitor<'a> {
             !self.flags.at_root_excluding_style_rule(),
         )?;
 
+        let nested_selector = nested_selector.map(|selector| {
+            Box::new(NestedSelector {
+                selector,
+                resolved: parsed_selector.clone(),
+            })
+        });
+
         // todo: _mediaQueries
         let selector = self
             .extender
-            .add_selector(parsed_selector, &self.media_queries);
+            .add_selector(parsed_selector, &self.media_queries)?;
 
         let rule = CssStmt::RuleSet {
             selector: selector.clone(),
             body: Vec::new(),
             is_group_end: false,
+            nested_selector,
+            depth: self.style_rule_depth,
         };
 
         let old_at_root_excluding_style_rule = self.flags.at_root_excluding_style_rule();
@@ -2908,6 +3753,7 @@ impl<'a> Visitor<'a> {
 
         let old_style_rule_ignoring_at_root = self.style_rule_ignoring_at_root.take();
         self.style_rule_ignoring_at_root = Some(selector);
+        self.style_rule_depth += 1;
 
         self.with_parent(
             rule,
@@ -2920,9 +3766,10 @@ impl<'a> Visitor<'a> {
 
                 Ok(())
             },
-            CssStmt::is_style_rule,
+            |stmt| !preserves_nesting && stmt.is_style_rule(),
         )?;
 
+        self.style_rule_depth -= 1;
         self.style_rule_ignoring_at_root = old_style_rule_ignoring_at_root;
         self.flags.set(
             ContextFlags::AT_ROOT_EXCLUDING_STYLE_RULE,
@@ -2934,6 +3781,39 @@ impl<'a> Visitor<'a> {
         Ok(None)
     }
 
+    /// An empty copy of the innermost style rule, which at-rules nested within
+    /// it use to hold their declarations
+    fn copy_parent_style_rule(&self) -> CssStmt {
+        CssStmt::RuleSet {
+            selector: self.style_rule_ignoring_at_root.clone().unwrap(),
+            body: Vec::new(),
+            is_group_end: false,
+            nested_selector: None,
+            depth: self.style_rule_depth.saturating_sub(1),
+        }
+    }
+
+    /// The number of style rules that an at-rule added at the current
+    /// position bubbles out of, used to indent it in the nested output style
+    fn bubbling_depth(&self) -> usize {
+        if self.preserves_nesting() {
+            0
+        } else {
+            self.style_rule_depth
+        }
+    }
+
+    /// Whether we're inside a style rule whose nested rules are kept as
+    /// written, rather than resolved and bubbled up to the top level
+    fn preserves_nesting(&self) -> bool {
+        self.options.nesting == Nesting::Preserve
+            && self.style_rule_exists()
+            && self
+                .style_rule_ignoring_at_root
+                .as_ref()
+                .is_some_and(|parent| parent.as_selector_list().has_uniform_specificity())
+    }
+
     fn set_group_end(&mut self) -> Option<()> {
         if !self.style_rule_exists() {
             let children = self
@@ -2993,6 +3873,7 @@ impl<'a> Visitor<'a> {
                         property: InternedString::get_or_intern(&name),
                         value: Box::new(value),
                         declared_as_custom_property: is_custom_property,
+                        span: style.span,
                     }),
                     self.parent,
                 );
@@ -0,0 +1,340 @@
+#[cfg(feature = "pkg-importer")]
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "pkg-importer")]
+use serde_json::{Map, Value as Json};
+
+#[cfg(feature = "pkg-importer")]
+use crate::Fs;
+
+/// Built-in importers used to resolve `pkg:` URLs
+///
+/// See [`Options::pkg_importer`](crate::Options::pkg_importer) for more information.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PkgImporter {
+    /// Resolve `pkg:` URLs using the same algorithm as Node.js: packages are
+    /// looked up in the nearest `node_modules` directory, and their
+    /// `package.json` is consulted to find the stylesheet to load.
+    ///
+    /// When this importer is enabled, webpack-style URLs beginning with `~`
+    /// (e.g. `~bootstrap/scss/functions`) are resolved the same way.
+    ///
+    /// This requires the `"pkg-importer"` feature, which is enabled by default.
+    #[cfg(feature = "pkg-importer")]
+    Node,
+}
+
+#[cfg(feature = "pkg-importer")]
+const CONDITIONS: [&str; 3] = ["sass", "style", "default"];
+
+#[cfg(feature = "pkg-importer")]
+const EXTENSIONS: [&str; 3] = ["scss", "sass", "css"];
+
+#[cfg(feature = "pkg-importer")]
+fn has_valid_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| EXTENSIONS.contains(&ext))
+}
+
+/// Resolves package URLs, such as `pkg:@acme/tokens` or `pkg:bootstrap/scss/functions`,
+/// to files on disk
+#[cfg(feature = "pkg-importer")]
+pub(crate) struct NodePackageImporter<'a> {
+    fs: &'a dyn Fs,
+}
+
+#[cfg(feature = "pkg-importer")]
+impl<'a> NodePackageImporter<'a> {
+    pub fn new(fs: &'a dyn Fs) -> Self {
+        Self { fs }
+    }
+
+    /// `url` is the package URL without its `pkg:` or `~` prefix. Packages are
+    /// searched for in `node_modules` directories starting at `from` and
+    /// walking up to the root.
+    ///
+    /// `resolve_file` resolves a path without an extension, or to a partial or
+    /// index file, in the same way as regular imports.
+    pub fn resolve(
+        &self,
+        url: &str,
+        from: &Path,
+        resolve_file: impl Fn(&Path) -> Option<PathBuf>,
+    ) -> Result<Option<PathBuf>, String> {
+        let (package_name, subpath) = Self::split_package_name(url)?;
+
+        let package_root = match self.find_package_root(package_name, from) {
+            Some(root) => root,
+            None => return Ok(None),
+        };
+
+        let manifest_path = package_root.join("package.json");
+        let manifest = if self.fs.is_file(&manifest_path) {
+            let contents = self
+                .fs
+                .read(&manifest_path)
+                .map_err(|e| format!("Failed to read {}: {}", manifest_path.display(), e))?;
+            serde_json::from_slice::<Json>(&contents)
+                .map_err(|e| format!("Failed to parse {}: {}", manifest_path.display(), e))?
+        } else {
+            Json::Object(Map::new())
+        };
+
+        if let Some(exports) = manifest.get("exports") {
+            if let Some(resolved) =
+                self.resolve_exports(exports, subpath, package_name, &package_root)?
+            {
+                if !has_valid_extension(&resolved) {
+                    return Err(format!(
+                        "The export for '{}' in '{}' resolved to '{}', which is not a '.scss', '.sass', or '.css' file.",
+                        subpath.unwrap_or("root"),
+                        package_name,
+                        resolved.display()
+                    ));
+                }
+
+                return Ok(Some(resolved));
+            }
+        }
+
+        match subpath {
+            Some(subpath) => Ok(resolve_file(&package_root.join(subpath))),
+            None => {
+                for field in ["sass", "style"] {
+                    if let Some(entry) = manifest.get(field).and_then(Json::as_str) {
+                        let entry = package_root.join(entry);
+                        if has_valid_extension(&entry) && self.fs.is_file(&entry) {
+                            return Ok(Some(entry));
+                        }
+                    }
+                }
+
+                Ok(resolve_file(&package_root.join("index")))
+            }
+        }
+    }
+
+    /// Splits a URL into the package name, including its scope if it has one,
+    /// and the subpath within that package
+    fn split_package_name(url: &str) -> Result<(&str, Option<&str>), String> {
+        let mut name_end = url.find('/').unwrap_or(url.len());
+
+        if url.starts_with('@') {
+            if name_end == url.len() {
+                return Err(format!(
+                    "pkg: URL \"{}\" is missing a package name after its scope.",
+                    url
+                ));
+            }
+
+            name_end = url[name_end + 1..]
+                .find('/')
+                .map_or(url.len(), |idx| name_end + 1 + idx);
+        }
+
+        let name = &url[..name_end];
+
+        if name.is_empty() || name.starts_with('.') || name.contains('\\') {
+            return Err(format!("Invalid Node package name \"{}\".", name));
+        }
+
+        let subpath = url[name_end..].trim_start_matches('/');
+
+        Ok((
+            name,
+            if subpath.is_empty() {
+                None
+            } else {
+                Some(subpath)
+            },
+        ))
+    }
+
+    fn find_package_root(&self, package_name: &str, from: &Path) -> Option<PathBuf> {
+        from.ancestors()
+            .map(|dir| dir.join("node_modules").join(package_name))
+            .find(|candidate| self.fs.is_dir(candidate))
+    }
+
+    /// The list of keys in `exports` that may match a given subpath, in order
+    /// of priority
+    fn exports_to_check(subpath: Option<&str>) -> Vec<String> {
+        let subpath = match subpath {
+            Some(subpath) => subpath,
+            None => return vec![".".to_owned()],
+        };
+
+        let path = Path::new(subpath);
+
+        let mut paths = vec![subpath.to_owned()];
+
+        let partial = |path: &Path| -> Option<String> {
+            let file_name = path.file_name()?.to_str()?;
+            if file_name.starts_with('_') {
+                return None;
+            }
+            Some(
+                path.with_file_name(format!("_{}", file_name))
+                    .to_string_lossy()
+                    .into_owned(),
+            )
+        };
+
+        if has_valid_extension(path) {
+            paths.extend(partial(path));
+        } else {
+            for ext in EXTENSIONS {
+                paths.push(format!("{}.{}", subpath, ext));
+            }
+            for ext in EXTENSIONS {
+                paths.extend(partial(Path::new(&format!("{}.{}", subpath, ext))));
+            }
+            for index in ["index", "_index"] {
+                for ext in EXTENSIONS {
+                    paths.push(format!("{}/{}.{}", subpath, index, ext));
+                }
+            }
+        }
+
+        paths
+            .into_iter()
+            .map(|path| format!("./{}", path.replace('\\', "/")))
+            .collect()
+    }
+
+    /// Only exports that resolve to existing files are considered, so that
+    /// patterns like `"./*": "./*.scss"` don't conflict with one another
+    fn resolve_exports(
+        &self,
+        exports: &Json,
+        subpath: Option<&str>,
+        package_name: &str,
+        package_root: &Path,
+    ) -> Result<Option<PathBuf>, String> {
+        let subpath_map = match exports {
+            Json::Object(map) if map.keys().any(|key| key.starts_with('.')) => {
+                if map.keys().any(|key| !key.starts_with('.')) {
+                    return Err(format!(
+                        "'exports' in '{}' can not have both conditions and paths at the same level.",
+                        package_name
+                    ));
+                }
+
+                Some(map)
+            }
+            _ => None,
+        };
+
+        let mut matches = Vec::new();
+
+        for key in Self::exports_to_check(subpath) {
+            let resolved = match subpath_map {
+                Some(map) => Self::resolve_subpath(map, &key)?,
+                None if key == "." => Self::resolve_target(exports, None)?,
+                None => None,
+            };
+
+            if let Some(resolved) = resolved.map(|target| package_root.join(&target[2..])) {
+                if self.fs.is_file(&resolved) && !matches.contains(&resolved) {
+                    matches.push(resolved);
+                }
+            }
+        }
+
+        match matches.len() {
+            0 | 1 => Ok(matches.pop()),
+            _ => Err(format!(
+                "Unable to determine which of multiple potential resolutions found for {} in {} should be used.\n\nFound:\n{}",
+                subpath.unwrap_or("root"),
+                package_name,
+                matches
+                    .iter()
+                    .map(|path| path.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            )),
+        }
+    }
+
+    fn resolve_subpath(map: &Map<String, Json>, key: &str) -> Result<Option<String>, String> {
+        if let Some(target) = map.get(key) {
+            if !key.contains('*') {
+                return Self::resolve_target(target, None);
+            }
+        }
+
+        // subpath patterns, such as `"./scss/*": "./src/*.scss"`. The pattern
+        // with the longest prefix takes precedence
+        let mut best: Option<(usize, &Json, &str)> = None;
+
+        for (pattern, target) in map {
+            let (prefix, suffix) = match pattern.split_once('*') {
+                Some(parts) => parts,
+                None => continue,
+            };
+
+            if key.len() < prefix.len() + suffix.len()
+                || !key.starts_with(prefix)
+                || !key.ends_with(suffix)
+            {
+                continue;
+            }
+
+            let replacement = &key[prefix.len()..key.len() - suffix.len()];
+
+            match best {
+                Some((best_prefix_len, ..)) if best_prefix_len >= prefix.len() => {}
+                _ => best = Some((prefix.len(), target, replacement)),
+            }
+        }
+
+        match best {
+            Some((_, target, replacement)) => Self::resolve_target(target, Some(replacement)),
+            None => Ok(None),
+        }
+    }
+
+    fn resolve_target(
+        target: &Json,
+        pattern_match: Option<&str>,
+    ) -> Result<Option<String>, String> {
+        match target {
+            Json::String(target) => {
+                if !target.starts_with("./") {
+                    return Err(format!(
+                        "Export '{}' must be a path relative to the package root at './'.",
+                        target
+                    ));
+                }
+
+                Ok(Some(match pattern_match {
+                    Some(replacement) => target.replace('*', replacement),
+                    None => target.clone(),
+                }))
+            }
+            Json::Object(conditions) => {
+                for (condition, value) in conditions {
+                    if CONDITIONS.contains(&condition.as_str()) {
+                        if let Some(resolved) = Self::resolve_target(value, pattern_match)? {
+                            return Ok(Some(resolved));
+                        }
+                    }
+                }
+
+                Ok(None)
+            }
+            Json::Array(targets) => {
+                for target in targets {
+                    if let Some(resolved) = Self::resolve_target(target, pattern_match)? {
+                        return Ok(Some(resolved));
+                    }
+                }
+
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    }
+}
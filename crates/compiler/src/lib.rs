@@ -87,44 +87,71 @@ use serializer::Serializer;
 #[cfg(feature = "wasm-exports")]
 use wasm_bindgen::prelude::*;
 
-use codemap::CodeMap;
+use codemap::{CodeMap, Span};
 
+#[cfg(feature = "config")]
+pub use crate::config::{Config, DeprecationConfig, SourceMapConfig, SourceMapUrls};
 pub use crate::error::{
     PublicSassErrorKind as ErrorKind, SassError as Error, SassResult as Result,
 };
 pub use crate::fs::{Fs, NullFs, StdFs};
-pub use crate::options::{InputSyntax, Options, OutputStyle};
+pub use crate::importer::PkgImporter;
+pub use crate::options::{
+    Comments, Indent, InputSyntax, LineEnding, MediaMerging, Nesting, Options, OutputStyle,
+};
+pub use crate::tree::{
+    CssAtRule, CssComment, CssDeclaration, CssNode, CssStyleRule, CssStylesheet,
+};
+use crate::{
+    ast::CssStmt, evaluate::Visitor, lexer::Lexer, optimize::Optimizer, parse::ScssParser,
+};
 pub(crate) use crate::{context_flags::ContextFlags, lexer::Token};
-use crate::{evaluate::Visitor, lexer::Lexer, parse::ScssParser};
 
 mod ast;
 mod builtin;
 mod color;
 mod common;
+#[cfg(feature = "config")]
+mod config;
 mod context_flags;
 mod error;
 mod evaluate;
 mod fs;
+mod importer;
 mod interner;
 mod lexer;
+mod media_merge;
+mod optimize;
 mod options;
 mod parse;
 mod selector;
 mod serializer;
+mod tree;
 mod unit;
 mod utils;
 mod value;
 
 fn raw_to_parse_error(map: &CodeMap, err: Error, unicode: bool) -> Box<Error> {
-    let (message, span) = err.raw();
-    Box::new(Error::from_loc(message, map.look_up_span(span), unicode))
+    let (message, span, related) = err.raw();
+    let related = related
+        .into_iter()
+        .map(|(span, label)| (map.look_up_span(span), label))
+        .collect();
+    Box::new(Error::from_loc(
+        message,
+        map.look_up_span(span),
+        related,
+        unicode,
+    ))
 }
 
-fn from_string_with_file_name<P: AsRef<Path>>(
+/// Parses and evaluates `input`, returning the CSS statements to be written
+/// along with the code map and span of the entrypoint needed to write them
+fn compile<P: AsRef<Path>>(
     input: String,
     file_name: P,
     options: &Options,
-) -> Result<String> {
+) -> Result<(Vec<CssStmt>, CodeMap, Span)> {
     let mut map = CodeMap::new();
     let path = file_name.as_ref();
     let file = map.add_file(path.to_string_lossy().into_owned(), input);
@@ -157,9 +184,30 @@ fn from_string_with_file_name<P: AsRef<Path>>(
         Ok(_) => {}
         Err(e) => return Err(raw_to_parse_error(&map, *e, options.unicode_error_messages)),
     }
-    let stmts = visitor.finish();
+    let mut stmts = visitor
+        .finish()
+        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?;
+
+    if options.media_merging != MediaMerging::Preserve {
+        stmts = media_merge::merge_media_rules(stmts, options.media_merging);
+    }
+
+    if options.optimize {
+        stmts = Optimizer::new(options, empty_span).optimize(stmts);
+    }
 
-    let mut serializer = Serializer::new(options, &map, false, empty_span);
+    Ok((stmts, map, empty_span))
+}
+
+fn serialize_stmts(
+    stmts: Vec<CssStmt>,
+    options: &Options,
+    map: &CodeMap,
+    empty_span: Span,
+) -> Result<String> {
+    let stmts = CssStmt::retain_comments(stmts, &|comment| options.keeps_comment(comment));
+
+    let mut serializer = Serializer::new(options, map, false, empty_span);
 
     let mut prev_was_group_end = false;
     let mut prev_requires_semicolon = false;
@@ -173,7 +221,7 @@ fn from_string_with_file_name<P: AsRef<Path>>(
 
         serializer
             .visit_group(stmt, prev_was_group_end, prev_requires_semicolon)
-            .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?;
+            .map_err(|e| raw_to_parse_error(map, *e, options.unicode_error_messages))?;
 
         prev_was_group_end = is_group_end;
         prev_requires_semicolon = requires_semicolon;
@@ -182,6 +230,27 @@ fn from_string_with_file_name<P: AsRef<Path>>(
     Ok(serializer.finish(prev_requires_semicolon))
 }
 
+fn from_string_with_file_name<P: AsRef<Path>>(
+    input: String,
+    file_name: P,
+    options: &Options,
+) -> Result<String> {
+    let (stmts, map, empty_span) = compile(input, file_name, options)?;
+
+    serialize_stmts(stmts, options, &map, empty_span)
+}
+
+fn tree_from_string_with_file_name<P: AsRef<Path>>(
+    input: String,
+    file_name: P,
+    options: &Options,
+) -> Result<CssStylesheet> {
+    let (stmts, map, empty_span) = compile(input, file_name, options)?;
+
+    CssStylesheet::from_stmts(stmts, options, &map, empty_span)
+        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))
+}
+
 /// Compile CSS from a path
 ///
 /// n.b. `grass` does not currently support files or paths that are not valid UTF-8
@@ -213,6 +282,43 @@ pub fn from_string(input: String, options: &Options) -> Result<String> {
     from_string_with_file_name(input, "stdin", options)
 }
 
+/// Compile a path to a tree of CSS nodes, which can be modified and then
+/// written with [`CssStylesheet::to_css`]
+///
+/// n.b. `grass` does not currently support files or paths that are not valid UTF-8
+#[inline]
+pub fn compile_path_to_tree<P: AsRef<Path>>(p: P, options: &Options) -> Result<CssStylesheet> {
+    tree_from_string_with_file_name(String::from_utf8(options.fs.read(p.as_ref())?)?, p, options)
+}
+
+/// Compile a string to a tree of CSS nodes, which can be modified and then
+/// written with [`CssStylesheet::to_css`]
+///
+/// ```
+/// # use grass_compiler as grass;
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let tree = grass::compile_to_tree(
+///         "a { b { color: red !important; } }".to_owned(),
+///         &grass::Options::default(),
+///     )?;
+///
+///     if let grass::CssNode::StyleRule(rule) = &tree.children[0] {
+///         assert_eq!(rule.selectors, ["a b"]);
+///
+///         if let grass::CssNode::Declaration(declaration) = &rule.children[0] {
+///             assert_eq!(declaration.name, "color");
+///             assert_eq!(declaration.value, "red");
+///             assert!(declaration.important);
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[inline]
+pub fn compile_to_tree(input: String, options: &Options) -> Result<CssStylesheet> {
+    tree_from_string_with_file_name(input, "stdin", options)
+}
+
 #[cfg(feature = "wasm-exports")]
 #[wasm_bindgen(js_name = from_string)]
 pub fn from_string_js(input: String) -> std::result::Result<String, String> {
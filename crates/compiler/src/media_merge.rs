@@ -0,0 +1,363 @@
+//! An optional pass over the final CSS that combines `@media` rules with the
+//! same query, enabled by [`Options::media_merging`].
+//!
+//! Moving a rule changes its position in the cascade, so a `@media` rule is
+//! only moved past other CSS when none of it could override, or be overridden
+//! by, what it moves past.
+
+use std::mem;
+
+use crate::{
+    ast::{CssStmt, MediaRule},
+    common::unvendor,
+    selector::{ComplexSelector, ComplexSelectorComponent, SimpleSelector},
+    MediaMerging,
+};
+
+/// Something declared by a statement whose meaning may depend on its order
+/// relative to other declarations
+enum Declaration {
+    /// Properties declared by a style rule
+    Properties {
+        selector: Vec<ComplexSelector>,
+        properties: Vec<String>,
+    },
+
+    /// A named at-rule such as `@keyframes foo`, where a later rule with the
+    /// same name replaces an earlier one
+    AtRule(String),
+}
+
+pub(crate) fn merge_media_rules(stmts: Vec<CssStmt>, mode: MediaMerging) -> Vec<CssStmt> {
+    let stmts: Vec<CssStmt> = stmts
+        .into_iter()
+        .map(|mut stmt| {
+            if let Some(body) = child_statements(&mut stmt) {
+                *body = merge_media_rules(mem::take(body), mode);
+            }
+            stmt
+        })
+        .collect();
+
+    let stmts = merge_identical_queries(stmts);
+
+    if mode == MediaMerging::MobileFirst {
+        sort_mobile_first(stmts)
+    } else {
+        stmts
+    }
+}
+
+fn child_statements(stmt: &mut CssStmt) -> Option<&mut Vec<CssStmt>> {
+    match stmt {
+        CssStmt::RuleSet { body, .. } => Some(body),
+        CssStmt::Media(media, ..) => Some(&mut media.body),
+        CssStmt::UnknownAtRule(at_rule, ..) => Some(&mut at_rule.body),
+        CssStmt::Supports(supports, ..) => Some(&mut supports.body),
+        CssStmt::Layer(layer, ..) => Some(&mut layer.body),
+        CssStmt::Container(container, ..) => Some(&mut container.body),
+        CssStmt::Scope(scope, ..) => Some(&mut scope.body),
+        CssStmt::KeyframesRuleSet(..)
+        | CssStmt::Style(..)
+        | CssStmt::Comment(..)
+        | CssStmt::Import(..) => None,
+    }
+}
+
+fn query_key(media: &MediaRule) -> String {
+    media
+        .query
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Moves the contents of each `@media` rule into the next `@media` rule with
+/// the same query, where that doesn't change the cascade
+fn merge_identical_queries(stmts: Vec<CssStmt>) -> Vec<CssStmt> {
+    let keys: Vec<Option<String>> = stmts
+        .iter()
+        .map(|stmt| match stmt {
+            CssStmt::Media(media, ..) => Some(query_key(media)),
+            _ => None,
+        })
+        .collect();
+    let mut declarations: Vec<Vec<Declaration>> = stmts.iter().map(declarations).collect();
+    let mut stmts: Vec<Option<CssStmt>> = stmts.into_iter().map(Some).collect();
+    // Whether each rule has already been merged into, or tried against, a
+    // later rule with the same query
+    let mut visited = vec![false; stmts.len()];
+
+    for target in (0..stmts.len()).rev() {
+        let key = match &keys[target] {
+            Some(key) if !visited[target] => key,
+            _ => continue,
+        };
+
+        let mut target = target;
+
+        for idx in (0..target).rev() {
+            if visited[idx] || keys[idx].as_ref() != Some(key) {
+                continue;
+            }
+
+            visited[idx] = true;
+
+            let is_safe = (idx + 1..target)
+                .filter(|&between| stmts[between].is_some())
+                .all(|between| !conflicts(&declarations[idx], &declarations[between]));
+
+            // Earlier rules with this query may still be merged into this one
+            if !is_safe {
+                target = idx;
+                continue;
+            }
+
+            let earlier = stmts[idx].take().unwrap();
+            let was_group_end = earlier.is_group_end();
+
+            if let (CssStmt::Media(earlier, ..), Some(CssStmt::Media(later, ..))) =
+                (earlier, &mut stmts[target])
+            {
+                let mut body = earlier.body;
+                body.append(&mut later.body);
+                later.body = body;
+            }
+
+            let mut moved = mem::take(&mut declarations[idx]);
+            moved.append(&mut declarations[target]);
+            declarations[target] = moved;
+
+            // Keep the blank line that separated the removed rule from what
+            // followed it
+            if was_group_end {
+                if let Some(prev) = stmts[..idx].iter_mut().rev().find_map(Option::as_mut) {
+                    prev.set_group_end();
+                }
+            }
+        }
+    }
+
+    stmts.into_iter().flatten().collect()
+}
+
+/// Moves `@media` rules with a lone `min-width` query to the end, ordered from
+/// the narrowest to the widest, if none of them conflict with what they move
+/// past
+fn sort_mobile_first(stmts: Vec<CssStmt>) -> Vec<CssStmt> {
+    let widths: Vec<Option<f64>> = stmts
+        .iter()
+        .map(|stmt| match stmt {
+            CssStmt::Media(media, ..) => match media.query.as_slice() {
+                [query] => query.min_width(),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+
+    if widths.iter().all(Option::is_none) {
+        return stmts;
+    }
+
+    let declarations: Vec<Vec<Declaration>> = stmts.iter().map(declarations).collect();
+
+    for (idx, width) in widths.iter().enumerate() {
+        let width = match width {
+            Some(width) => *width,
+            None => continue,
+        };
+
+        // Everything after this rule that will end up before it
+        let is_safe = (idx + 1..stmts.len())
+            .filter(|&later| match widths[later] {
+                Some(later_width) => later_width < width,
+                None => true,
+            })
+            .all(|later| !conflicts(&declarations[idx], &declarations[later]));
+
+        if !is_safe {
+            return stmts;
+        }
+    }
+
+    let mut rest = Vec::with_capacity(stmts.len());
+    let mut sorted = Vec::new();
+
+    for (stmt, width) in stmts.into_iter().zip(widths) {
+        match width {
+            Some(width) => sorted.push((width, stmt)),
+            None => rest.push(stmt),
+        }
+    }
+
+    // This is a stable sort, so rules with the same width keep their order
+    sorted.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    if let Some(last) = rest.last_mut() {
+        last.set_group_end();
+    }
+
+    rest.extend(sorted.into_iter().map(|(_, mut stmt)| {
+        stmt.set_group_end();
+        stmt
+    }));
+
+    rest
+}
+
+fn declarations(stmt: &CssStmt) -> Vec<Declaration> {
+    let mut declarations = Vec::new();
+    collect_declarations(stmt, &mut declarations);
+    declarations
+}
+
+fn collect_declarations(stmt: &CssStmt, declarations: &mut Vec<Declaration>) {
+    match stmt {
+        CssStmt::RuleSet { selector, body, .. } => {
+            let properties: Vec<String> = body
+                .iter()
+                .filter_map(|child| match child {
+                    CssStmt::Style(style) => Some(style.property.to_string()),
+                    _ => None,
+                })
+                .collect();
+
+            if !properties.is_empty() {
+                declarations.push(Declaration::Properties {
+                    selector: selector.as_selector_list().components.clone(),
+                    properties,
+                });
+            }
+
+            for child in body {
+                collect_declarations(child, declarations);
+            }
+        }
+        CssStmt::UnknownAtRule(at_rule, ..) => {
+            declarations.push(Declaration::AtRule(format!(
+                "{} {}",
+                at_rule.name.to_ascii_lowercase(),
+                at_rule.params
+            )));
+
+            for child in &at_rule.body {
+                collect_declarations(child, declarations);
+            }
+        }
+        CssStmt::Media(media, ..) => media
+            .body
+            .iter()
+            .for_each(|child| collect_declarations(child, declarations)),
+        CssStmt::Supports(supports, ..) => supports
+            .body
+            .iter()
+            .for_each(|child| collect_declarations(child, declarations)),
+        CssStmt::Layer(layer, ..) => layer
+            .body
+            .iter()
+            .for_each(|child| collect_declarations(child, declarations)),
+        CssStmt::Container(container, ..) => container
+            .body
+            .iter()
+            .for_each(|child| collect_declarations(child, declarations)),
+        CssStmt::Scope(scope, ..) => scope
+            .body
+            .iter()
+            .for_each(|child| collect_declarations(child, declarations)),
+        CssStmt::KeyframesRuleSet(..)
+        | CssStmt::Style(..)
+        | CssStmt::Comment(..)
+        | CssStmt::Import(..) => {}
+    }
+}
+
+/// Whether the order of `a` and `b` could affect how a document is styled
+fn conflicts(a: &[Declaration], b: &[Declaration]) -> bool {
+    a.iter().any(|a| {
+        b.iter().any(|b| match (a, b) {
+            (
+                Declaration::Properties {
+                    selector: a_selector,
+                    properties: a_properties,
+                },
+                Declaration::Properties {
+                    selector: b_selector,
+                    properties: b_properties,
+                },
+            ) => {
+                a_properties
+                    .iter()
+                    .any(|a| b_properties.iter().any(|b| properties_overlap(a, b)))
+                    && a_selector
+                        .iter()
+                        .any(|a| b_selector.iter().any(|b| may_compete(a, b)))
+            }
+            (Declaration::AtRule(a), Declaration::AtRule(b)) => a == b,
+            _ => false,
+        })
+    })
+}
+
+/// Whether `a` and `b` may set the same value, as `margin` and `margin-top`
+/// or `transition` and `-webkit-transition` do
+fn properties_overlap(a: &str, b: &str) -> bool {
+    if a.starts_with("--") || b.starts_with("--") {
+        return a == b;
+    }
+
+    let a = unvendor(a);
+    let b = unvendor(b);
+
+    a == b
+        || a == "all"
+        || b == "all"
+        || b.strip_prefix(a).is_some_and(|rest| rest.starts_with('-'))
+        || a.strip_prefix(b).is_some_and(|rest| rest.starts_with('-'))
+}
+
+/// Whether `a` and `b` could match the same element with the same
+/// specificity, in which case their order decides which one applies
+fn may_compete(a: &ComplexSelector, b: &ComplexSelector) -> bool {
+    let a_specificity = a.specificity();
+    let b_specificity = b.specificity();
+
+    if a_specificity.max < b_specificity.min || b_specificity.max < a_specificity.min {
+        return false;
+    }
+
+    let (a, b) = match (a.components.last(), b.components.last()) {
+        (
+            Some(ComplexSelectorComponent::Compound(a)),
+            Some(ComplexSelectorComponent::Compound(b)),
+        ) => (a, b),
+        _ => return true,
+    };
+
+    let pseudo_elements = |components: &[SimpleSelector]| -> Vec<String> {
+        components
+            .iter()
+            .filter_map(|simple| match simple {
+                SimpleSelector::Pseudo(pseudo) if !pseudo.is_class => {
+                    Some(pseudo.normalized_name().to_owned())
+                }
+                _ => None,
+            })
+            .collect()
+    };
+
+    if pseudo_elements(&a.components) != pseudo_elements(&b.components) {
+        return false;
+    }
+
+    !a.components.iter().any(|a| {
+        b.components.iter().any(|b| match (a, b) {
+            (SimpleSelector::Type(a), SimpleSelector::Type(b)) => {
+                a.namespace == b.namespace && !a.ident.eq_ignore_ascii_case(&b.ident)
+            }
+            (SimpleSelector::Id(a), SimpleSelector::Id(b)) => a != b,
+            _ => false,
+        })
+    })
+}
@@ -0,0 +1,441 @@
+//! An optional pass over the final CSS that removes redundant output, enabled
+//! by [`Options::optimize`].
+//!
+//! Every transformation here must be safe for the cascade: the optimized
+//! stylesheet should style every document exactly as the original did.
+
+use std::{mem, sync::Arc};
+
+use codemap::Span;
+
+use crate::{
+    ast::{CssStmt, Style},
+    color::{Color, ColorFormat},
+    common::{Brackets, ListSeparator, QuoteKind},
+    selector::{ComplexSelectorComponent, ExtendedSelector, SelectorList, SimpleSelector},
+    serializer::serialize_value,
+    value::{SassNumber, Value},
+    Options, OutputStyle,
+};
+
+/// Shorthand properties whose value is a list of up to 4 components, for the
+/// top, right, bottom and left sides
+const BOX_SHORTHANDS: &[&str] = &[
+    "margin",
+    "padding",
+    "inset",
+    "border-width",
+    "border-style",
+    "border-color",
+    "scroll-margin",
+    "scroll-padding",
+];
+
+/// Pseudo-classes and pseudo-elements that every browser understands
+const UNIVERSALLY_SUPPORTED_PSEUDOS: &[&str] = &[
+    "active",
+    "after",
+    "before",
+    "checked",
+    "disabled",
+    "empty",
+    "enabled",
+    "first-child",
+    "first-letter",
+    "first-line",
+    "first-of-type",
+    "focus",
+    "hover",
+    "lang",
+    "last-child",
+    "last-of-type",
+    "link",
+    "nth-child",
+    "nth-last-child",
+    "nth-last-of-type",
+    "nth-of-type",
+    "only-child",
+    "only-of-type",
+    "root",
+    "target",
+    "visited",
+];
+
+pub(crate) struct Optimizer<'a> {
+    options: &'a Options<'a>,
+    span: Span,
+}
+
+impl<'a> Optimizer<'a> {
+    pub fn new(options: &'a Options<'a>, span: Span) -> Self {
+        Self { options, span }
+    }
+
+    pub fn optimize(&self, stmts: Vec<CssStmt>) -> Vec<CssStmt> {
+        self.optimize_children(stmts)
+    }
+
+    fn optimize_children(&self, stmts: Vec<CssStmt>) -> Vec<CssStmt> {
+        let mut optimized: Vec<CssStmt> = Vec::with_capacity(stmts.len());
+
+        for stmt in stmts {
+            let stmt = self.optimize_stmt(stmt);
+
+            if stmt.is_invisible() {
+                continue;
+            }
+
+            if let Some(prev) = optimized.last_mut() {
+                if self.merge_rules(prev, &stmt) {
+                    continue;
+                }
+            }
+
+            optimized.push(stmt);
+        }
+
+        optimized
+    }
+
+    fn optimize_stmt(&self, stmt: CssStmt) -> CssStmt {
+        match stmt {
+            CssStmt::RuleSet {
+                selector,
+                body,
+                is_group_end,
+                nested_selector,
+                depth,
+            } => CssStmt::RuleSet {
+                selector,
+                body: self.remove_overridden_declarations(self.optimize_children(body)),
+                is_group_end,
+                nested_selector,
+                depth,
+            },
+            CssStmt::Style(style) => CssStmt::Style(self.optimize_style(style)),
+            CssStmt::Media(mut media, is_group_end) => {
+                media.body = self.optimize_children(media.body);
+                CssStmt::Media(media, is_group_end)
+            }
+            CssStmt::UnknownAtRule(mut at_rule, is_group_end) => {
+                at_rule.body = self.optimize_children(at_rule.body);
+                CssStmt::UnknownAtRule(at_rule, is_group_end)
+            }
+            CssStmt::Supports(mut supports, is_group_end) => {
+                supports.body = self.optimize_children(supports.body);
+                CssStmt::Supports(supports, is_group_end)
+            }
+            CssStmt::Layer(mut layer, is_group_end) => {
+                layer.body = self.optimize_children(layer.body);
+                CssStmt::Layer(layer, is_group_end)
+            }
+            CssStmt::Container(mut container, is_group_end) => {
+                container.body = self.optimize_children(container.body);
+                CssStmt::Container(container, is_group_end)
+            }
+            CssStmt::Scope(mut scope, is_group_end) => {
+                scope.body = self.optimize_children(scope.body);
+                CssStmt::Scope(scope, is_group_end)
+            }
+            CssStmt::KeyframesRuleSet(mut keyframes) => {
+                keyframes.body =
+                    self.remove_overridden_declarations(self.optimize_children(keyframes.body));
+                CssStmt::KeyframesRuleSet(keyframes)
+            }
+            CssStmt::Comment(..) | CssStmt::Import(..) => stmt,
+        }
+    }
+
+    /// Merges `next` into `prev` if they're adjacent style rules with either
+    /// the same selector or the same declarations.
+    ///
+    /// Returns whether `next` was merged.
+    fn merge_rules(&self, prev: &mut CssStmt, next: &CssStmt) -> bool {
+        let (
+            CssStmt::RuleSet {
+                selector: prev_selector,
+                body: prev_body,
+                is_group_end: prev_is_group_end,
+                nested_selector: None,
+                ..
+            },
+            CssStmt::RuleSet {
+                selector: next_selector,
+                body: next_body,
+                is_group_end: next_is_group_end,
+                nested_selector: None,
+                ..
+            },
+        ) = (prev, next)
+        else {
+            return false;
+        };
+
+        if *prev_selector.as_selector_list() == *next_selector.as_selector_list() {
+            prev_body.extend(next_body.iter().cloned());
+            *prev_body = self.remove_overridden_declarations(mem::take(prev_body));
+            *prev_is_group_end |= *next_is_group_end;
+            return true;
+        }
+
+        // If any selector in a list is invalid, browsers drop the entire rule,
+        // so selectors that only some browsers support can't be combined
+        if !self.has_same_declarations(prev_body, next_body)
+            || !is_universally_supported(&prev_selector.as_selector_list())
+            || !is_universally_supported(&next_selector.as_selector_list())
+        {
+            return false;
+        }
+
+        let mut merged = prev_selector.as_selector_list().clone();
+        for complex in &next_selector.as_selector_list().components {
+            if !merged.components.contains(complex) {
+                merged.components.push(complex.clone());
+            }
+        }
+
+        *prev_selector = ExtendedSelector::new(merged);
+        *prev_is_group_end |= *next_is_group_end;
+
+        true
+    }
+
+    fn has_same_declarations(&self, a: &[CssStmt], b: &[CssStmt]) -> bool {
+        a.len() == b.len()
+            && a.iter().zip(b).all(|(a, b)| match (a, b) {
+                (CssStmt::Style(a), CssStmt::Style(b)) => {
+                    a.property == b.property
+                        && a.declared_as_custom_property == b.declared_as_custom_property
+                        && self.serialize(&a.value.node).is_some()
+                        && self.serialize(&a.value.node) == self.serialize(&b.value.node)
+                }
+                _ => false,
+            })
+    }
+
+    /// Removes declarations that are overridden by a later declaration of the
+    /// same property in the same block.
+    ///
+    /// Declarations that may be fallbacks for browsers that don't understand
+    /// the later value, such as `display: -webkit-box; display: flex`, are kept.
+    fn remove_overridden_declarations(&self, body: Vec<CssStmt>) -> Vec<CssStmt> {
+        let mut kept = Vec::with_capacity(body.len());
+        // The latest declaration of each property, since the last nested rule
+        let mut later: Vec<(&str, bool, Option<String>)> = Vec::new();
+
+        for stmt in body.into_iter().rev() {
+            let style = match &stmt {
+                CssStmt::Style(style) => style,
+                CssStmt::Comment(..) => {
+                    kept.push(stmt);
+                    continue;
+                }
+                _ => {
+                    later.clear();
+                    kept.push(stmt);
+                    continue;
+                }
+            };
+
+            let property = style.property.resolve_ref();
+            let is_important = is_important(&style.value.node);
+            let value = self.serialize(&style.value.node);
+
+            let is_overridden =
+                later
+                    .iter()
+                    .any(|(later_property, later_important, later_value)| {
+                        *later_property == property
+                            && (*later_important || !is_important)
+                            && (style.declared_as_custom_property
+                                || (value.is_some() && value == *later_value)
+                                || (!may_be_fallback(value.as_deref())
+                                    && !may_be_fallback(later_value.as_deref())))
+                    });
+
+            if is_overridden {
+                continue;
+            }
+
+            later.retain(|(later_property, ..)| *later_property != property);
+            later.push((property, is_important, value));
+            kept.push(stmt);
+        }
+
+        kept.reverse();
+        kept
+    }
+
+    fn optimize_style(&self, mut style: Style) -> Style {
+        if style.declared_as_custom_property {
+            return style;
+        }
+
+        let property = style.property.resolve_ref();
+
+        let value = self.optimize_value(style.value.node.clone(), property != "flex");
+
+        style.value.node = if BOX_SHORTHANDS.contains(&property) {
+            collapse_box_shorthand(value)
+        } else {
+            value
+        };
+
+        style
+    }
+
+    fn optimize_value(&self, value: Value, shorten_zeros: bool) -> Value {
+        match value {
+            Value::Dimension(SassNumber {
+                num,
+                unit,
+                as_slash: None,
+            }) if shorten_zeros && num.is_zero() && unit.is_length() => {
+                Value::Dimension(SassNumber::new_unitless(0))
+            }
+            Value::Color(color) => Value::Color(self.shorten_color(color)),
+            Value::List(elems, separator, brackets) => Value::List(
+                elems
+                    .into_iter()
+                    .map(|elem| self.optimize_value(elem, shorten_zeros))
+                    .collect(),
+                separator,
+                brackets,
+            ),
+            value => value,
+        }
+    }
+
+    /// Writes opaque colors in their shortest form, such as `#fff` for
+    /// `#ffffff` and `red` for `rgb(255, 0, 0)`
+    fn shorten_color(&self, color: Arc<Color>) -> Arc<Color> {
+        if !color.alpha().is_one() || color.space_channels().is_some() {
+            return color;
+        }
+
+        let compressed = Options::default().style(OutputStyle::Compressed);
+        let shortest =
+            match serialize_value(&Value::Color(Arc::clone(&color)), &compressed, self.span) {
+                Ok(shortest) => shortest,
+                Err(..) => return color,
+            };
+
+        let mut color = (*color).clone();
+        color.format = ColorFormat::Literal(shortest);
+        Arc::new(color)
+    }
+
+    fn serialize(&self, value: &Value) -> Option<String> {
+        serialize_value(value, self.options, self.span).ok()
+    }
+}
+
+fn is_important(value: &Value) -> bool {
+    match value {
+        Value::List(elems, ListSeparator::Space, Brackets::None) => {
+            matches!(elems.last(), Some(Value::String(s, QuoteKind::None)) if s == "!important")
+        }
+        _ => false,
+    }
+}
+
+/// Whether a value may not be understood by every browser, in which case an
+/// earlier declaration of the same property could be a deliberate fallback
+fn may_be_fallback(value: Option<&str>) -> bool {
+    let value = match value {
+        Some(value) => value,
+        None => return true,
+    };
+
+    value.contains('(')
+        || value.contains('\\')
+        || value
+            .split(|c: char| c.is_ascii_whitespace() || c == ',' || c == '/')
+            .any(|word| word.starts_with('-') && word[1..].starts_with(char::is_alphabetic))
+}
+
+/// Whether every browser understands every selector in `list`
+fn is_universally_supported(list: &SelectorList) -> bool {
+    list.components.iter().all(|complex| {
+        complex.components.iter().all(|component| match component {
+            ComplexSelectorComponent::Combinator(..) => true,
+            ComplexSelectorComponent::Compound(compound) => {
+                compound.components.iter().all(|simple| match simple {
+                    SimpleSelector::Type(..)
+                    | SimpleSelector::Class(..)
+                    | SimpleSelector::Id(..)
+                    | SimpleSelector::Attribute(..)
+                    | SimpleSelector::Universal(..) => true,
+                    SimpleSelector::Pseudo(pseudo) => {
+                        pseudo.selector.is_none()
+                            && UNIVERSALLY_SUPPORTED_PSEUDOS
+                                .contains(&pseudo.name.to_ascii_lowercase().as_str())
+                    }
+                    SimpleSelector::Placeholder(..) | SimpleSelector::Parent(..) => false,
+                })
+            }
+        })
+    })
+}
+
+/// Collapses a value like `margin: 0 0 0 0` to `margin: 0`
+fn collapse_box_shorthand(value: Value) -> Value {
+    let (mut elems, separator, brackets) = match value {
+        Value::List(elems, ListSeparator::Space, Brackets::None) => {
+            (elems, ListSeparator::Space, Brackets::None)
+        }
+        value => return value,
+    };
+
+    let important = if is_important(&Value::List(elems.clone(), separator, brackets)) {
+        elems.pop()
+    } else {
+        None
+    };
+
+    if !(2..=4).contains(&elems.len())
+        || elems.iter().any(|elem| {
+            !matches!(
+                elem,
+                Value::Dimension(..) | Value::Color(..) | Value::String(..)
+            )
+        })
+    {
+        if let Some(important) = important {
+            elems.push(important);
+        }
+        return Value::List(elems, separator, brackets);
+    }
+
+    if elems.len() == 4 && elems[3] == elems[1] && same_type(&elems[3], &elems[1]) {
+        elems.pop();
+    }
+
+    if elems.len() == 3 && elems[2] == elems[0] && same_type(&elems[2], &elems[0]) {
+        elems.pop();
+    }
+
+    if elems.len() == 2 && elems[1] == elems[0] && same_type(&elems[1], &elems[0]) {
+        elems.pop();
+    }
+
+    match important {
+        Some(important) => {
+            elems.push(important);
+            Value::List(elems, separator, brackets)
+        }
+        None if elems.len() == 1 => elems.pop().unwrap(),
+        None => Value::List(elems, separator, brackets),
+    }
+}
+
+/// Sass equality considers values like `1in` and `96px`, or `"a"` and `a`, to
+/// be equal, but they're only interchangeable here if they're written the same
+fn same_type(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Dimension(a), Value::Dimension(b)) => a.unit == b.unit,
+        (Value::String(_, a), Value::String(_, b)) => a == b,
+        (Value::Color(..), Value::Color(..)) => true,
+        _ => false,
+    }
+}
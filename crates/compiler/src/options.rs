@@ -1,6 +1,8 @@
 use std::path::{Path, PathBuf};
 
-use crate::{Fs, StdFs};
+#[cfg(feature = "config")]
+use crate::{error::SassResult, Config};
+use crate::{Fs, PkgImporter, StdFs};
 
 /// Configuration for Sass compilation
 ///
@@ -15,6 +17,16 @@ pub struct Options<'a> {
     pub(crate) unicode_error_messages: bool,
     pub(crate) quiet: bool,
     pub(crate) input_syntax: Option<InputSyntax>,
+    pub(crate) pkg_importer: Option<PkgImporter>,
+    pub(crate) nesting: Nesting,
+    pub(crate) random_seed: Option<u64>,
+    pub(crate) source_comments: bool,
+    pub(crate) optimize: bool,
+    pub(crate) media_merging: MediaMerging,
+    pub(crate) comments: Option<Comments>,
+    pub(crate) indent: Indent,
+    pub(crate) line_ending: LineEnding,
+    pub(crate) trailing_newline: Option<bool>,
 }
 
 impl Default for Options<'_> {
@@ -28,6 +40,16 @@ impl Default for Options<'_> {
             unicode_error_messages: true,
             quiet: false,
             input_syntax: None,
+            pkg_importer: None,
+            nesting: Nesting::Flatten,
+            random_seed: None,
+            source_comments: false,
+            optimize: false,
+            media_merging: MediaMerging::Preserve,
+            comments: None,
+            indent: Indent::Spaces(2),
+            line_ending: LineEnding::Lf,
+            trailing_newline: None,
         }
     }
 }
@@ -44,11 +66,14 @@ impl<'a> Options<'a> {
         self
     }
 
-    /// `grass` currently offers 2 different output styles
+    /// `grass` currently offers 4 different output styles
     ///
     ///  - [`OutputStyle::Expanded`] writes each selector and declaration on its own line.
     ///  - [`OutputStyle::Compressed`] removes as many extra characters as possible
     ///    and writes the entire stylesheet on a single line.
+    ///  - [`OutputStyle::Nested`] indents style rules by their nesting depth in the
+    ///    source and closes blocks at the end of their last line.
+    ///  - [`OutputStyle::Compact`] writes each style rule on a single line.
     ///
     /// By default, output is expanded.
     #[must_use]
@@ -108,6 +133,22 @@ impl<'a> Options<'a> {
         self
     }
 
+    /// Enable a built-in importer for `pkg:` URLs, such as `@use "pkg:bootstrap"`.
+    ///
+    /// With [`PkgImporter::Node`], packages are resolved from `node_modules`
+    /// directories, starting next to the importing file and walking up the
+    /// directory tree. The `exports`, `sass`, and `style` fields of the
+    /// package's `package.json` determine which stylesheet is loaded.
+    ///
+    /// By default, no package importer is enabled and `pkg:` URLs cannot be
+    /// resolved.
+    #[must_use]
+    #[inline]
+    pub const fn pkg_importer(mut self, pkg_importer: PkgImporter) -> Self {
+        self.pkg_importer = Some(pkg_importer);
+        self
+    }
+
     /// This flag tells Sass whether to emit a `@charset`
     /// declaration or a UTF-8 byte-order mark.
     ///
@@ -150,9 +191,195 @@ impl<'a> Options<'a> {
         self
     }
 
+    /// This option controls how nested style rules, such as
+    /// `.card { &:hover { .. } .title { .. } }`, are emitted.
+    ///
+    ///  - [`Nesting::Flatten`] resolves `&` and writes each nested rule on its
+    ///    own at the top level.
+    ///  - [`Nesting::Preserve`] keeps the nested rules, and any `@media`,
+    ///    `@supports`, `@layer`, `@container` and `@scope` rules nested within
+    ///    them, as native CSS nesting.
+    ///
+    /// When preserving, rules that CSS nesting can't express are still
+    /// flattened. This includes selectors that use Sass-only features such as
+    /// `&-suffix`, rules moved by `@at-root`, rules whose selectors are
+    /// changed by `@extend`, and rules nested within a selector list whose
+    /// selectors differ in specificity, such as `.a, #b`, since CSS nesting
+    /// would match them with the specificity of `#b`.
+    ///
+    /// By default, nesting is flattened.
+    #[must_use]
+    #[inline]
+    pub const fn nesting(mut self, nesting: Nesting) -> Self {
+        self.nesting = nesting;
+        self
+    }
+
+    /// This option seeds the generator used by `random()`, `math.random()` and
+    /// `unique-id()`.
+    ///
+    /// Compiling the same input with the same seed will always produce the
+    /// same output, which is useful for content-hashed caching and snapshot
+    /// tests. Each call to these functions still returns a different value
+    /// within a single compilation.
+    ///
+    /// By default, with the `random` feature enabled, a new seed is chosen for
+    /// each compilation. Without it, a fixed seed is used.
+    #[must_use]
+    #[inline]
+    pub const fn random_seed(mut self, seed: u64) -> Self {
+        self.random_seed = Some(seed);
+        self
+    }
+
+    /// This option, when `true`, writes a comment such as
+    /// `/* line 42, components/_button.scss */` above each style rule, pointing
+    /// to where it was declared. Paths are relative to the directory of the
+    /// entrypoint.
+    ///
+    /// This is useful for debugging in environments that can't consume source
+    /// maps. These comments are only written in the expanded and nested output
+    /// styles.
+    ///
+    /// By default, this value is `false`.
+    #[must_use]
+    #[inline]
+    pub const fn source_comments(mut self, source_comments: bool) -> Self {
+        self.source_comments = source_comments;
+        self
+    }
+
+    /// This option, when `true`, makes the output smaller by removing redundant
+    /// CSS before it is written. This
+    ///
+    ///  - removes declarations overridden by a later declaration of the same
+    ///    property in the same rule, unless the earlier one may be a fallback
+    ///  - merges adjacent style rules with the same selector, and adjacent
+    ///    style rules with the same declarations whose selectors every browser
+    ///    supports
+    ///  - shortens zero lengths like `0px` to `0`, box shorthands like
+    ///    `margin: 0 0 0 0` to `margin: 0`, and colors like `#ffffff` to `#fff`
+    ///  - removes empty style rules
+    ///
+    /// None of these change how the stylesheet applies to a document. This
+    /// works with every output style, but is most useful alongside
+    /// [`OutputStyle::Compressed`].
+    ///
+    /// By default, this value is `false`.
+    #[must_use]
+    #[inline]
+    pub const fn optimize(mut self, optimize: bool) -> Self {
+        self.optimize = optimize;
+        self
+    }
+
+    /// This option controls whether `@media` rules with the same query, such
+    /// as those emitted by a breakpoint mixin in every component, are combined.
+    ///
+    ///  - [`MediaMerging::Preserve`] writes every `@media` rule where it occurs.
+    ///  - [`MediaMerging::Merge`] moves the contents of each `@media` rule into
+    ///    the last `@media` rule with the same query.
+    ///  - [`MediaMerging::MobileFirst`] additionally moves `@media` rules with a
+    ///    single `min-width` query to the end of the stylesheet, ordered from
+    ///    the narrowest to the widest.
+    ///
+    /// Rules are only moved when that can't change which declarations apply to
+    /// an element. If a rule declares a property that a rule it would move past
+    /// also declares, for a selector that could match the same element with the
+    /// same specificity, it is left in place.
+    ///
+    /// By default, `@media` rules are preserved.
+    #[must_use]
+    #[inline]
+    pub const fn media_merging(mut self, media_merging: MediaMerging) -> Self {
+        self.media_merging = media_merging;
+        self
+    }
+
+    /// This option controls which loud comments (`/* .. */`) are written to
+    /// the output. Silent comments (`// ..`) are never written.
+    ///
+    ///  - [`Comments::Keep`] writes every loud comment.
+    ///  - [`Comments::KeepImportant`] only writes comments beginning with
+    ///    `/*!`, which are conventionally used for license headers.
+    ///  - [`Comments::Strip`] doesn't write any comments.
+    ///
+    /// By default, every comment is kept, except in [`OutputStyle::Compressed`]
+    /// output, where only comments beginning with `/*!` are kept.
+    #[must_use]
+    #[inline]
+    pub const fn comments(mut self, comments: Comments) -> Self {
+        self.comments = Some(comments);
+        self
+    }
+
+    /// This option controls how nested blocks are indented in expanded and
+    /// nested output, as either [`Indent::Spaces`] or [`Indent::Tabs`]. It
+    /// has no effect on compressed and compact output, which aren't indented.
+    ///
+    /// By default, each level is indented with 2 spaces.
+    #[must_use]
+    #[inline]
+    pub const fn indent(mut self, indent: Indent) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// This option controls whether lines are separated with `\n` or `\r\n`.
+    ///
+    /// This applies to every line break in the output, including those
+    /// within comments and custom property values. Unlike `dart-sass`, `grass`
+    /// does not write a stylesheet describing compilation errors ("error CSS"),
+    /// so error messages are unaffected by this option and by [`Options::indent`].
+    ///
+    /// By default, lines end with [`LineEnding::Lf`].
+    #[must_use]
+    #[inline]
+    pub const fn line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// This option controls whether non-empty output ends with a line break.
+    ///
+    /// By default, a trailing line break is written unless output is
+    /// compressed.
+    #[must_use]
+    #[inline]
+    pub const fn trailing_newline(mut self, trailing_newline: bool) -> Self {
+        self.trailing_newline = Some(trailing_newline);
+        self
+    }
+
+    /// Create options from a `grass.toml` config file
+    ///
+    /// This is equivalent to calling [`Config::apply`] on the default options,
+    /// and allows build scripts to share configuration with the commandline
+    /// interface. See [`Config`] for a description of the file format.
+    ///
+    /// This requires the `"config"` feature, which is enabled by default.
+    #[cfg(feature = "config")]
+    pub fn from_config<P: AsRef<Path>>(path: P) -> SassResult<Options<'static>> {
+        Ok(Config::from_path(path)?.apply(Options::default()))
+    }
+
     pub(crate) fn is_compressed(&self) -> bool {
         matches!(self.style, OutputStyle::Compressed)
     }
+
+    pub(crate) fn writes_trailing_newline(&self) -> bool {
+        self.trailing_newline.unwrap_or(!self.is_compressed())
+    }
+
+    /// Whether the loud comment `comment` should be written to the output
+    pub(crate) fn keeps_comment(&self, comment: &str) -> bool {
+        match self.comments {
+            Some(Comments::Keep) => true,
+            Some(Comments::KeepImportant) => comment.starts_with("/*!"),
+            Some(Comments::Strip) => false,
+            None => !self.is_compressed() || comment.starts_with("/*!"),
+        }
+    }
 }
 
 /// Useful when parsing Sass from sources other than the file system
@@ -181,6 +408,83 @@ impl InputSyntax {
     }
 }
 
+/// How nested style rules are emitted
+///
+/// See [`Options::nesting`] for additional information
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Nesting {
+    /// Resolve `&` and emit every nested style rule at the top level.
+    ///
+    /// This is the default.
+    Flatten,
+
+    /// Keep nested style rules nested, using native CSS nesting.
+    Preserve,
+}
+
+/// How nested blocks are indented
+///
+/// See [`Options::indent`] for additional information
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Indent {
+    /// Indent each level with the given number of spaces
+    Spaces(usize),
+
+    /// Indent each level with a single tab
+    Tabs,
+}
+
+/// The line break written between lines
+///
+/// See [`Options::line_ending`] for additional information
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// `\n`, as used on Unix-like systems
+    Lf,
+
+    /// `\r\n`, as used on Windows
+    Crlf,
+}
+
+/// Which loud comments are written to the output
+///
+/// See [`Options::comments`] for additional information
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Comments {
+    /// Write every loud comment
+    Keep,
+
+    /// Only write comments beginning with `/*!`
+    KeepImportant,
+
+    /// Don't write any comments
+    Strip,
+}
+
+/// How `@media` rules with the same query are combined
+///
+/// See [`Options::media_merging`] for additional information
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MediaMerging {
+    /// Write every `@media` rule where it occurs.
+    ///
+    /// This is the default.
+    Preserve,
+
+    /// Combine `@media` rules with the same query at the position of the last
+    /// one.
+    Merge,
+
+    /// Combine `@media` rules with the same query, then move those with a
+    /// single `min-width` query to the end, from the narrowest to the widest.
+    MobileFirst,
+}
+
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum OutputStyle {
@@ -192,4 +496,14 @@ pub enum OutputStyle {
     /// Ideal for release builds, this mode removes as many extra characters as
     /// possible and writes the entire stylesheet on a single line.
     Compressed,
+
+    /// This mode, from libsass and Ruby Sass, writes each declaration on its
+    /// own line and indents style rules by how deeply they were nested in the
+    /// source. Closing braces are written at the end of the last line of a
+    /// block.
+    Nested,
+
+    /// This mode, from libsass and Ruby Sass, writes each style rule on a
+    /// single line.
+    Compact,
 }
@@ -0,0 +1,215 @@
+use crate::{
+    ast::{ContainerQuery, ScopePrelude},
+    error::SassResult,
+    lexer::Lexer,
+    selector::SelectorParser,
+};
+
+use super::BaseParser;
+
+/// Parses the already-interpolated preludes of `@layer`, `@container` and
+/// `@scope` rules
+pub(crate) struct AtRulePreludeParser<'a> {
+    pub toks: Lexer<'a>,
+}
+
+impl<'a> BaseParser<'a> for AtRulePreludeParser<'a> {
+    fn toks(&self) -> &Lexer<'a> {
+        &self.toks
+    }
+
+    fn toks_mut(&mut self) -> &mut Lexer<'a> {
+        &mut self.toks
+    }
+}
+
+impl<'a> AtRulePreludeParser<'a> {
+    pub fn new(toks: Lexer<'a>) -> AtRulePreludeParser<'a> {
+        AtRulePreludeParser { toks }
+    }
+
+    pub fn parse_layer_names(&mut self) -> SassResult<Vec<String>> {
+        let mut names = Vec::new();
+        loop {
+            self.whitespace()?;
+            names.push(self.parse_layer_name()?);
+            self.whitespace()?;
+
+            if !self.scan_char(',') {
+                break;
+            }
+        }
+
+        self.expect_done()?;
+
+        Ok(names)
+    }
+
+    pub fn parse_container_queries(&mut self) -> SassResult<Vec<ContainerQuery>> {
+        let mut queries = Vec::new();
+        loop {
+            self.whitespace()?;
+            queries.push(self.parse_container_query()?);
+            self.whitespace()?;
+
+            if !self.scan_char(',') {
+                break;
+            }
+        }
+
+        self.expect_done()?;
+
+        Ok(queries)
+    }
+
+    pub fn parse_scope_prelude(&mut self) -> SassResult<ScopePrelude> {
+        self.whitespace()?;
+
+        let start = if self.toks.next_char_is('(') {
+            Some(self.parse_scope_selector()?)
+        } else {
+            None
+        };
+
+        self.whitespace()?;
+
+        let end = if self.scan_identifier("to", false)? {
+            self.whitespace()?;
+            Some(self.parse_scope_selector()?)
+        } else {
+            None
+        };
+
+        self.whitespace()?;
+        self.expect_done()?;
+
+        Ok(ScopePrelude { start, end })
+    }
+
+    fn expect_done(&mut self) -> SassResult<()> {
+        if self.toks.next().is_some() {
+            return Err(("expected no more input.", self.toks.current_span()).into());
+        }
+
+        Ok(())
+    }
+
+    /// A layer name is a sequence of identifiers separated by periods, e.g.
+    /// `framework.theme`
+    fn parse_layer_name(&mut self) -> SassResult<String> {
+        let mut name = self.parse_identifier(false, false)?;
+
+        while self.scan_char('.') {
+            name.push('.');
+            name.push_str(&self.parse_identifier(false, false)?);
+        }
+
+        Ok(name)
+    }
+
+    fn parse_container_query(&mut self) -> SassResult<ContainerQuery> {
+        let mut name = None;
+
+        if self.looking_at_identifier() {
+            let start = self.toks.cursor();
+            let identifier = self.parse_identifier(false, false)?;
+            let lower = identifier.to_ascii_lowercase();
+
+            // `not` and functional queries like `style(..)` begin the condition
+            // rather than naming the container
+            if lower == "not" || self.toks.next_char_is('(') {
+                self.toks.set_cursor(start);
+            } else {
+                if matches!(lower.as_str(), "none" | "and" | "or") {
+                    return Err((
+                        format!("Invalid container name \"{}\".", identifier),
+                        self.toks.span_from(start),
+                    )
+                        .into());
+                }
+
+                name = Some(identifier);
+                self.whitespace()?;
+
+                if self.toks.peek().is_none() || self.toks.next_char_is(',') {
+                    return Ok(ContainerQuery {
+                        name,
+                        condition: None,
+                    });
+                }
+            }
+        }
+
+        Ok(ContainerQuery {
+            name,
+            condition: Some(self.parse_container_condition()?),
+        })
+    }
+
+    fn parse_container_condition(&mut self) -> SassResult<String> {
+        if self.scan_identifier("not", false)? {
+            self.expect_whitespace()?;
+            return Ok(format!("not {}", self.parse_query_in_parens()?));
+        }
+
+        let mut result = self.parse_query_in_parens()?;
+        self.whitespace()?;
+
+        let operator = if self.scan_identifier("and", false)? {
+            "and"
+        } else if self.scan_identifier("or", false)? {
+            "or"
+        } else {
+            return Ok(result);
+        };
+
+        loop {
+            self.expect_whitespace()?;
+            result.push(' ');
+            result.push_str(operator);
+            result.push(' ');
+            result.push_str(&self.parse_query_in_parens()?);
+            self.whitespace()?;
+
+            if !self.scan_identifier(operator, false)? {
+                return Ok(result);
+            }
+        }
+    }
+
+    /// Either a parenthesized size query such as `(min-width: 400px)` or a
+    /// functional query such as `style(--theme: dark)`
+    fn parse_query_in_parens(&mut self) -> SassResult<String> {
+        let function_name = if self.looking_at_identifier() {
+            self.parse_identifier(false, false)?
+        } else {
+            String::new()
+        };
+
+        self.expect_char('(')?;
+        let result = format!(
+            "{}({})",
+            function_name,
+            self.declaration_value(false)?.trim()
+        );
+        self.expect_char(')')?;
+
+        Ok(result)
+    }
+
+    fn parse_scope_selector(&mut self) -> SassResult<String> {
+        self.expect_char('(')?;
+
+        let start = self.toks.cursor();
+        let selector = self.declaration_value(false)?;
+        let span = self.toks.span_from(start);
+
+        self.expect_char(')')?;
+
+        // Validate the selector, but emit it as written
+        let sel_toks = Lexer::new_from_string(&selector, span);
+        SelectorParser::new(sel_toks, true, false, span).parse()?;
+
+        Ok(selector.trim().to_owned())
+    }
+}
@@ -4,7 +4,7 @@ use codemap::{CodeMap, Span, Spanned};
 
 use crate::{
     ast::*, builtin::DISALLOWED_PLAIN_CSS_FUNCTION_NAMES, common::QuoteKind, error::SassResult,
-    lexer::Lexer, ContextFlags, Options,
+    lexer::Lexer, ContextFlags, Options, Token,
 };
 
 use super::{value::ValueParser, BaseParser, StylesheetParser};
@@ -77,6 +77,65 @@ impl<'a> StylesheetParser<'a> for CssParser<'a> {
     const IDENTIFIER_LIKE: Option<fn(&mut Self) -> SassResult<Spanned<AstExpr>>> =
         Some(Self::parse_identifier_like);
 
+    /// Following the CSS Nesting Module, anything that reaches a `{` before the
+    /// end of the statement is a style rule. Custom properties, and names
+    /// followed by a colon and whitespace as in `a: b {`, are always parsed as
+    /// declarations so that nested declarations are reported as such.
+    fn looking_at_nested_style_rule(&mut self) -> bool {
+        if matches!(self.toks.peek(), Some(Token { kind: '-', .. }))
+            && matches!(self.toks.peek_n(1), Some(Token { kind: '-', .. }))
+        {
+            return false;
+        }
+
+        let start = self.toks.cursor();
+
+        if self.looking_at_identifier()
+            && self.parse_identifier(false, false).is_ok()
+            && self.whitespace().is_ok()
+            && self.scan_char(':')
+            && matches!(
+                self.toks.peek(),
+                Some(Token {
+                    kind: ' ' | '\t' | '\n' | '\r' | '{',
+                    ..
+                }) | None
+            )
+        {
+            self.toks.set_cursor(start);
+            return false;
+        }
+
+        self.toks.set_cursor(start);
+
+        let mut depth = 0_usize;
+        let mut quote: Option<char> = None;
+        let mut is_style_rule = false;
+
+        while let Some(tok) = self.toks.next() {
+            match (quote, tok.kind) {
+                (_, '\\') => {
+                    self.toks.next();
+                }
+                (Some(q), c) if c == q => quote = None,
+                (Some(..), _) => {}
+                (None, '"' | '\'') => quote = Some(tok.kind),
+                (None, '(' | '[') => depth += 1,
+                (None, ')' | ']') => depth = depth.saturating_sub(1),
+                (None, '{') if depth == 0 => {
+                    is_style_rule = true;
+                    break;
+                }
+                (None, ';' | '}') if depth == 0 => break,
+                (None, _) => {}
+            }
+        }
+
+        self.toks.set_cursor(start);
+
+        is_style_rule
+    }
+
     fn parse_at_rule(
         &mut self,
         _child: fn(&mut Self) -> SassResult<AstStmt>,
@@ -99,8 +158,11 @@ impl<'a> StylesheetParser<'a> for CssParser<'a> {
                     .into())
             }
             Some("import") => self.parse_css_import_rule(start),
+            Some("container") => self.parse_container_rule(start),
+            Some("layer") => self.parse_layer_rule(start),
             Some("media") => self.parse_media_rule(start),
             Some("-moz-document") => self._parse_moz_document_rule(name),
+            Some("scope") => self.parse_scope_rule(start),
             Some("supports") => self.parse_supports_rule(),
             _ => self.unknown_at_rule(name, start),
         }
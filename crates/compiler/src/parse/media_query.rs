@@ -1,4 +1,8 @@
-use crate::{ast::MediaQuery, error::SassResult, lexer::Lexer};
+use crate::{
+    ast::{MediaCondition, MediaQuery},
+    error::SassResult,
+    lexer::Lexer,
+};
 
 use super::BaseParser;
 
@@ -67,7 +71,7 @@ impl<'a> MediaQueryParser<'a> {
             self.expect_whitespace()?;
             if !self.looking_at_identifier() {
                 return Ok(MediaQuery::condition(
-                    vec![format!("(not {})", self.parse_media_in_parens()?)],
+                    vec![MediaCondition::Not(Box::new(self.parse_media_in_parens()?))],
                     true,
                 ));
             }
@@ -106,7 +110,9 @@ impl<'a> MediaQueryParser<'a> {
             return Ok(MediaQuery::media_type(
                 media_type,
                 modifier,
-                Some(vec![format!("(not {})", self.parse_media_in_parens()?)]),
+                Some(vec![MediaCondition::Not(Box::new(
+                    self.parse_media_in_parens()?,
+                ))]),
             ));
         }
 
@@ -117,14 +123,80 @@ impl<'a> MediaQueryParser<'a> {
         ))
     }
 
-    fn parse_media_in_parens(&mut self) -> SassResult<String> {
+    fn parse_media_in_parens(&mut self) -> SassResult<MediaCondition> {
         self.expect_char('(')?;
-        let result = format!("({})", self.declaration_value(false)?);
+
+        let start = self.toks.cursor();
+
+        if let Some(condition) = self.try_parse_nested_condition() {
+            return Ok(condition);
+        }
+
+        // Anything we don't understand is passed through as-is
+        self.toks.set_cursor(start);
+        let contents = self.declaration_value(false)?;
         self.expect_char(')')?;
-        Ok(result)
+
+        Ok(MediaCondition::from_feature(&contents))
+    }
+
+    /// Attempts to parse a condition nested within parentheses, such as the
+    /// contents of `((color) or (hover))` or `(not (color))`, along with the
+    /// closing parenthesis
+    fn try_parse_nested_condition(&mut self) -> Option<MediaCondition> {
+        self.whitespace().ok()?;
+
+        let condition = if self.toks.next_char_is('(') {
+            let first = self.parse_media_in_parens().ok()?;
+            self.whitespace().ok()?;
+
+            if self.scan_identifier("and", false).ok()? {
+                self.expect_whitespace().ok()?;
+                let mut conditions = vec![first];
+                conditions.append(&mut self.parse_media_logic_sequence("and").ok()?);
+                MediaCondition::And(conditions)
+            } else if self.scan_identifier("or", false).ok()? {
+                self.expect_whitespace().ok()?;
+                let mut conditions = vec![first];
+                conditions.append(&mut self.parse_media_logic_sequence("or").ok()?);
+                MediaCondition::Or(conditions)
+            } else {
+                // Redundant parentheses are kept as written, and what they
+                // contain is no longer merged with other conditions
+                match first {
+                    MediaCondition::Feature(..) | MediaCondition::Range(..) => {
+                        MediaCondition::Feature(format!("({})", first))
+                    }
+                    MediaCondition::Not(..) | MediaCondition::And(..) | MediaCondition::Or(..) => {
+                        MediaCondition::Feature(format!("(({}))", first))
+                    }
+                }
+            }
+        } else if self.scan_identifier("not", false).ok()? {
+            self.expect_whitespace().ok()?;
+
+            if !self.toks.next_char_is('(') {
+                return None;
+            }
+
+            MediaCondition::Not(Box::new(self.parse_media_in_parens().ok()?))
+        } else {
+            return None;
+        };
+
+        self.whitespace().ok()?;
+
+        if !self.scan_char(')') {
+            return None;
+        }
+
+        Some(condition)
     }
 
-    fn parse_media_logic_sequence(&mut self, operator: &'static str) -> SassResult<Vec<String>> {
+    fn parse_media_logic_sequence(
+        &mut self,
+        operator: &'static str,
+    ) -> SassResult<Vec<MediaCondition>> {
         let mut result = Vec::new();
         loop {
             result.push(self.parse_media_in_parens()?);
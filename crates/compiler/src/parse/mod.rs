@@ -1,6 +1,7 @@
 use crate::ast::*;
 
 pub(crate) use at_root_query::AtRootQueryParser;
+pub(crate) use at_rule_prelude::AtRulePreludeParser;
 pub(crate) use base::BaseParser;
 pub(crate) use css::CssParser;
 pub(crate) use keyframes::KeyframesSelectorParser;
@@ -10,6 +11,7 @@ pub(crate) use scss::ScssParser;
 pub(crate) use stylesheet::StylesheetParser;
 
 mod at_root_query;
+mod at_rule_prelude;
 mod base;
 mod css;
 mod keyframes;
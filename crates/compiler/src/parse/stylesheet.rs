@@ -42,6 +42,12 @@ pub(crate) trait StylesheetParser<'a>: BaseParser<'a> + Sized {
     #[allow(clippy::type_complexity)]
     const IDENTIFIER_LIKE: Option<fn(&mut Self) -> SassResult<Spanned<AstExpr>>> = None;
 
+    /// Whether the next statement in a plain CSS style rule is a nested style
+    /// rule rather than a declaration
+    fn looking_at_nested_style_rule(&mut self) -> bool {
+        false
+    }
+
     fn parse_style_rule_selector(&mut self) -> SassResult<Interpolation> {
         self.almost_any_value(false)
     }
@@ -1003,6 +1009,86 @@ pub(crate) trait StylesheetParser<'a>: BaseParser<'a> + Sized {
         }))
     }
 
+    fn parse_layer_rule(&mut self, start: usize) -> SassResult<AstStmt> {
+        let was_in_unknown_at_rule = self.flags().in_unknown_at_rule();
+        self.flags_mut().set(ContextFlags::IN_UNKNOWN_AT_RULE, true);
+
+        let names_start = self.toks().cursor();
+        let names = if self.at_end_of_statement() {
+            None
+        } else {
+            Some(self.almost_any_value(false)?)
+        };
+        let names_span = self.toks_mut().span_from(names_start);
+
+        let children = if self.looking_at_children()? {
+            Some(self.with_children(Self::parse_statement)?.node)
+        } else {
+            if names.is_none() {
+                return Err(("Expected identifier.", self.toks().current_span()).into());
+            }
+
+            self.expect_statement_separator(Some("@layer rule"))?;
+            None
+        };
+
+        self.flags_mut()
+            .set(ContextFlags::IN_UNKNOWN_AT_RULE, was_in_unknown_at_rule);
+
+        Ok(AstStmt::Layer(AstLayerRule {
+            names,
+            names_span,
+            children,
+            span: self.toks_mut().span_from(start),
+        }))
+    }
+
+    fn parse_container_rule(&mut self, start: usize) -> SassResult<AstStmt> {
+        let was_in_unknown_at_rule = self.flags().in_unknown_at_rule();
+        self.flags_mut().set(ContextFlags::IN_UNKNOWN_AT_RULE, true);
+
+        let query_start = self.toks().cursor();
+        let query = self.almost_any_value(false)?;
+        let query_span = self.toks_mut().span_from(query_start);
+
+        let children = self.with_children(Self::parse_statement)?.node;
+
+        self.flags_mut()
+            .set(ContextFlags::IN_UNKNOWN_AT_RULE, was_in_unknown_at_rule);
+
+        Ok(AstStmt::Container(AstContainerRule {
+            query,
+            query_span,
+            children,
+            span: self.toks_mut().span_from(start),
+        }))
+    }
+
+    fn parse_scope_rule(&mut self, start: usize) -> SassResult<AstStmt> {
+        let was_in_unknown_at_rule = self.flags().in_unknown_at_rule();
+        self.flags_mut().set(ContextFlags::IN_UNKNOWN_AT_RULE, true);
+
+        let prelude_start = self.toks().cursor();
+        let prelude = if self.at_end_of_statement() {
+            Interpolation::new()
+        } else {
+            self.almost_any_value(false)?
+        };
+        let prelude_span = self.toks_mut().span_from(prelude_start);
+
+        let children = self.with_children(Self::parse_statement)?.node;
+
+        self.flags_mut()
+            .set(ContextFlags::IN_UNKNOWN_AT_RULE, was_in_unknown_at_rule);
+
+        Ok(AstStmt::Scope(AstScopeRule {
+            prelude,
+            prelude_span,
+            children,
+            span: self.toks_mut().span_from(start),
+        }))
+    }
+
     fn parse_interpolated_string(&mut self) -> SassResult<Spanned<StringExpr>> {
         let start = self.toks().cursor();
         let quote = match self.toks_mut().next() {
@@ -1117,6 +1203,7 @@ pub(crate) trait StylesheetParser<'a>: BaseParser<'a> + Sized {
             args,
             body,
             has_content,
+            span: self.toks_mut().span_from(start),
         }))
     }
 
@@ -1527,7 +1614,16 @@ pub(crate) trait StylesheetParser<'a>: BaseParser<'a> + Sized {
             });
         }
 
-        let base_name = url
+        // the default namespace of a package URL is derived from the package
+        // path, e.g. `pkg:bootstrap` has the namespace `bootstrap`
+        let url_str = url.to_string_lossy();
+        let package_path = url_str
+            .strip_prefix("pkg:")
+            .or_else(|| url_str.strip_prefix('~'))
+            .map(Path::new);
+
+        let base_name = package_path
+            .unwrap_or(url)
             .file_name()
             .map_or_else(OsString::new, ToOwned::to_owned);
         let base_name = base_name.to_string_lossy();
@@ -1688,6 +1784,7 @@ pub(crate) trait StylesheetParser<'a>: BaseParser<'a> + Sized {
 
         match name.as_plain() {
             Some("at-root") => self.parse_at_root_rule(start),
+            Some("container") => self.parse_container_rule(start),
             Some("content") => self.parse_content_rule(start),
             Some("debug") => self.parse_debug_rule(),
             Some("each") => self.parse_each_rule(child),
@@ -1707,10 +1804,12 @@ pub(crate) trait StylesheetParser<'a>: BaseParser<'a> + Sized {
             Some("if") => self.parse_if_rule(child),
             Some("import") => self.parse_import_rule(start),
             Some("include") => self.parse_include_rule(),
+            Some("layer") => self.parse_layer_rule(start),
             Some("media") => self.parse_media_rule(start),
             Some("mixin") => self.parse_mixin_rule(start),
             // todo: support -moz-document
             // Some("-moz-document") => self.parse_moz_document_rule(name),
+            Some("scope") => self.parse_scope_rule(start),
             Some("supports") => self.parse_supports_rule(),
             Some("use") => {
                 self.flags_mut()
@@ -1779,6 +1878,10 @@ pub(crate) trait StylesheetParser<'a>: BaseParser<'a> + Sized {
 
         if self.is_plain_css() && self.flags().in_style_rule() && !self.flags().in_unknown_at_rule()
         {
+            if self.looking_at_nested_style_rule() {
+                return self.parse_style_rule(None, None);
+            }
+
             return self.parse_property_or_variable_declaration(true);
         }
 
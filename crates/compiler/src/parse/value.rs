@@ -1707,6 +1707,54 @@ impl<'a, 'c, P: StylesheetParser<'a>> ValueParser<'a, 'c, P> {
         Ok(arguments)
     }
 
+    /// Like [`ValueParser::parse_calculation_arguments`], but allows the first
+    /// argument to be an unquoted rounding strategy such as `up`
+    fn parse_round_arguments(parser: &mut P, start: usize) -> SassResult<Vec<AstExpr>> {
+        let before_args = parser.toks().cursor();
+
+        parser.expect_char('(')?;
+        parser.whitespace()?;
+
+        if parser.looking_at_identifier() {
+            let strategy_start = parser.toks().cursor();
+            let strategy = parser.parse_identifier(false, false)?;
+            let strategy_span = parser.toks_mut().span_from(strategy_start);
+            parser.whitespace()?;
+
+            if parser.scan_char(',') {
+                parser.whitespace()?;
+
+                let mut arguments = vec![
+                    AstExpr::String(
+                        StringExpr(Interpolation::new_plain(strategy), QuoteKind::None),
+                        strategy_span,
+                    ),
+                    ValueParser::parse_calculation_sum(parser)?.node,
+                ];
+
+                if parser.scan_char(',') {
+                    parser.whitespace()?;
+                    arguments.push(ValueParser::parse_calculation_sum(parser)?.node);
+                }
+
+                parser.expect_char_with_message(
+                    ')',
+                    if arguments.len() == 3 {
+                        r#""+", "-", "*", "/", or ")""#
+                    } else {
+                        r#""+", "-", "*", "/", ",", or ")""#
+                    },
+                )?;
+
+                return Ok(arguments);
+            }
+        }
+
+        parser.toks_mut().set_cursor(before_args);
+
+        ValueParser::parse_calculation_arguments(parser, Some(3), start)
+    }
+
     fn try_parse_calculation(
         parser: &mut P,
         name: &str,
@@ -1755,7 +1803,32 @@ impl<'a, 'c, P: StylesheetParser<'a>> ValueParser<'a, 'c, P> {
                 }
                 .span(parser.toks_mut().span_from(start))
             }
-            _ => return Ok(None),
+            _ => {
+                let name = match CalculationName::from_name(name) {
+                    Some(name) => name,
+                    None => return Ok(None),
+                };
+
+                // The remaining math functions share their names with Sass
+                // functions (either global or user-defined), so like min() and
+                // max() they fall back to normal function calls if their
+                // arguments can't be parsed as a calculation.
+                let before_args = parser.toks().cursor();
+
+                let args = match if name == CalculationName::Round {
+                    ValueParser::parse_round_arguments(parser, start)
+                } else {
+                    ValueParser::parse_calculation_arguments(parser, name.max_args(), start)
+                } {
+                    Ok(args) => args,
+                    Err(..) => {
+                        parser.toks_mut().set_cursor(before_args);
+                        return Ok(None);
+                    }
+                };
+
+                AstExpr::Calculation { name, args }.span(parser.toks_mut().span_from(start))
+            }
         }))
     }
 
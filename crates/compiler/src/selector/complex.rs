@@ -247,6 +247,35 @@ impl ComplexSelector {
         }
     }
 
+    pub fn is_css_nestable(&self) -> bool {
+        if !matches!(
+            self.components.last(),
+            Some(ComplexSelectorComponent::Compound(..))
+        ) {
+            return false;
+        }
+
+        let has_repeated_combinator = self
+            .components
+            .windows(2)
+            .any(|pair| pair[0].is_combinator() && pair[1].is_combinator());
+
+        !has_repeated_combinator
+            && self.components.iter().all(|c| match c {
+                ComplexSelectorComponent::Combinator(..) => true,
+                ComplexSelectorComponent::Compound(compound) => {
+                    compound.components.iter().all(|simple| match simple {
+                        SimpleSelector::Parent(suffix) => suffix.is_none(),
+                        SimpleSelector::Pseudo(Pseudo {
+                            selector: Some(sel),
+                            ..
+                        }) => sel.is_css_nestable(),
+                        _ => true,
+                    })
+                }
+            })
+    }
+
     pub fn contains_parent_selector(&self) -> bool {
         self.components.iter().any(|c| {
             if let ComplexSelectorComponent::Compound(compound) = c {
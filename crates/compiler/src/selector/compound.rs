@@ -18,6 +18,19 @@ pub(crate) struct CompoundSelector {
 
 impl fmt::Display for CompoundSelector {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // A type selector must come first, so `&` written after one, as in
+        // `div&`, is emitted after it
+        if let [parent @ SimpleSelector::Parent(None), first @ (SimpleSelector::Type(..) | SimpleSelector::Universal(..)), rest @ ..] =
+            self.components.as_slice()
+        {
+            write!(f, "{}{}", first, parent)?;
+            for simple in rest {
+                write!(f, "{}", simple)?;
+            }
+
+            return Ok(());
+        }
+
         let mut did_write = false;
         for simple in &self.components {
             if did_write {
@@ -192,9 +205,34 @@ impl CompoundSelector {
                         components.push(end);
                     }
 
-                    components.extend(resolved_members.clone().into_iter().skip(1));
-
-                    let last = CompoundSelector { components };
+                    let rest: Vec<SimpleSelector> =
+                        resolved_members.clone().into_iter().skip(1).collect();
+
+                    // A type selector following `&`, as in the CSS `div&`, must be
+                    // unified with the parent rather than appended to it
+                    let last = if rest
+                        .iter()
+                        .any(|simple| simple.is_type() || simple.is_universal())
+                    {
+                        match (CompoundSelector { components })
+                            .unify(CompoundSelector { components: rest })
+                        {
+                            Some(unified) => unified,
+                            None => {
+                                return Err((
+                                    format!(
+                                        "Parent \"{}\" is incompatible with this selector.",
+                                        complex
+                                    ),
+                                    span,
+                                )
+                                    .into())
+                            }
+                        }
+                    } else {
+                        components.extend(rest);
+                        CompoundSelector { components }
+                    };
 
                     complex.components.pop();
 
@@ -12,9 +12,11 @@ use crate::selector::{Selector, SelectorList};
 #[derive(Debug, Clone)]
 pub(crate) struct ExtendedSelector(Rc<RefCell<SelectorList>>);
 
+// Selectors are compared by identity, consistent with `Hash`, so that
+// distinct style rules with the same selector are tracked separately
 impl PartialEq for ExtendedSelector {
     fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0
+        Rc::ptr_eq(&self.0, &other.0)
     }
 }
 
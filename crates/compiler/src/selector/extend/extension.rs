@@ -1,6 +1,9 @@
 use codemap::Span;
 
-use crate::ast::CssMediaQuery;
+use crate::{
+    ast::CssMediaQuery,
+    error::{SassError, SassResult},
+};
 
 use super::{ComplexSelector, SimpleSelector};
 
@@ -29,13 +32,17 @@ pub(crate) struct Extension {
     /// it can apply within any context.
     pub media_context: Option<Vec<CssMediaQuery>>,
 
+    /// The span of the innermost `@media` rule containing the `@extend`, if
+    /// `media_context` isn't `None`.
+    pub media_span: Option<Span>,
+
     /// The span in which `extender` was defined.
     pub span: Span,
 
-    #[allow(dead_code)]
+    /// If this extension was created by merging two extensions, the extensions
+    /// it was created from.
     pub left: Option<Box<Extension>>,
 
-    #[allow(dead_code)]
     pub right: Option<Box<Extension>>,
 }
 
@@ -54,6 +61,7 @@ impl Extension {
             is_optional: true,
             is_original,
             media_context: None,
+            media_span: None,
             left: None,
             right: None,
         }
@@ -61,16 +69,52 @@ impl Extension {
 
     /// Asserts that the `media_context` for a selector is compatible with the
     /// query context for this extender.
-    // todo: this should return a `Result`. it currently does not because the cascade effect
-    // from this returning a `Result` will make some code returning `Option`s much uglier (we can't
-    // use `?` to return both `Option` and `Result` from the same function)
-    #[allow(clippy::needless_return)]
-    pub fn assert_compatible_media_context(&self, media_context: &Option<Vec<CssMediaQuery>>) {
-        if &self.media_context == media_context {
-            return;
+    pub fn assert_compatible_media_context(
+        &self,
+        media_context: &Option<Vec<CssMediaQuery>>,
+    ) -> SassResult<()> {
+        let expected_media_context = match &self.media_context {
+            Some(expected_media_context) => expected_media_context,
+            None => return Ok(()),
+        };
+
+        if media_context.as_ref() == Some(expected_media_context) {
+            return Ok(());
+        }
+
+        Err(self.with_media_span(
+            (
+                "You may not @extend selectors across media queries.",
+                self.span,
+            )
+                .into(),
+        ))
+    }
+
+    /// Adds the span of the `@media` rule containing this extension to `err`, if
+    /// there is one.
+    pub fn with_media_span(&self, err: Box<SassError>) -> Box<SassError> {
+        match self.media_span {
+            Some(media_span) => err.with_related(media_span, "media"),
+            None => err,
         }
+    }
 
-        // Err(("You may not @extend selectors across media queries.", self.span).into())
+    /// Returns the extensions this extension was merged from, recursively, or
+    /// this extension if it wasn't created by merging.
+    pub fn unmerge(self) -> Vec<Extension> {
+        match (self.left, self.right) {
+            (Some(left), Some(right)) => {
+                let mut extensions = left.unmerge();
+                extensions.append(&mut right.unmerge());
+                extensions
+            }
+            (left, right) => vec![Extension {
+                left,
+                right,
+                ..self
+            }],
+        }
     }
 
     #[allow(clippy::missing_const_for_fn)]
@@ -52,19 +52,22 @@ impl MergedExtension {
     }
 
     fn into_extension(left: Extension, right: Extension) -> Extension {
+        let (media_context, media_span) = match &left.media_context {
+            Some(..) => (left.media_context.clone(), left.media_span),
+            None => (right.media_context.clone(), right.media_span),
+        };
+
         Extension {
-            extender: left.extender,
-            target: left.target,
+            extender: left.extender.clone(),
+            target: left.target.clone(),
             span: left.span,
-            media_context: match left.media_context {
-                Some(v) => Some(v),
-                None => right.media_context,
-            },
+            media_context,
+            media_span,
             specificity: left.specificity,
             is_optional: true,
             is_original: false,
-            left: None,
-            right: None,
+            left: Some(Box::new(left)),
+            right: Some(Box::new(right)),
         }
     }
 }
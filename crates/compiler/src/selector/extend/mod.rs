@@ -16,7 +16,7 @@ use super::{
 
 pub(crate) use extended_selector::ExtendedSelector;
 use extended_selector::SelectorHashSet;
-use extension::Extension;
+pub(crate) use extension::Extension;
 pub(crate) use functions::unify_complex;
 use functions::{paths, weave};
 use merged::MergedExtension;
@@ -76,7 +76,7 @@ pub(crate) struct ExtensionStore {
     ///
     /// This tracks the contexts in which each selector's style rule is defined.
     /// If a rule is defined at the top level, it doesn't have an entry.
-    media_contexts: HashMap<SelectorList, Vec<CssMediaQuery>>,
+    media_contexts: HashMap<ExtendedSelector, Vec<CssMediaQuery>>,
 
     /// A map from `SimpleSelector`s to the specificity of their source
     /// selectors.
@@ -188,7 +188,7 @@ impl ExtensionStore {
             extender.originals.extend(selector.components.iter());
         }
 
-        Ok(extender.extend_list(selector, Some(&extensions), &None))
+        extender.extend_list(selector, Some(&extensions), &None)
     }
 
     fn with_mode(mode: ExtendMode, span: Span) -> Self {
@@ -204,13 +204,13 @@ impl ExtensionStore {
         list: SelectorList,
         extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
         media_query_context: &Option<Vec<CssMediaQuery>>,
-    ) -> SelectorList {
+    ) -> SassResult<SelectorList> {
         // This could be written more simply using Vec<Vec<T>>, but we want to avoid
         // any allocations in the common case where no extends apply.
         let mut extended: Option<Vec<ComplexSelector>> = None;
         for (i, complex) in list.components.iter().enumerate() {
             if let Some(result) =
-                self.extend_complex(complex.clone(), extensions, media_query_context)
+                self.extend_complex(complex.clone(), extensions, media_query_context)?
             {
                 if extended.is_none() {
                     extended = Some(if i == 0 {
@@ -230,13 +230,13 @@ impl ExtensionStore {
 
         let extended = match extended {
             Some(v) => v,
-            None => return list,
+            None => return Ok(list),
         };
 
-        SelectorList {
+        Ok(SelectorList {
             components: self.trim(extended, &|complex| self.originals.contains(complex)),
-            span: self.span,
-        }
+            span: list.span,
+        })
     }
 
     /// Extends `complex` using `extensions`, and returns the contents of a
@@ -246,7 +246,7 @@ impl ExtensionStore {
         complex: ComplexSelector,
         extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
         media_query_context: &Option<Vec<CssMediaQuery>>,
-    ) -> Option<Vec<ComplexSelector>> {
+    ) -> SassResult<Option<Vec<ComplexSelector>>> {
         // The complex selectors that each compound selector in `complex.components`
         // can expand to.
         //
@@ -273,7 +273,7 @@ impl ExtensionStore {
         for (i, component) in complex.components.iter().enumerate() {
             if let ComplexSelectorComponent::Compound(component) = component {
                 if let Some(extended) =
-                    self.extend_compound(component, extensions, media_query_context, is_original)
+                    self.extend_compound(component, extensions, media_query_context, is_original)?
                 {
                     if extended_not_expanded.is_none() {
                         extended_not_expanded = Some(
@@ -309,11 +309,14 @@ impl ExtensionStore {
             }
         }
 
-        let extended_not_expanded = extended_not_expanded?;
+        let extended_not_expanded = match extended_not_expanded {
+            Some(extended_not_expanded) => extended_not_expanded,
+            None => return Ok(None),
+        };
 
         let mut first = true;
 
-        Some(
+        Ok(Some(
             paths(extended_not_expanded)
                 .into_iter()
                 .flat_map(move |path| {
@@ -344,7 +347,7 @@ impl ExtensionStore {
                     .collect::<Vec<ComplexSelector>>()
                 })
                 .collect(),
-        )
+        ))
     }
 
     /// Extends `compound` using `extensions`, and returns the contents of a
@@ -358,7 +361,7 @@ impl ExtensionStore {
         extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
         media_query_context: &Option<Vec<CssMediaQuery>>,
         in_original: bool,
-    ) -> Option<Vec<ComplexSelector>> {
+    ) -> SassResult<Option<Vec<ComplexSelector>>> {
         // If there's more than one target and they all need to match, we track
         // which targets are actually extended.
         let mut targets_used: HashSet<SimpleSelector> = HashSet::new();
@@ -373,7 +376,7 @@ impl ExtensionStore {
                 extensions,
                 media_query_context,
                 &mut targets_used,
-            ) {
+            )? {
                 Some(extended) => {
                     if options.is_none() {
                         let mut new_options = Vec::new();
@@ -397,7 +400,10 @@ impl ExtensionStore {
             }
         }
 
-        let options = options?;
+        let options = match options {
+            Some(options) => options,
+            None => return Ok(None),
+        };
 
         // If `self.mode` isn't `ExtendMode::Normal` and we didn't use all the targets in
         // `extensions`, extension fails for `compound`.
@@ -406,23 +412,20 @@ impl ExtensionStore {
             && targets_used.len() != extensions.map_or(self.extensions.len(), HashMap::len)
             && self.mode != ExtendMode::Normal
         {
-            return None;
+            return Ok(None);
         }
 
         // Optimize for the simple case of a single simple selector that doesn't
         // need any unification.
         if options.len() == 1 {
-            return Some(
-                options
-                    .first()?
-                    .clone()
-                    .into_iter()
-                    .map(|state| {
-                        state.assert_compatible_media_context(media_query_context);
-                        state.extender
-                    })
-                    .collect(),
-            );
+            return options[0]
+                .iter()
+                .map(|state| {
+                    state.assert_compatible_media_context(media_query_context)?;
+                    Ok(state.extender.clone())
+                })
+                .collect::<SassResult<Vec<ComplexSelector>>>()
+                .map(Some);
         }
 
         // Find all paths through `options`. In this case, each path represents a
@@ -493,32 +496,40 @@ impl ExtensionStore {
                     )]);
                 }
 
-                unify_complex(Vec::from(to_unify))?
+                match unify_complex(Vec::from(to_unify)) {
+                    Some(unified) => unified,
+                    None => return Ok(None),
+                }
             };
 
             let mut line_break = false;
 
             for state in path {
-                state.assert_compatible_media_context(media_query_context);
+                state.assert_compatible_media_context(media_query_context)?;
                 line_break = line_break || state.extender.line_break;
             }
 
-            Some(
+            Ok(Some(
                 complexes
                     .into_iter()
                     .map(|components| ComplexSelector::new(components, line_break))
                     .collect::<Vec<ComplexSelector>>(),
-            )
+            ))
         });
 
-        let unified_paths: Vec<ComplexSelector> = unified_paths.flatten().flatten().collect();
+        let unified_paths: Vec<ComplexSelector> = unified_paths
+            .collect::<SassResult<Vec<Option<Vec<ComplexSelector>>>>>()?
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect();
 
-        Some(if in_original && self.mode != ExtendMode::Replace {
+        Ok(Some(if in_original && self.mode != ExtendMode::Replace {
             let original = unified_paths.first().cloned();
             self.trim(unified_paths, &|complex| Some(complex) == original.as_ref())
         } else {
             self.trim(unified_paths, &|_| false)
-        })
+        }))
     }
 
     fn extend_simple(
@@ -527,7 +538,7 @@ impl ExtensionStore {
         extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
         media_query_context: &Option<Vec<CssMediaQuery>>,
         targets_used: &mut HashSet<SimpleSelector>,
-    ) -> Option<Vec<Vec<Extension>>> {
+    ) -> SassResult<Option<Vec<Vec<Extension>>>> {
         if let SimpleSelector::Pseudo(Pseudo {
             selector: Some(..), ..
         }) = &simple
@@ -537,8 +548,8 @@ impl ExtensionStore {
             } else {
                 unreachable!()
             };
-            if let Some(extended) = self.extend_pseudo(simple, extensions, media_query_context) {
-                return Some(
+            if let Some(extended) = self.extend_pseudo(simple, extensions, media_query_context)? {
+                return Ok(Some(
                     extended
                         .into_iter()
                         .map(move |pseudo| {
@@ -553,12 +564,13 @@ impl ExtensionStore {
                             })
                         })
                         .collect(),
-                );
+                ));
             }
         }
 
-        self.without_pseudo(simple, extensions, targets_used, self.mode)
-            .map(|v| vec![v])
+        Ok(self
+            .without_pseudo(simple, extensions, targets_used, self.mode)
+            .map(|v| vec![v]))
     }
 
     /// Extends `pseudo` using `extensions`, and returns a list of resulting
@@ -568,7 +580,7 @@ impl ExtensionStore {
         pseudo: Pseudo,
         extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
         media_query_context: &Option<Vec<CssMediaQuery>>,
-    ) -> Option<Vec<Pseudo>> {
+    ) -> SassResult<Option<Vec<Pseudo>>> {
         let extended = self.extend_list(
             pseudo
                 .selector
@@ -577,10 +589,10 @@ impl ExtensionStore {
                 .unwrap_or_else(|| SelectorList::new(self.span)),
             extensions,
             media_query_context,
-        );
+        )?;
         /*todo: identical(extended, pseudo.selector)*/
         if Some(&extended) == pseudo.selector.as_deref() {
-            return None;
+            return Ok(None);
         }
 
         // For `:not()`, we usually want to get rid of any complex selectors because
@@ -689,15 +701,17 @@ impl ExtensionStore {
                 })
                 .collect::<Vec<Pseudo>>();
             if result.is_empty() {
-                None
+                Ok(None)
             } else {
-                Some(result)
+                Ok(Some(result))
             }
         } else {
-            Some(vec![pseudo.with_selector(Some(Box::new(SelectorList {
-                components: complexes,
-                span: self.span,
-            })))])
+            Ok(Some(vec![pseudo.with_selector(Some(Box::new(
+                SelectorList {
+                    components: complexes,
+                    span: self.span,
+                },
+            )))]))
         }
     }
 
@@ -865,7 +879,7 @@ impl ExtensionStore {
         mut selector: SelectorList,
         // span: Span,
         media_query_context: &Option<Vec<CssMediaQuery>>,
-    ) -> ExtendedSelector {
+    ) -> SassResult<ExtendedSelector> {
         if !selector.is_invisible() {
             for complex in selector.components.clone() {
                 self.originals.insert(&complex);
@@ -873,25 +887,15 @@ impl ExtensionStore {
         }
 
         if !self.extensions.is_empty() {
-            selector = self.extend_list(selector, None, media_query_context);
-            /*
-              todo: when we have error handling
-                  } on SassException catch (error) {
-              throw SassException(
-                  "From ${error.span.message('')}\n"
-                  "${error.message}",
-                  span);
-            }
-              */
+            selector = self.extend_list(selector, None, media_query_context)?;
         }
-        if let Some(mut media_query_context) = media_query_context.clone() {
+        let extended_selector = ExtendedSelector::new(selector.clone());
+        if let Some(media_query_context) = media_query_context.clone() {
             self.media_contexts
-                .get_mut(&selector)
-                .replace(&mut media_query_context);
+                .insert(extended_selector.clone(), media_query_context);
         }
-        let extended_selector = ExtendedSelector::new(selector.clone());
         self.register_selector(selector, &extended_selector);
-        extended_selector
+        Ok(extended_selector)
     }
 
     /// Registers the `SimpleSelector`s in `list` to point to `selector` in
@@ -942,7 +946,7 @@ impl ExtensionStore {
         extend: &ExtendRule,
         media_context: &Option<Vec<CssMediaQuery>>,
         span: Span,
-    ) {
+    ) -> SassResult<()> {
         let selectors = self.selectors.get(target).cloned();
         let existing_extensions = self.extensions_by_extender.get(target).cloned();
 
@@ -955,6 +959,7 @@ impl ExtensionStore {
                 target: Some(target.clone()),
                 span,
                 media_context: media_context.clone(),
+                media_span: extend.media_span,
                 is_optional: extend.is_optional,
                 is_original: false,
                 left: None,
@@ -970,8 +975,8 @@ impl ExtensionStore {
                 // If there's already an extend from `extender` to `target`, we don't need
                 // to re-run the extension. We may need to mark the extension as
                 // mandatory, though.
-                let mut new_val = MergedExtension::merge(existing_state.clone(), state).unwrap();
-                sources.get_mut(&complex).replace(&mut new_val);
+                let merged = MergedExtension::merge(existing_state.clone(), state)?;
+                sources.insert(complex, merged);
                 continue;
             }
 
@@ -1003,7 +1008,7 @@ impl ExtensionStore {
         let new_extensions = if let Some(new) = new_extensions {
             new
         } else {
-            return;
+            return Ok(());
         };
 
         let mut new_extensions_by_target = HashMap::new();
@@ -1011,15 +1016,150 @@ impl ExtensionStore {
 
         if let Some(existing_extensions) = existing_extensions {
             let additional_extensions =
-                self.extend_existing_extensions(existing_extensions, &new_extensions_by_target);
+                self.extend_existing_extensions(existing_extensions, &new_extensions_by_target)?;
             if let Some(additional_extensions) = additional_extensions {
                 map_add_all_2(&mut new_extensions_by_target, additional_extensions);
             }
         }
 
         if let Some(selectors) = selectors {
-            self.extend_existing_selectors(selectors, &new_extensions_by_target);
+            self.extend_existing_selectors(selectors, &new_extensions_by_target)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether this extender has no extensions.
+    pub fn is_empty(&self) -> bool {
+        self.extensions.is_empty()
+    }
+
+    /// Returns whether any selector in this extender contains `simple`.
+    pub fn contains_simple_selector(&self, simple: &SimpleSelector) -> bool {
+        self.selectors.contains_key(simple)
+    }
+
+    /// Returns all mandatory extensions in this extender whose targets match
+    /// `callback`.
+    ///
+    /// Extensions that were merged are returned separately, so that a mandatory
+    /// extension isn't hidden by an optional one with the same extender and target.
+    pub fn extensions_where_target(
+        &self,
+        callback: impl Fn(&SimpleSelector) -> bool,
+    ) -> Vec<Extension> {
+        let mut extensions = Vec::new();
+
+        for (target, sources) in &self.extensions {
+            if !callback(target) {
+                continue;
+            }
+
+            for extension in sources.values() {
+                extensions.extend(
+                    extension
+                        .clone()
+                        .unmerge()
+                        .into_iter()
+                        .filter(|extension| !extension.is_optional),
+                );
+            }
         }
+
+        extensions
+    }
+
+    /// Extends `self` with all the extensions in `extension_stores`.
+    ///
+    /// This is used to apply the extensions defined in downstream modules to the
+    /// selectors of the modules they `@use`. Extensions of private placeholder
+    /// selectors aren't applied across module boundaries.
+    pub fn add_extensions(&mut self, extension_stores: &[&ExtensionStore]) -> SassResult<()> {
+        // Extensions already in this store whose extenders are extended by
+        // `extension_stores`.
+        let mut extensions_to_extend: Option<Vec<Extension>> = None;
+
+        // Selectors that contain simple selectors that are extended by
+        // `extension_stores`.
+        let mut selectors_to_extend: Option<SelectorHashSet> = None;
+
+        // An extension map with the same structure as `self.extensions` that only
+        // includes extensions from `extension_stores`.
+        let mut new_extensions: Option<
+            HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>,
+        > = None;
+
+        for extension_store in extension_stores {
+            if extension_store.is_empty() {
+                continue;
+            }
+
+            for (simple, specificity) in &extension_store.source_specificity {
+                self.source_specificity.insert(simple.clone(), *specificity);
+            }
+
+            for (target, new_sources) in &extension_store.extensions {
+                if matches!(target, SimpleSelector::Placeholder(name) if name.starts_with('-') || name.starts_with('_'))
+                {
+                    continue;
+                }
+
+                let extensions_for_target = self.extensions_by_extender.get(target);
+                if let Some(extensions_for_target) = extensions_for_target {
+                    extensions_to_extend
+                        .get_or_insert_with(Vec::new)
+                        .extend(extensions_for_target.iter().cloned());
+                }
+
+                let selectors_for_target = self.selectors.get(target);
+                if let Some(selectors_for_target) = selectors_for_target {
+                    let selectors = selectors_to_extend.get_or_insert_with(SelectorHashSet::new);
+                    for selector in selectors_for_target.clone() {
+                        selectors.insert(selector);
+                    }
+                }
+
+                let is_relevant = extensions_for_target.is_some() || selectors_for_target.is_some();
+
+                let existing_sources = self.extensions.entry(target.clone()).or_default();
+
+                for (extender, extension) in new_sources {
+                    let extension = match existing_sources.get(extender) {
+                        Some(existing) => {
+                            MergedExtension::merge(existing.clone(), extension.clone())?
+                        }
+                        None => extension.clone(),
+                    };
+
+                    existing_sources.insert(extender.clone(), extension.clone());
+
+                    if is_relevant {
+                        new_extensions
+                            .get_or_insert_with(HashMap::new)
+                            .entry(target.clone())
+                            .or_default()
+                            .insert(extender.clone(), extension);
+                    }
+                }
+            }
+        }
+
+        let new_extensions = match new_extensions {
+            Some(new_extensions) => new_extensions,
+            None => return Ok(()),
+        };
+
+        if let Some(extensions_to_extend) = extensions_to_extend {
+            // We can ignore the return value here because it's only useful for extend
+            // loops, which can't exist across module boundaries.
+            self.extend_existing_extensions(extensions_to_extend, &new_extensions)?;
+        }
+
+        if let Some(selectors_to_extend) = selectors_to_extend {
+            self.extend_existing_selectors(selectors_to_extend, &new_extensions)?;
+        }
+
+        Ok(())
     }
 
     /// Extend `extensions` using `new_extensions`.
@@ -1041,7 +1181,7 @@ impl ExtensionStore {
         &mut self,
         extensions: Vec<Extension>,
         new_extensions: &HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>,
-    ) -> Option<HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>> {
+    ) -> SassResult<Option<HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>> {
         let mut additional_extensions: Option<
             HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>,
         > = None;
@@ -1052,25 +1192,14 @@ impl ExtensionStore {
                 .unwrap()
                 .clone();
 
-            // `extend_existing_selectors` would have thrown already.
-            let selectors: Vec<ComplexSelector> = if let Some(v) = self.extend_complex(
+            let selectors: Vec<ComplexSelector> = match self.extend_complex(
                 extension.extender.clone(),
                 Some(new_extensions),
                 &extension.media_context,
-            ) {
-                v
-            } else {
-                continue;
+            )? {
+                Some(v) => v,
+                None => continue,
             };
-            // todo: when we add error handling, this error is special
-            /*
-            } on SassException catch (error) {
-                throw SassException(
-                    "From ${extension.extenderSpan.message('')}\n"
-                    "${error.message}",
-                    error.span);
-            }
-            */
 
             let contains_extension = selectors.first() == Some(&extension.extender);
 
@@ -1086,10 +1215,12 @@ impl ExtensionStore {
                 let with_extender = extension.clone().with_extender(complex.clone());
                 let existing_extension = sources.get(&complex);
                 if let Some(existing_extension) = existing_extension.cloned() {
-                    sources.get_mut(&complex).replace(
-                        &mut MergedExtension::merge(existing_extension.clone(), with_extender)
-                            .unwrap(),
-                    );
+                    sources
+                        .get_mut(&complex)
+                        .replace(&mut MergedExtension::merge(
+                            existing_extension.clone(),
+                            with_extender,
+                        )?);
                 } else {
                     sources
                         .get_mut(&complex)
@@ -1122,7 +1253,7 @@ impl ExtensionStore {
                 sources.remove(&extension.extender);
             }
         }
-        additional_extensions
+        Ok(additional_extensions)
     }
 
     /// Extend `extensions` using `new_extensions`.
@@ -1130,24 +1261,14 @@ impl ExtensionStore {
         &mut self,
         selectors: SelectorHashSet,
         new_extensions: &HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>,
-    ) {
+    ) -> SassResult<()> {
         for mut selector in selectors {
             let old_value = selector.clone().into_selector().0;
             selector.set_inner(self.extend_list(
                 old_value.clone(),
                 Some(new_extensions),
-                &self.media_contexts.get(&old_value).cloned(),
-            ));
-            /*
-            todo: error handling
-            } on SassException catch (error) {
-            throw SassException(
-                "From ${selector.span.message('')}\n"
-                "${error.message}",
-                error.span);
-            }
-
-            */
+                &self.media_contexts.get(&selector).cloned(),
+            )?);
 
             // If no extends actually happened (for example becaues unification
             // failed), we don't need to re-register the selector.
@@ -1157,6 +1278,8 @@ impl ExtensionStore {
             }
             self.register_selector(selector_as_selector, &selector);
         }
+
+        Ok(())
     }
 }
 
@@ -1,4 +1,8 @@
+use codemap::Span;
+
 #[derive(Clone, Debug)]
 pub(crate) struct ExtendRule {
     pub is_optional: bool,
+    /// The span of the innermost `@media` rule containing the `@extend`, if any
+    pub media_span: Option<Span>,
 }
@@ -77,6 +77,28 @@ impl SelectorList {
             .any(ComplexSelector::contains_parent_selector)
     }
 
+    /// Whether this selector can be written unchanged within a CSS nesting
+    /// context, which rules out Sass-only features such as parent selector
+    /// suffixes and trailing or repeated combinators
+    pub fn is_css_nestable(&self) -> bool {
+        self.components.iter().all(ComplexSelector::is_css_nestable)
+    }
+
+    /// Whether every complex selector in this list has the same specificity.
+    ///
+    /// CSS nesting matches a parent selector list as though it were wrapped in
+    /// `:is(..)`, which takes the specificity of its most specific selector, so
+    /// rules nested within a list with mixed specificity would match differently
+    /// than their flattened equivalents
+    pub fn has_uniform_specificity(&self) -> bool {
+        let mut specificities = self.components.iter().map(ComplexSelector::specificity);
+
+        match specificities.next() {
+            Some(first) => specificities.all(|specificity| specificity == first),
+            None => true,
+        }
+    }
+
     pub const fn new(span: Span) -> Self {
         Self {
             components: Vec::new(),
@@ -40,6 +40,11 @@ pub(crate) struct SelectorParser<'a> {
     /// Whether this parser allows placeholder selectors beginning with `%`.
     allows_placeholder: bool,
 
+    /// Whether this parser follows the CSS Nesting Module rather than Sass,
+    /// allowing `&` anywhere within a compound selector and never treating
+    /// the text following it as a suffix.
+    css_nesting: bool,
+
     pub toks: Lexer<'a>,
 
     span: Span,
@@ -61,10 +66,17 @@ impl<'a> SelectorParser<'a> {
             toks,
             allows_parent,
             allows_placeholder,
+            css_nesting: false,
             span,
         }
     }
 
+    /// Parse `&` according to the CSS Nesting Module, as used by plain CSS
+    pub fn css_nesting(mut self) -> Self {
+        self.css_nesting = true;
+        self
+    }
+
     pub fn parse(mut self) -> SassResult<SelectorList> {
         let tmp = self.parse_selector_list()?;
         if self.toks.peek().is_some() {
@@ -150,7 +162,7 @@ impl<'a> SelectorParser<'a> {
                     components.push(ComplexSelectorComponent::Compound(
                         self.parse_compound_selector()?,
                     ));
-                    if let Some(Token { kind: '&', .. }) = self.toks.peek() {
+                    if !self.css_nesting && matches!(self.toks.peek(), Some(Token { kind: '&', .. })) {
                         return Err(("\"&\" may only used at the beginning of a compound selector.", self.span).into());
                     }
                 }
@@ -161,7 +173,7 @@ impl<'a> SelectorParser<'a> {
                     components.push(ComplexSelectorComponent::Compound(
                         self.parse_compound_selector()?,
                     ));
-                    if let Some(Token { kind: '&', .. }) = self.toks.peek() {
+                    if !self.css_nesting && matches!(self.toks.peek(), Some(Token { kind: '&', .. })) {
                         return Err(("\"&\" may only used at the beginning of a compound selector.", self.span).into());
                     }
                 }
@@ -180,6 +192,15 @@ impl<'a> SelectorParser<'a> {
         let mut components = vec![self.parse_simple_selector(None)?];
 
         while let Some(Token { kind, .. }) = self.toks.peek() {
+            if self.css_nesting
+                && (kind == '&'
+                    || (components.last().is_some_and(SimpleSelector::is_parent)
+                        && self.looking_at_identifier()))
+            {
+                components.push(self.parse_simple_selector(None)?);
+                continue;
+            }
+
             if !is_simple_selector_start(kind) {
                 break;
             }
@@ -187,6 +208,15 @@ impl<'a> SelectorParser<'a> {
             components.push(self.parse_simple_selector(Some(false))?);
         }
 
+        // CSS allows `&` anywhere within a compound selector, as in `div&`, but
+        // parent resolution expects it to come first
+        if self.css_nesting {
+            if let Some(idx) = components.iter().position(SimpleSelector::is_parent) {
+                let parent = components.remove(idx);
+                components.insert(0, parent);
+            }
+        }
+
         Ok(CompoundSelector { components })
     }
 
@@ -318,7 +348,7 @@ impl<'a> SelectorParser<'a> {
 
     fn parse_parent_selector(&mut self) -> SassResult<SimpleSelector> {
         self.toks.next();
-        let suffix = if self.looking_at_identifier_body() {
+        let suffix = if !self.css_nesting && self.looking_at_identifier_body() {
             let mut buffer = String::new();
             self.parse_identifier_body(&mut buffer, false, false)?;
             Some(buffer)
@@ -82,7 +82,8 @@ impl fmt::Display for SimpleSelector {
             Self::Pseudo(pseudo) => write!(f, "{}", pseudo),
             Self::Type(name) => write!(f, "{}", name),
             Self::Attribute(attr) => write!(f, "{}", attr),
-            Self::Parent(..) => unreachable!("It should not be possible to format `&`."),
+            // Only unresolved selectors with preserved CSS nesting contain `&`
+            Self::Parent(suffix) => write!(f, "&{}", suffix.as_deref().unwrap_or("")),
         }
     }
 }
@@ -129,7 +130,7 @@ impl SimpleSelector {
                 name != "not" && selector.as_ref().map_or(false, |sel| sel.is_invisible())
             }
             Self::Placeholder(..) => true,
-            Self::Parent(..) => unreachable!("parent selectors should be resolved at this point"),
+            Self::Parent(..) => false,
         }
     }
 
@@ -1,10 +1,14 @@
-use std::io::Write;
+use std::{
+    io::Write,
+    mem,
+    path::{Component, Path, PathBuf},
+};
 
 use codemap::{CodeMap, Span};
 
 use crate::{
-    ast::{CssStmt, MediaQuery, Style, SupportsRule},
-    color::{Color, ColorFormat, NAMED_COLORS},
+    ast::{ContainerRule, CssStmt, LayerRule, MediaQuery, Mixin, ScopeRule, Style, SupportsRule},
+    color::{ChannelKind, Color, ColorFormat, ColorSpace, NAMED_COLORS},
     common::{BinaryOp, Brackets, ListSeparator, QuoteKind},
     error::SassResult,
     selector::{
@@ -16,7 +20,7 @@ use crate::{
         fuzzy_equals, ArgList, CalculationArg, CalculationName, SassCalculation, SassFunction,
         SassMap, SassNumber, Value,
     },
-    Options,
+    Indent, LineEnding, Options, OutputStyle,
 };
 
 pub(crate) fn serialize_selector_list(
@@ -107,6 +111,19 @@ pub(crate) fn inspect_function_ref(
     Ok(serializer.finish_for_expr())
 }
 
+pub(crate) fn inspect_mixin_ref(
+    mixin: &Mixin,
+    options: &Options,
+    span: Span,
+) -> SassResult<String> {
+    let code_map = CodeMap::new();
+    let mut serializer = Serializer::new(options, &code_map, true, span);
+
+    serializer.visit_mixin_ref(mixin, span)?;
+
+    Ok(serializer.finish_for_expr())
+}
+
 pub(crate) fn inspect_number(
     number: &SassNumber,
     options: &Options,
@@ -120,11 +137,67 @@ pub(crate) fn inspect_number(
     Ok(serializer.finish_for_expr())
 }
 
+/// Replaces each `\n` in `css` that isn't already part of a `\r\n` with `\r\n`
+fn with_crlf_line_endings(css: &str) -> String {
+    let mut result = String::with_capacity(css.len() + css.len() / 16);
+    let mut prev = None;
+
+    for c in css.chars() {
+        if c == '\n' && prev != Some('\r') {
+            result.push('\r');
+        }
+
+        result.push(c);
+        prev = Some(c);
+    }
+
+    result
+}
+
+/// Returns `path` relative to the directory `base`, or `path` itself if the
+/// two can't be related without touching the file system
+fn relative_path(path: &Path, base: &Path) -> PathBuf {
+    let path_components: Vec<Component> = path
+        .components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect();
+    let base_components: Vec<Component> = base
+        .components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect();
+
+    if path.has_root() != base.has_root() {
+        return path.to_path_buf();
+    }
+
+    let common = path_components
+        .iter()
+        .zip(&base_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    if base_components[common..]
+        .iter()
+        .any(|c| matches!(c, Component::ParentDir))
+    {
+        return path.to_path_buf();
+    }
+
+    base_components[common..]
+        .iter()
+        .map(|_| Component::ParentDir)
+        .chain(path_components[common..].iter().copied())
+        .collect()
+}
+
 pub(crate) struct Serializer<'a> {
     indentation: usize,
     options: &'a Options<'a>,
     inspect: bool,
     indent_width: usize,
+    /// The source depths of the style rules written in the current block that
+    /// may still enclose the next one, used by the nested output style
+    written_rule_depths: Vec<usize>,
     // todo: use this field
     _quote: bool,
     buffer: Vec<u8>,
@@ -138,7 +211,11 @@ impl<'a> Serializer<'a> {
             inspect,
             _quote: true,
             indentation: 0,
-            indent_width: 2,
+            indent_width: match options.indent {
+                Indent::Spaces(width) => width,
+                Indent::Tabs => 1,
+            },
+            written_rule_depths: Vec::new(),
             options,
             buffer: Vec::new(),
             map,
@@ -221,11 +298,31 @@ impl<'a> Serializer<'a> {
                 self.buffer.extend_from_slice(name.ident.as_bytes());
             }
             SimpleSelector::Attribute(attr) => write!(&mut self.buffer, "{}", attr).unwrap(),
-            SimpleSelector::Parent(..) => unreachable!("It should not be possible to format `&`."),
+            // Only unresolved selectors with preserved CSS nesting contain `&`
+            SimpleSelector::Parent(suffix) => {
+                self.buffer.push(b'&');
+                if let Some(suffix) = suffix {
+                    self.buffer.extend_from_slice(suffix.as_bytes());
+                }
+            }
         }
     }
 
     fn write_compound_selector(&mut self, compound: &CompoundSelector) {
+        // A type selector must come first, so `&` written after one, as in
+        // `div&`, is emitted after it
+        if let [parent @ SimpleSelector::Parent(None), first @ (SimpleSelector::Type(..) | SimpleSelector::Universal(..)), rest @ ..] =
+            compound.components.as_slice()
+        {
+            self.write_simple_selector(first);
+            self.write_simple_selector(parent);
+            for simple in rest {
+                self.write_simple_selector(simple);
+            }
+
+            return;
+        }
+
         let mut did_write = false;
         for simple in &compound.components {
             if did_write {
@@ -306,12 +403,7 @@ impl<'a> Serializer<'a> {
     }
 
     fn write_calculation_name(&mut self, name: CalculationName) {
-        match name {
-            CalculationName::Calc => self.buffer.extend_from_slice(b"calc"),
-            CalculationName::Min => self.buffer.extend_from_slice(b"min"),
-            CalculationName::Max => self.buffer.extend_from_slice(b"max"),
-            CalculationName::Clamp => self.buffer.extend_from_slice(b"clamp"),
-        }
+        self.buffer.extend_from_slice(name.as_str().as_bytes());
     }
 
     fn visit_calculation(&mut self, calculation: &SassCalculation) -> SassResult<()> {
@@ -462,7 +554,60 @@ impl<'a> Serializer<'a> {
             && Self::is_symmetrical_hex(color.blue().0.round() as u32)
     }
 
+    /// Colors in non-legacy spaces, such as `lab(50% 20 30)` or
+    /// `color(display-p3 1 0 0 / 0.5)`
+    fn write_space_color(&mut self, space: ColorSpace, channels: [Option<f64>; 3], alpha: f64) {
+        if space.is_predefined() {
+            self.buffer.extend_from_slice(b"color(");
+            self.buffer.extend_from_slice(space.name().as_bytes());
+            self.buffer.push(b' ');
+        } else {
+            self.buffer.extend_from_slice(space.name().as_bytes());
+            self.buffer.push(b'(');
+        }
+
+        for (idx, (channel, info)) in channels.iter().zip(space.channels()).enumerate() {
+            if idx != 0 {
+                self.buffer.push(b' ');
+            }
+
+            let channel = match channel {
+                Some(channel) => *channel,
+                None => {
+                    self.buffer.extend_from_slice(b"none");
+                    continue;
+                }
+            };
+
+            match info.kind {
+                ChannelKind::Percent => {
+                    self.write_float(channel * 100.0 / info.max);
+                    self.buffer.push(b'%');
+                }
+                ChannelKind::Hue => {
+                    self.write_float(channel);
+                    self.buffer.extend_from_slice(b"deg");
+                }
+                ChannelKind::Linear => self.write_float(channel),
+            }
+        }
+
+        if !fuzzy_equals(alpha, 1.0) {
+            self.write_optional_space();
+            self.buffer.push(b'/');
+            self.write_optional_space();
+            self.write_float(alpha);
+        }
+
+        self.buffer.push(b')');
+    }
+
     pub fn visit_color(&mut self, color: &Color) {
+        if let Some((space, channels)) = color.space_channels() {
+            self.write_space_color(space, channels, color.alpha().0);
+            return;
+        }
+
         let red = color.red().0.round() as u8;
         let green = color.green().0.round() as u8;
         let blue = color.blue().0.round() as u8;
@@ -515,29 +660,7 @@ impl<'a> Serializer<'a> {
     }
 
     fn write_media_query(&mut self, query: &MediaQuery) {
-        if let Some(modifier) = &query.modifier {
-            self.buffer.extend_from_slice(modifier.as_bytes());
-            self.buffer.push(b' ');
-        }
-
-        if let Some(media_type) = &query.media_type {
-            self.buffer.extend_from_slice(media_type.as_bytes());
-
-            if !query.conditions.is_empty() {
-                self.buffer.extend_from_slice(b" and ");
-            }
-        }
-
-        if query.conditions.len() == 1 && query.conditions.first().unwrap().starts_with("(not ") {
-            self.buffer.extend_from_slice(b"not ");
-            let condition = query.conditions.first().unwrap();
-            self.buffer
-                .extend_from_slice(condition["(not ".len()..condition.len() - 1].as_bytes());
-        } else {
-            let operator = if query.conjunction { " and " } else { " or " };
-            self.buffer
-                .extend_from_slice(query.conditions.join(operator).as_bytes());
-        }
+        write!(&mut self.buffer, "{}", query).unwrap();
     }
 
     pub fn visit_number(&mut self, number: &SassNumber) -> SassResult<()> {
@@ -651,8 +774,8 @@ impl<'a> Serializer<'a> {
             self.buffer.push(b';');
         }
 
-        if !self.buffer.is_empty() {
-            self.write_optional_newline();
+        if !self.buffer.is_empty() && self.options.writes_trailing_newline() {
+            self.buffer.push(b'\n');
         }
 
         // SAFETY: todo
@@ -664,18 +787,42 @@ impl<'a> Serializer<'a> {
             as_string.insert_str(0, "@charset \"UTF-8\";\n");
         }
 
-        as_string
+        match self.options.line_ending {
+            LineEnding::Lf => as_string,
+            LineEnding::Crlf => with_crlf_line_endings(&as_string),
+        }
     }
 
     fn write_indentation(&mut self) {
-        if self.options.is_compressed() {
+        if matches!(
+            self.options.style,
+            OutputStyle::Compressed | OutputStyle::Compact
+        ) {
             return;
         }
 
-        self.buffer.reserve(self.indentation);
-        for _ in 0..self.indentation {
-            self.buffer.push(b' ');
+        let indent = match self.options.indent {
+            Indent::Spaces(..) => b' ',
+            Indent::Tabs => b'\t',
+        };
+
+        self.buffer
+            .resize(self.buffer.len() + self.indentation, indent);
+    }
+
+    /// In the nested output style, style rules and the at-rules that bubbled
+    /// out of them are indented once for each enclosing style rule that was
+    /// written with declarations of its own
+    fn nested_rule_indentation(&mut self, depth: usize) -> usize {
+        while self
+            .written_rule_depths
+            .last()
+            .is_some_and(|&last| last >= depth)
+        {
+            self.written_rule_depths.pop();
         }
+
+        self.written_rule_depths.len() * self.indent_width
     }
 
     fn write_list_separator(&mut self, sep: ListSeparator) {
@@ -946,6 +1093,25 @@ impl<'a> Serializer<'a> {
         Ok(())
     }
 
+    fn visit_mixin_ref(&mut self, mixin: &Mixin, span: Span) -> SassResult<()> {
+        if !self.inspect {
+            return Err((
+                format!(
+                    "{} isn't a valid CSS value.",
+                    inspect_mixin_ref(mixin, self.options, span)?
+                ),
+                span,
+            )
+                .into());
+        }
+
+        self.buffer.extend_from_slice(b"get-mixin(");
+        self.visit_quoted_string(false, mixin.name().as_str());
+        self.buffer.push(b')');
+
+        Ok(())
+    }
+
     fn visit_arglist(&mut self, arglist: &ArgList, span: Span) -> SassResult<()> {
         self.visit_list(&arglist.elems, ListSeparator::Comma, Brackets::None, span)
     }
@@ -965,6 +1131,7 @@ impl<'a> Serializer<'a> {
             }
             Value::Map(map) => self.visit_map(map, span)?,
             Value::FunctionRef(func) => self.visit_function_ref(func, span)?,
+            Value::MixinRef(mixin) => self.visit_mixin_ref(mixin, span)?,
             Value::String(s, QuoteKind::Quoted) => self.visit_quoted_string(false, s),
             Value::String(s, QuoteKind::None) => self.visit_unquoted_string(s),
             Value::ArgList(arglist) => self.visit_arglist(arglist, span)?,
@@ -992,6 +1159,27 @@ impl<'a> Serializer<'a> {
         Ok(())
     }
 
+    /// Writes a comment pointing to the source of the style rule at `span`,
+    /// such as `/* line 42, src/_button.scss */`
+    fn write_source_comment(&mut self, span: Span) {
+        let loc = self.map.look_up_span(span);
+        let entrypoint = self.map.look_up_span(self.span).file;
+        let base = Path::new(entrypoint.name())
+            .parent()
+            .unwrap_or(Path::new(""));
+        let path = relative_path(Path::new(loc.file.name()), base);
+
+        self.write_indentation();
+        self.buffer.extend_from_slice(
+            format!(
+                "/* line {}, {} */\n",
+                loc.begin.line + 1,
+                path.to_string_lossy()
+            )
+            .as_bytes(),
+        );
+    }
+
     fn write_import(&mut self, import: &str, modifiers: Option<String>) -> SassResult<()> {
         self.write_indentation();
         self.buffer.extend_from_slice(b"@import ");
@@ -1006,7 +1194,7 @@ impl<'a> Serializer<'a> {
     }
 
     fn write_comment(&mut self, comment: &str, span: Span) -> SassResult<()> {
-        if self.options.is_compressed() && !comment.starts_with("/*!") {
+        if !self.options.keeps_comment(comment) {
             return Ok(());
         }
 
@@ -1035,21 +1223,58 @@ impl<'a> Serializer<'a> {
 
     pub fn requires_semicolon(stmt: &CssStmt) -> bool {
         match stmt {
-            CssStmt::Style(_) | CssStmt::Import(_, _) => true,
+            CssStmt::Style(_) | CssStmt::Import(..) => true,
             CssStmt::UnknownAtRule(rule, _) => !rule.has_body,
+            CssStmt::Layer(rule, _) => !rule.has_body,
             _ => false,
         }
     }
 
     fn write_children(&mut self, mut children: Vec<CssStmt>) -> SassResult<()> {
-        if self.options.is_compressed() {
-            self.buffer.push(b'{');
-        } else {
-            self.buffer.extend_from_slice(b" {\n");
+        match self.options.style {
+            OutputStyle::Compressed => self.buffer.push(b'{'),
+            OutputStyle::Compact => self.buffer.extend_from_slice(b" { "),
+            OutputStyle::Expanded | OutputStyle::Nested => self.buffer.extend_from_slice(b" {\n"),
         }
 
         self.indentation += self.indent_width;
 
+        // The nested and compact styles close each block at the end of its
+        // last child, rather than on a line of its own
+        if matches!(
+            self.options.style,
+            OutputStyle::Nested | OutputStyle::Compact
+        ) {
+            let written_rule_depths = mem::take(&mut self.written_rule_depths);
+            let mut children = children
+                .into_iter()
+                .filter(|child| !child.is_invisible())
+                .peekable();
+
+            while let Some(child) = children.next() {
+                let needs_semicolon = Self::requires_semicolon(&child);
+                self.visit_stmt(child)?;
+
+                if needs_semicolon {
+                    self.buffer.push(b';');
+                }
+
+                if children.peek().is_some() {
+                    if self.options.style == OutputStyle::Nested {
+                        self.buffer.push(b'\n');
+                    } else {
+                        self.buffer.push(b' ');
+                    }
+                }
+            }
+
+            self.indentation -= self.indent_width;
+            self.written_rule_depths = written_rule_depths;
+            self.buffer.extend_from_slice(b" }");
+
+            return Ok(());
+        }
+
         let last = children.pop();
 
         for child in children {
@@ -1119,18 +1344,129 @@ impl<'a> Serializer<'a> {
         Ok(())
     }
 
+    fn write_layer_rule(&mut self, layer_rule: LayerRule) -> SassResult<()> {
+        self.write_indentation();
+        self.buffer.extend_from_slice(b"@layer");
+
+        if let Some((first, rest)) = layer_rule.names.split_first() {
+            self.buffer.push(b' ');
+            self.buffer.extend_from_slice(first.as_bytes());
+
+            for name in rest {
+                self.buffer.push(b',');
+                self.write_optional_space();
+                self.buffer.extend_from_slice(name.as_bytes());
+            }
+        }
+
+        if !layer_rule.has_body {
+            debug_assert!(layer_rule.body.is_empty());
+            return Ok(());
+        } else if layer_rule.body.iter().all(CssStmt::is_invisible) {
+            self.write_optional_space();
+            self.buffer.extend_from_slice(b"{}");
+            return Ok(());
+        }
+
+        self.write_children(layer_rule.body)
+    }
+
+    fn write_container_rule(&mut self, container_rule: ContainerRule) -> SassResult<()> {
+        self.write_indentation();
+        self.buffer.extend_from_slice(b"@container ");
+
+        if let Some((last, rest)) = container_rule.query.split_last() {
+            for query in rest {
+                write!(&mut self.buffer, "{}", query)?;
+                self.buffer.push(b',');
+                self.write_optional_space();
+            }
+
+            write!(&mut self.buffer, "{}", last)?;
+        }
+
+        self.write_children(container_rule.body)
+    }
+
+    fn write_scope_rule(&mut self, scope_rule: ScopeRule) -> SassResult<()> {
+        self.write_indentation();
+        self.buffer.extend_from_slice(b"@scope");
+
+        if !scope_rule.prelude.is_empty() {
+            write!(&mut self.buffer, " {}", scope_rule.prelude)?;
+        }
+
+        self.write_children(scope_rule.body)
+    }
+
     /// Returns whether or not text was written
     fn visit_stmt(&mut self, stmt: CssStmt) -> SassResult<bool> {
         if stmt.is_invisible() {
             return Ok(false);
         }
 
+        let at_rule_depth = match &stmt {
+            CssStmt::Media(rule, ..) => Some(rule.depth),
+            CssStmt::UnknownAtRule(rule, ..) => Some(rule.depth),
+            CssStmt::Supports(rule, ..) => Some(rule.depth),
+            CssStmt::Layer(rule, ..) => Some(rule.depth),
+            CssStmt::Container(rule, ..) => Some(rule.depth),
+            CssStmt::Scope(rule, ..) => Some(rule.depth),
+            _ => None,
+        };
+
+        let at_rule_indentation = match at_rule_depth {
+            Some(depth) if self.options.style == OutputStyle::Nested => {
+                self.nested_rule_indentation(depth)
+            }
+            Some(..) | None => 0,
+        };
+
+        self.indentation += at_rule_indentation;
+
         match stmt {
-            CssStmt::RuleSet { selector, body, .. } => {
+            CssStmt::RuleSet {
+                selector,
+                body,
+                nested_selector,
+                is_group_end,
+                depth,
+            } => {
+                // Rules kept nested within their parent are already indented
+                // by being written as its children
+                let depth_indentation =
+                    if self.options.style == OutputStyle::Nested && nested_selector.is_none() {
+                        let indentation = self.nested_rule_indentation(depth);
+                        self.written_rule_depths.push(depth);
+                        indentation
+                    } else {
+                        0
+                    };
+
+                self.indentation += depth_indentation;
+
+                if self.options.source_comments
+                    && matches!(
+                        self.options.style,
+                        OutputStyle::Expanded | OutputStyle::Nested
+                    )
+                {
+                    self.write_source_comment(selector.as_selector_list().span);
+                }
+
                 self.write_indentation();
-                self.write_selector_list(&selector.as_selector_list());
+
+                match nested_selector {
+                    Some(nested_selector) => self.write_selector_list(&nested_selector.selector),
+                    None => self.write_selector_list(&selector.as_selector_list()),
+                }
 
                 self.write_children(body)?;
+                self.indentation -= depth_indentation;
+
+                if is_group_end {
+                    self.written_rule_depths.clear();
+                }
             }
             CssStmt::Media(media_rule, ..) => {
                 self.write_indentation();
@@ -1162,13 +1498,11 @@ impl<'a> Serializer<'a> {
 
                 if !unknown_at_rule.has_body {
                     debug_assert!(unknown_at_rule.body.is_empty());
-                    return Ok(true);
                 } else if unknown_at_rule.body.iter().all(CssStmt::is_invisible) {
                     self.buffer.extend_from_slice(b" {}");
-                    return Ok(true);
+                } else {
+                    self.write_children(unknown_at_rule.body)?;
                 }
-
-                self.write_children(unknown_at_rule.body)?;
             }
             CssStmt::Style(style) => self.write_style(style)?,
             CssStmt::Comment(comment, span) => self.write_comment(&comment, span)?,
@@ -1186,10 +1520,15 @@ impl<'a> Serializer<'a> {
 
                 self.write_children(keyframes_rule_set.body)?;
             }
-            CssStmt::Import(import, modifier) => self.write_import(&import, modifier)?,
+            CssStmt::Import(import, modifier, _) => self.write_import(&import, modifier)?,
             CssStmt::Supports(supports_rule, _) => self.write_supports_rule(supports_rule)?,
+            CssStmt::Layer(layer_rule, _) => self.write_layer_rule(layer_rule)?,
+            CssStmt::Container(container_rule, _) => self.write_container_rule(container_rule)?,
+            CssStmt::Scope(scope_rule, _) => self.write_scope_rule(scope_rule)?,
         }
 
+        self.indentation -= at_rule_indentation;
+
         Ok(true)
     }
 }
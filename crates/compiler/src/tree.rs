@@ -0,0 +1,582 @@
+//! A public representation of compiled CSS, returned by
+//! [`crate::compile_to_tree`].
+//!
+//! The tree can be inspected and modified in Rust, then written with
+//! [`CssStylesheet::to_css`], which uses the same serializer as
+//! [`crate::from_string`].
+
+use std::sync::Arc;
+
+use codemap::{CodeMap, File, LineCol, Span, SpanLoc, Spanned};
+
+use crate::{
+    ast::{
+        ContainerQuery, ContainerRule, CssStmt, KeyframesRuleSet, LayerRule, MediaQuery, MediaRule,
+        NestedSelector, ScopePrelude, ScopeRule, Style, SupportsRule, UnknownAtRule,
+    },
+    common::{unvendor, Brackets, ListSeparator, QuoteKind},
+    error::SassResult,
+    interner::InternedString,
+    lexer::Lexer,
+    parse::KeyframesSelectorParser,
+    selector::{ExtendedSelector, SelectorList, SelectorParser},
+    serializer::{serialize_selector_list, serialize_value},
+    value::Value,
+    Options, Result,
+};
+
+/// A compiled stylesheet
+///
+/// Selectors, declaration values and at-rule preludes are serialized
+/// according to the [`crate::OutputStyle`] of the [`Options`] the stylesheet
+/// was compiled with.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CssStylesheet {
+    pub children: Vec<CssNode>,
+
+    /// The start of the entrypoint, which source comments written by
+    /// [`Self::to_css`] are relative to
+    pub span: Option<SpanLoc>,
+}
+
+/// A single statement in a [`CssStylesheet`]
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CssNode {
+    StyleRule(CssStyleRule),
+    Declaration(CssDeclaration),
+    AtRule(CssAtRule),
+    Comment(CssComment),
+}
+
+/// A style rule: `a, b { color: red; }`
+///
+/// Keyframe blocks such as `50% { .. }` are represented as style rules within
+/// a `@keyframes` rule.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CssStyleRule {
+    /// Each complex selector in the rule's selector list. Rules nested within
+    /// another style rule have selectors relative to that rule, such as
+    /// `&:hover`
+    pub selectors: Vec<String>,
+    pub children: Vec<CssNode>,
+
+    /// Whether this rule is followed by a blank line in expanded output
+    pub is_group_end: bool,
+
+    /// Where the rule's selector was written, or `None` for rules that weren't
+    /// compiled from source
+    pub span: Option<SpanLoc>,
+}
+
+/// A declaration: `color: red !important`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CssDeclaration {
+    pub name: String,
+
+    /// The declaration's value, not including `!important`
+    pub value: String,
+    pub important: bool,
+
+    /// Where the declaration was written, or `None` for declarations that
+    /// weren't compiled from source
+    pub span: Option<SpanLoc>,
+}
+
+/// An at-rule such as `@media`, `@supports`, `@font-face` or `@import`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CssAtRule {
+    /// The name of the rule, without the leading `@`
+    pub name: String,
+
+    /// Everything between the name and the block or semicolon, such as
+    /// `screen and (min-width: 768px)`
+    pub prelude: String,
+
+    /// The contents of the rule's block, or `None` for statements like
+    /// `@import "foo.css";` that have no block
+    pub children: Option<Vec<CssNode>>,
+
+    /// Whether this rule is followed by a blank line in expanded output
+    pub is_group_end: bool,
+
+    /// Where the rule was written, or `None` for rules that weren't compiled
+    /// from source
+    pub span: Option<SpanLoc>,
+}
+
+/// A loud comment: `/* .. */`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CssComment {
+    /// The full text of the comment, including `/*` and `*/`
+    pub text: String,
+
+    /// Where the comment was written, or `None` for comments that weren't
+    /// compiled from source
+    pub span: Option<SpanLoc>,
+}
+
+impl CssStylesheet {
+    pub(crate) fn from_stmts(
+        stmts: Vec<CssStmt>,
+        options: &Options,
+        map: &CodeMap,
+        span: Span,
+    ) -> SassResult<Self> {
+        let builder = TreeBuilder { options, map };
+
+        Ok(Self {
+            children: builder.nodes(stmts)?,
+            span: Some(map.look_up_span(span)),
+        })
+    }
+
+    /// Write this stylesheet as CSS
+    ///
+    /// Selectors, media queries and the preludes of other well-known at-rules
+    /// are parsed again, so this fails if they were modified to be invalid.
+    /// Declaration values and the preludes of other at-rules are written as
+    /// they are.
+    ///
+    /// The tree doesn't record line breaks between selectors or how deeply
+    /// style rules were nested in the source, so these aren't reproduced in
+    /// expanded and nested output.
+    ///
+    /// ```
+    /// # use grass_compiler as grass;
+    /// fn main() -> Result<(), Box<grass::Error>> {
+    ///     let options = grass::Options::default();
+    ///     let mut tree = grass::compile_to_tree("a { color: red; }".to_owned(), &options)?;
+    ///
+    ///     if let grass::CssNode::StyleRule(rule) = &mut tree.children[0] {
+    ///         rule.selectors.push("b".to_owned());
+    ///     }
+    ///
+    ///     assert_eq!(tree.to_css(&options)?, "a, b {\n  color: red;\n}\n");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn to_css(&self, options: &Options) -> Result<String> {
+        let mut writer = TreeWriter::new(self.span.as_ref());
+
+        let stmts = writer.stmts(&self.children, false, false).map_err(|e| {
+            crate::raw_to_parse_error(&writer.map, *e, options.unicode_error_messages)
+        })?;
+
+        crate::serialize_stmts(stmts, options, &writer.map, writer.span)
+    }
+}
+
+/// Converts the statements produced by evaluation into public nodes
+struct TreeBuilder<'a> {
+    options: &'a Options<'a>,
+    map: &'a CodeMap,
+}
+
+impl TreeBuilder<'_> {
+    fn nodes(&self, stmts: Vec<CssStmt>) -> SassResult<Vec<CssNode>> {
+        stmts
+            .into_iter()
+            .filter(|stmt| !stmt.is_invisible())
+            .map(|stmt| self.node(stmt))
+            .collect()
+    }
+
+    fn span(&self, span: Span) -> Option<SpanLoc> {
+        Some(self.map.look_up_span(span))
+    }
+
+    fn at_rule(
+        &self,
+        name: &str,
+        prelude: String,
+        children: Option<Vec<CssStmt>>,
+        is_group_end: bool,
+        span: Span,
+    ) -> SassResult<CssNode> {
+        Ok(CssNode::AtRule(CssAtRule {
+            name: name.to_owned(),
+            prelude,
+            children: children.map(|children| self.nodes(children)).transpose()?,
+            is_group_end,
+            span: self.span(span),
+        }))
+    }
+
+    fn node(&self, stmt: CssStmt) -> SassResult<CssNode> {
+        match stmt {
+            CssStmt::RuleSet {
+                selector,
+                body,
+                is_group_end,
+                nested_selector,
+                ..
+            } => {
+                let list = match nested_selector {
+                    Some(nested_selector) => nested_selector.selector,
+                    None => selector.as_selector_list().clone(),
+                };
+
+                let selectors = list
+                    .components
+                    .iter()
+                    .filter(|complex| !complex.is_invisible())
+                    .map(|complex| {
+                        let single = SelectorList {
+                            components: vec![complex.clone()],
+                            span: list.span,
+                        };
+                        serialize_selector_list(&single, self.options, list.span)
+                    })
+                    .collect();
+
+                Ok(CssNode::StyleRule(CssStyleRule {
+                    selectors,
+                    children: self.nodes(body)?,
+                    is_group_end,
+                    span: self.span(list.span),
+                }))
+            }
+            CssStmt::KeyframesRuleSet(keyframes) => Ok(CssNode::StyleRule(CssStyleRule {
+                selectors: keyframes.selector.iter().map(ToString::to_string).collect(),
+                children: self.nodes(keyframes.body)?,
+                is_group_end: false,
+                span: self.span(keyframes.span),
+            })),
+            CssStmt::Style(style) => {
+                let value_span = style.value.span;
+                let (value, important) = match style.value.node {
+                    Value::List(mut elems, ListSeparator::Space, Brackets::None)
+                        if matches!(
+                            elems.last(),
+                            Some(Value::String(s, QuoteKind::None)) if s == "!important"
+                        ) =>
+                    {
+                        elems.pop();
+
+                        let value = if elems.len() == 1 {
+                            elems.pop().unwrap()
+                        } else {
+                            Value::List(elems, ListSeparator::Space, Brackets::None)
+                        };
+
+                        (value, true)
+                    }
+                    value => (value, false),
+                };
+
+                Ok(CssNode::Declaration(CssDeclaration {
+                    name: style.property.to_string(),
+                    value: serialize_value(&value, self.options, value_span)?,
+                    important,
+                    span: self.span(style.span),
+                }))
+            }
+            CssStmt::Media(media, is_group_end) => self.at_rule(
+                "media",
+                media
+                    .query
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                Some(media.body),
+                is_group_end,
+                media.span,
+            ),
+            CssStmt::UnknownAtRule(at_rule, is_group_end) => self.at_rule(
+                &at_rule.name,
+                at_rule.params,
+                at_rule.has_body.then_some(at_rule.body),
+                is_group_end,
+                at_rule.span,
+            ),
+            CssStmt::Supports(supports, is_group_end) => self.at_rule(
+                "supports",
+                supports.params,
+                Some(supports.body),
+                is_group_end,
+                supports.span,
+            ),
+            CssStmt::Layer(layer, is_group_end) => self.at_rule(
+                "layer",
+                layer.names.join(", "),
+                layer.has_body.then_some(layer.body),
+                is_group_end,
+                layer.span,
+            ),
+            CssStmt::Container(container, is_group_end) => self.at_rule(
+                "container",
+                container
+                    .query
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                Some(container.body),
+                is_group_end,
+                container.span,
+            ),
+            CssStmt::Scope(scope, is_group_end) => self.at_rule(
+                "scope",
+                scope.prelude.to_string(),
+                Some(scope.body),
+                is_group_end,
+                scope.span,
+            ),
+            CssStmt::Comment(text, span) => Ok(CssNode::Comment(CssComment {
+                text,
+                span: self.span(span),
+            })),
+            CssStmt::Import(url, modifiers, span) => {
+                let prelude = match modifiers {
+                    Some(modifiers) => format!("{} {}", url, modifiers),
+                    None => url,
+                };
+
+                self.at_rule("import", prelude, None, false, span)
+            }
+        }
+    }
+}
+
+/// Converts public nodes back into statements the serializer can write
+struct TreeWriter {
+    map: CodeMap,
+
+    /// Each source file referenced by the tree, along with its copy in `map`
+    files: Vec<(Arc<File>, Arc<File>)>,
+
+    /// The span of the entrypoint, also used for nodes without a span
+    span: Span,
+}
+
+impl TreeWriter {
+    fn new(entrypoint: Option<&SpanLoc>) -> Self {
+        let mut map = CodeMap::new();
+        let span = map.add_file("stdin".to_owned(), String::new()).span;
+
+        let mut writer = Self {
+            map,
+            files: Vec::new(),
+            span,
+        };
+
+        writer.span = writer.span(entrypoint);
+
+        writer
+    }
+
+    /// Finds the span in `self.map` that corresponds to `loc`
+    fn span(&mut self, loc: Option<&SpanLoc>) -> Span {
+        let loc = match loc {
+            Some(loc) => loc,
+            None => return self.span,
+        };
+
+        let file = match self
+            .files
+            .iter()
+            .find(|(original, _)| Arc::ptr_eq(original, &loc.file))
+        {
+            Some((_, file)) => Arc::clone(file),
+            None => {
+                let file = self
+                    .map
+                    .add_file(loc.file.name().to_owned(), loc.file.source().to_owned());
+                self.files.push((Arc::clone(&loc.file), Arc::clone(&file)));
+                file
+            }
+        };
+
+        let offset = |position: LineCol| -> Option<u64> {
+            if position.line >= file.num_lines() {
+                return None;
+            }
+
+            let line = file.line_span(position.line);
+            let column: usize = file
+                .source_slice(line)
+                .chars()
+                .take(position.column)
+                .map(char::len_utf8)
+                .sum();
+
+            Some(line.low() - file.span.low() + column as u64)
+        };
+
+        match (offset(loc.begin), offset(loc.end)) {
+            (Some(begin), Some(end)) if begin <= end => file.span.subspan(begin, end),
+            _ => self.span,
+        }
+    }
+
+    fn stmts(
+        &mut self,
+        nodes: &[CssNode],
+        in_style_rule: bool,
+        in_keyframes: bool,
+    ) -> SassResult<Vec<CssStmt>> {
+        nodes
+            .iter()
+            .map(|node| self.stmt(node, in_style_rule, in_keyframes))
+            .collect()
+    }
+
+    fn stmt(
+        &mut self,
+        node: &CssNode,
+        in_style_rule: bool,
+        in_keyframes: bool,
+    ) -> SassResult<CssStmt> {
+        match node {
+            CssNode::StyleRule(rule) => {
+                let span = self.span(rule.span.as_ref());
+                let text = rule.selectors.join(", ");
+
+                if in_keyframes {
+                    let selector =
+                        KeyframesSelectorParser::new(Lexer::new_from_string(&text, span))
+                            .parse_keyframes_selector()?;
+
+                    return Ok(CssStmt::KeyframesRuleSet(KeyframesRuleSet {
+                        selector,
+                        body: self.stmts(&rule.children, true, false)?,
+                        span,
+                    }));
+                }
+
+                let list = SelectorParser::new(
+                    Lexer::new_from_string(&text, span),
+                    in_style_rule,
+                    false,
+                    span,
+                )
+                .parse()?;
+
+                let nested_selector = in_style_rule.then(|| {
+                    Box::new(NestedSelector {
+                        selector: list.clone(),
+                        resolved: list.clone(),
+                    })
+                });
+
+                Ok(CssStmt::RuleSet {
+                    selector: ExtendedSelector::new(list),
+                    body: self.stmts(&rule.children, true, false)?,
+                    is_group_end: rule.is_group_end,
+                    nested_selector,
+                    depth: 0,
+                })
+            }
+            CssNode::Declaration(declaration) => {
+                let span = self.span(declaration.span.as_ref());
+                let value = Value::String(declaration.value.clone(), QuoteKind::None);
+
+                let value = if declaration.important {
+                    Value::List(
+                        vec![
+                            value,
+                            Value::String("!important".to_owned(), QuoteKind::None),
+                        ],
+                        ListSeparator::Space,
+                        Brackets::None,
+                    )
+                } else {
+                    value
+                };
+
+                Ok(CssStmt::Style(Style {
+                    property: InternedString::get_or_intern(&declaration.name),
+                    value: Box::new(Spanned { node: value, span }),
+                    declared_as_custom_property: declaration.name.starts_with("--"),
+                    span,
+                }))
+            }
+            CssNode::AtRule(at_rule) => self.at_rule(at_rule, in_style_rule),
+            CssNode::Comment(comment) => Ok(CssStmt::Comment(
+                comment.text.clone(),
+                self.span(comment.span.as_ref()),
+            )),
+        }
+    }
+
+    fn at_rule(&mut self, at_rule: &CssAtRule, in_style_rule: bool) -> SassResult<CssStmt> {
+        let span = self.span(at_rule.span.as_ref());
+        let is_group_end = at_rule.is_group_end;
+        let prelude = at_rule.prelude.as_str();
+
+        let children = match &at_rule.children {
+            Some(children) => {
+                let in_keyframes = unvendor(&at_rule.name) == "keyframes";
+                Some(self.stmts(children, in_style_rule, in_keyframes)?)
+            }
+            None => None,
+        };
+
+        Ok(match (at_rule.name.as_str(), children) {
+            ("media", Some(body)) => CssStmt::Media(
+                MediaRule {
+                    query: MediaQuery::parse_list(prelude, span)?,
+                    body,
+                    span,
+                    depth: 0,
+                },
+                is_group_end,
+            ),
+            ("supports", Some(body)) => CssStmt::Supports(
+                SupportsRule {
+                    params: prelude.to_owned(),
+                    body,
+                    span,
+                    depth: 0,
+                },
+                is_group_end,
+            ),
+            ("layer", body) => CssStmt::Layer(
+                LayerRule {
+                    names: if prelude.trim().is_empty() {
+                        Vec::new()
+                    } else {
+                        LayerRule::parse_names(prelude, span)?
+                    },
+                    has_body: body.is_some(),
+                    body: body.unwrap_or_default(),
+                    span,
+                    depth: 0,
+                },
+                is_group_end,
+            ),
+            ("container", Some(body)) => CssStmt::Container(
+                ContainerRule {
+                    query: ContainerQuery::parse_list(prelude, span)?,
+                    body,
+                    span,
+                    depth: 0,
+                },
+                is_group_end,
+            ),
+            ("scope", Some(body)) => CssStmt::Scope(
+                ScopeRule {
+                    prelude: ScopePrelude::parse(prelude, span)?,
+                    body,
+                    span,
+                    depth: 0,
+                },
+                is_group_end,
+            ),
+            ("import", None) => CssStmt::Import(prelude.to_owned(), None, span),
+            (name, body) => CssStmt::UnknownAtRule(
+                UnknownAtRule {
+                    name: name.to_owned(),
+                    params: prelude.to_owned(),
+                    has_body: body.is_some(),
+                    body: body.unwrap_or_default(),
+                    span,
+                    depth: 0,
+                },
+                is_group_end,
+            ),
+        })
+    }
+}
@@ -121,16 +121,25 @@ pub(crate) static UNIT_CONVERSION_TABLE: Lazy<HashMap<Unit, HashMap<Unit, f64>>>
         from_dpi.insert(Unit::Dpi, 1.0);
         from_dpi.insert(Unit::Dpcm, 2.54);
         from_dpi.insert(Unit::Dppx, 96.0);
+        from_dpi.insert(Unit::X, 96.0);
 
         let mut from_dpcm = HashMap::new();
         from_dpcm.insert(Unit::Dpi, 1.0 / 2.54);
         from_dpcm.insert(Unit::Dpcm, 1.0);
         from_dpcm.insert(Unit::Dppx, 96.0 / 2.54);
+        from_dpcm.insert(Unit::X, 96.0 / 2.54);
 
         let mut from_dppx = HashMap::new();
         from_dppx.insert(Unit::Dpi, 1.0 / 96.0);
         from_dppx.insert(Unit::Dpcm, 2.54 / 96.0);
         from_dppx.insert(Unit::Dppx, 1.0);
+        from_dppx.insert(Unit::X, 1.0);
+
+        let mut from_x = HashMap::new();
+        from_x.insert(Unit::Dpi, 1.0 / 96.0);
+        from_x.insert(Unit::Dpcm, 2.54 / 96.0);
+        from_x.insert(Unit::Dppx, 1.0);
+        from_x.insert(Unit::X, 1.0);
 
         let mut m = HashMap::new();
         m.insert(Unit::In, from_in);
@@ -155,6 +164,7 @@ pub(crate) static UNIT_CONVERSION_TABLE: Lazy<HashMap<Unit, HashMap<Unit, f64>>>
         m.insert(Unit::Dpi, from_dpi);
         m.insert(Unit::Dpcm, from_dpcm);
         m.insert(Unit::Dppx, from_dppx);
+        m.insert(Unit::X, from_x);
 
         m
     });
@@ -169,6 +179,40 @@ pub(crate) static KNOWN_COMPATIBILITIES: Lazy<[HashSet<Unit>; 5]> = Lazy::new(||
         Unit::Vh,
         Unit::Vmin,
         Unit::Vmax,
+        Unit::Lh,
+        Unit::Cap,
+        Unit::Ic,
+        Unit::Rlh,
+        Unit::Rcap,
+        Unit::Rch,
+        Unit::Rex,
+        Unit::Ric,
+        Unit::Vi,
+        Unit::Vb,
+        Unit::Svw,
+        Unit::Svh,
+        Unit::Svi,
+        Unit::Svb,
+        Unit::Svmin,
+        Unit::Svmax,
+        Unit::Lvw,
+        Unit::Lvh,
+        Unit::Lvi,
+        Unit::Lvb,
+        Unit::Lvmin,
+        Unit::Lvmax,
+        Unit::Dvw,
+        Unit::Dvh,
+        Unit::Dvi,
+        Unit::Dvb,
+        Unit::Dvmin,
+        Unit::Dvmax,
+        Unit::Cqw,
+        Unit::Cqh,
+        Unit::Cqi,
+        Unit::Cqb,
+        Unit::Cqmin,
+        Unit::Cqmax,
         Unit::Cm,
         Unit::Mm,
         Unit::Q,
@@ -180,7 +224,7 @@ pub(crate) static KNOWN_COMPATIBILITIES: Lazy<[HashSet<Unit>; 5]> = Lazy::new(||
     let angles = HashSet::from_iter([Unit::Deg, Unit::Grad, Unit::Rad, Unit::Turn]);
     let time = HashSet::from_iter([Unit::S, Unit::Ms]);
     let frequency = HashSet::from_iter([Unit::Hz, Unit::Khz]);
-    let resolution = HashSet::from_iter([Unit::Dpi, Unit::Dpcm, Unit::Dppx]);
+    let resolution = HashSet::from_iter([Unit::Dpi, Unit::Dpcm, Unit::Dppx, Unit::X]);
 
     [dimensions, angles, time, frequency, resolution]
 });
@@ -195,6 +239,40 @@ pub(crate) fn known_compatibilities_by_unit(unit: &Unit) -> Option<&HashSet<Unit
         | Unit::Vh
         | Unit::Vmin
         | Unit::Vmax
+        | Unit::Lh
+        | Unit::Cap
+        | Unit::Ic
+        | Unit::Rlh
+        | Unit::Rcap
+        | Unit::Rch
+        | Unit::Rex
+        | Unit::Ric
+        | Unit::Vi
+        | Unit::Vb
+        | Unit::Svw
+        | Unit::Svh
+        | Unit::Svi
+        | Unit::Svb
+        | Unit::Svmin
+        | Unit::Svmax
+        | Unit::Lvw
+        | Unit::Lvh
+        | Unit::Lvi
+        | Unit::Lvb
+        | Unit::Lvmin
+        | Unit::Lvmax
+        | Unit::Dvw
+        | Unit::Dvh
+        | Unit::Dvi
+        | Unit::Dvb
+        | Unit::Dvmin
+        | Unit::Dvmax
+        | Unit::Cqw
+        | Unit::Cqh
+        | Unit::Cqi
+        | Unit::Cqb
+        | Unit::Cqmin
+        | Unit::Cqmax
         | Unit::Cm
         | Unit::Mm
         | Unit::Q
@@ -205,7 +283,7 @@ pub(crate) fn known_compatibilities_by_unit(unit: &Unit) -> Option<&HashSet<Unit
         Unit::Deg | Unit::Grad | Unit::Rad | Unit::Turn => Some(&KNOWN_COMPATIBILITIES[1]),
         Unit::S | Unit::Ms => Some(&KNOWN_COMPATIBILITIES[2]),
         Unit::Hz | Unit::Khz => Some(&KNOWN_COMPATIBILITIES[3]),
-        Unit::Dpi | Unit::Dpcm | Unit::Dppx => Some(&KNOWN_COMPATIBILITIES[4]),
+        Unit::Dpi | Unit::Dpcm | Unit::Dppx | Unit::X => Some(&KNOWN_COMPATIBILITIES[4]),
         _ => None,
     }
 }
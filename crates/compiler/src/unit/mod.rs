@@ -43,6 +43,14 @@ pub(crate) enum Unit {
     /// Equal to the computed value of the line-height property on the root element
     /// (typically <html>), converted to an absolute length
     Rlh,
+    /// Represents the "cap height" of the root element's font
+    Rcap,
+    /// The advance measure (width) of the glyph "0" of the root element's font
+    Rch,
+    /// x-height of the root element's font
+    Rex,
+    /// Equal to the used advance measure of the "水" glyph of the root element's font
+    Ric,
 
     // Viewport relative units
     /// 1% of the viewport's width
@@ -59,6 +67,56 @@ pub(crate) enum Unit {
     /// Equal to 1% of the size of the initial containing block, in the direction of the root
     /// element's block axis
     Vb,
+    /// 1% of the small viewport's width
+    Svw,
+    /// 1% of the small viewport's height
+    Svh,
+    /// 1% of the small viewport's size in the direction of the root element's inline axis
+    Svi,
+    /// 1% of the small viewport's size in the direction of the root element's block axis
+    Svb,
+    /// 1% of the small viewport's smaller dimension
+    Svmin,
+    /// 1% of the small viewport's larger dimension
+    Svmax,
+    /// 1% of the large viewport's width
+    Lvw,
+    /// 1% of the large viewport's height
+    Lvh,
+    /// 1% of the large viewport's size in the direction of the root element's inline axis
+    Lvi,
+    /// 1% of the large viewport's size in the direction of the root element's block axis
+    Lvb,
+    /// 1% of the large viewport's smaller dimension
+    Lvmin,
+    /// 1% of the large viewport's larger dimension
+    Lvmax,
+    /// 1% of the dynamic viewport's width
+    Dvw,
+    /// 1% of the dynamic viewport's height
+    Dvh,
+    /// 1% of the dynamic viewport's size in the direction of the root element's inline axis
+    Dvi,
+    /// 1% of the dynamic viewport's size in the direction of the root element's block axis
+    Dvb,
+    /// 1% of the dynamic viewport's smaller dimension
+    Dvmin,
+    /// 1% of the dynamic viewport's larger dimension
+    Dvmax,
+
+    // Container query units
+    /// 1% of a query container's width
+    Cqw,
+    /// 1% of a query container's height
+    Cqh,
+    /// 1% of a query container's inline size
+    Cqi,
+    /// 1% of a query container's block size
+    Cqb,
+    /// The smaller of `cqi` and `cqb`
+    Cqmin,
+    /// The larger of `cqi` and `cqb`
+    Cqmax,
 
     // Angle units
     /// Represents an angle in degrees. One full circle is 360deg
@@ -89,6 +147,8 @@ pub(crate) enum Unit {
     Dpcm,
     /// Represents the number of dots per px unit
     Dppx,
+    /// An alias for `dppx`
+    X,
 
     // Other units
     /// Represents a fraction of the available space in the grid container
@@ -126,6 +186,7 @@ pub(crate) enum UnitKind {
     Absolute,
     FontRelative,
     ViewportRelative,
+    ContainerRelative,
     Angle,
     Time,
     Frequency,
@@ -168,12 +229,26 @@ impl Unit {
             return true;
         }
         match self.kind() {
-            UnitKind::FontRelative | UnitKind::ViewportRelative | UnitKind::Other => self == other,
+            UnitKind::FontRelative
+            | UnitKind::ViewportRelative
+            | UnitKind::ContainerRelative
+            | UnitKind::Other => self == other,
             UnitKind::None => true,
             u => other.kind() == u,
         }
     }
 
+    /// Whether this is a unit of distance, such as `px`, `em` or `vw`
+    pub fn is_length(&self) -> bool {
+        matches!(
+            self.kind(),
+            UnitKind::Absolute
+                | UnitKind::FontRelative
+                | UnitKind::ViewportRelative
+                | UnitKind::ContainerRelative
+        )
+    }
+
     /// Used internally to determine if two units are comparable or not
     fn kind(&self) -> UnitKind {
         match self {
@@ -187,14 +262,42 @@ impl Unit {
             | Unit::Ch
             | Unit::Cap
             | Unit::Ic
-            | Unit::Rlh => UnitKind::FontRelative,
-            Unit::Vw | Unit::Vh | Unit::Vmin | Unit::Vmax | Unit::Vi | Unit::Vb => {
-                UnitKind::ViewportRelative
+            | Unit::Rlh
+            | Unit::Rcap
+            | Unit::Rch
+            | Unit::Rex
+            | Unit::Ric => UnitKind::FontRelative,
+            Unit::Vw
+            | Unit::Vh
+            | Unit::Vmin
+            | Unit::Vmax
+            | Unit::Vi
+            | Unit::Vb
+            | Unit::Svw
+            | Unit::Svh
+            | Unit::Svi
+            | Unit::Svb
+            | Unit::Svmin
+            | Unit::Svmax
+            | Unit::Lvw
+            | Unit::Lvh
+            | Unit::Lvi
+            | Unit::Lvb
+            | Unit::Lvmin
+            | Unit::Lvmax
+            | Unit::Dvw
+            | Unit::Dvh
+            | Unit::Dvi
+            | Unit::Dvb
+            | Unit::Dvmin
+            | Unit::Dvmax => UnitKind::ViewportRelative,
+            Unit::Cqw | Unit::Cqh | Unit::Cqi | Unit::Cqb | Unit::Cqmin | Unit::Cqmax => {
+                UnitKind::ContainerRelative
             }
             Unit::Deg | Unit::Grad | Unit::Rad | Unit::Turn => UnitKind::Angle,
             Unit::S | Unit::Ms => UnitKind::Time,
             Unit::Hz | Unit::Khz => UnitKind::Frequency,
-            Unit::Dpi | Unit::Dpcm | Unit::Dppx => UnitKind::Resolution,
+            Unit::Dpi | Unit::Dpcm | Unit::Dppx | Unit::X => UnitKind::Resolution,
             Unit::None => UnitKind::None,
             Unit::Fr | Unit::Percent | Unit::Unknown(..) | Unit::Complex { .. } => UnitKind::Other,
         }
@@ -220,12 +323,40 @@ impl From<String> for Unit {
             "cap" => Unit::Cap,
             "ic" => Unit::Ic,
             "rlh" => Unit::Rlh,
+            "rcap" => Unit::Rcap,
+            "rch" => Unit::Rch,
+            "rex" => Unit::Rex,
+            "ric" => Unit::Ric,
             "vw" => Unit::Vw,
             "vh" => Unit::Vh,
             "vmin" => Unit::Vmin,
             "vmax" => Unit::Vmax,
             "vi" => Unit::Vi,
             "vb" => Unit::Vb,
+            "svw" => Unit::Svw,
+            "svh" => Unit::Svh,
+            "svi" => Unit::Svi,
+            "svb" => Unit::Svb,
+            "svmin" => Unit::Svmin,
+            "svmax" => Unit::Svmax,
+            "lvw" => Unit::Lvw,
+            "lvh" => Unit::Lvh,
+            "lvi" => Unit::Lvi,
+            "lvb" => Unit::Lvb,
+            "lvmin" => Unit::Lvmin,
+            "lvmax" => Unit::Lvmax,
+            "dvw" => Unit::Dvw,
+            "dvh" => Unit::Dvh,
+            "dvi" => Unit::Dvi,
+            "dvb" => Unit::Dvb,
+            "dvmin" => Unit::Dvmin,
+            "dvmax" => Unit::Dvmax,
+            "cqw" => Unit::Cqw,
+            "cqh" => Unit::Cqh,
+            "cqi" => Unit::Cqi,
+            "cqb" => Unit::Cqb,
+            "cqmin" => Unit::Cqmin,
+            "cqmax" => Unit::Cqmax,
             "deg" => Unit::Deg,
             "grad" => Unit::Grad,
             "rad" => Unit::Rad,
@@ -237,6 +368,8 @@ impl From<String> for Unit {
             "dpi" => Unit::Dpi,
             "dpcm" => Unit::Dpcm,
             "dppx" => Unit::Dppx,
+            // Unlike other units, `x` is case sensitive
+            "x" if unit == "x" => Unit::X,
             "fr" => Unit::Fr,
             _ => Unit::Unknown(InternedString::get_or_intern(unit)),
         }
@@ -262,12 +395,40 @@ impl fmt::Display for Unit {
             Unit::Cap => write!(f, "cap"),
             Unit::Ic => write!(f, "ic"),
             Unit::Rlh => write!(f, "rlh"),
+            Unit::Rcap => write!(f, "rcap"),
+            Unit::Rch => write!(f, "rch"),
+            Unit::Rex => write!(f, "rex"),
+            Unit::Ric => write!(f, "ric"),
             Unit::Vw => write!(f, "vw"),
             Unit::Vh => write!(f, "vh"),
             Unit::Vmin => write!(f, "vmin"),
             Unit::Vmax => write!(f, "vmax"),
             Unit::Vi => write!(f, "vi"),
             Unit::Vb => write!(f, "vb"),
+            Unit::Svw => write!(f, "svw"),
+            Unit::Svh => write!(f, "svh"),
+            Unit::Svi => write!(f, "svi"),
+            Unit::Svb => write!(f, "svb"),
+            Unit::Svmin => write!(f, "svmin"),
+            Unit::Svmax => write!(f, "svmax"),
+            Unit::Lvw => write!(f, "lvw"),
+            Unit::Lvh => write!(f, "lvh"),
+            Unit::Lvi => write!(f, "lvi"),
+            Unit::Lvb => write!(f, "lvb"),
+            Unit::Lvmin => write!(f, "lvmin"),
+            Unit::Lvmax => write!(f, "lvmax"),
+            Unit::Dvw => write!(f, "dvw"),
+            Unit::Dvh => write!(f, "dvh"),
+            Unit::Dvi => write!(f, "dvi"),
+            Unit::Dvb => write!(f, "dvb"),
+            Unit::Dvmin => write!(f, "dvmin"),
+            Unit::Dvmax => write!(f, "dvmax"),
+            Unit::Cqw => write!(f, "cqw"),
+            Unit::Cqh => write!(f, "cqh"),
+            Unit::Cqi => write!(f, "cqi"),
+            Unit::Cqb => write!(f, "cqb"),
+            Unit::Cqmin => write!(f, "cqmin"),
+            Unit::Cqmax => write!(f, "cqmax"),
             Unit::Deg => write!(f, "deg"),
             Unit::Grad => write!(f, "grad"),
             Unit::Rad => write!(f, "rad"),
@@ -279,6 +440,7 @@ impl fmt::Display for Unit {
             Unit::Dpi => write!(f, "dpi"),
             Unit::Dpcm => write!(f, "dpcm"),
             Unit::Dppx => write!(f, "dppx"),
+            Unit::X => write!(f, "x"),
             Unit::Fr => write!(f, "fr"),
             Unit::Unknown(s) => write!(f, "{}", s),
             Unit::None => Ok(()),
@@ -126,7 +126,10 @@ impl<V: fmt::Debug + Clone, T: MapView<Value = V> + Clone> MapView for Unprefixe
     }
 
     fn iter(&self) -> Vec<(Identifier, Self::Value)> {
-        unimplemented!()
+        self.keys()
+            .into_iter()
+            .filter_map(|name| Some((name, self.get(name)?)))
+            .collect()
     }
 }
 
@@ -176,7 +179,10 @@ impl<V: fmt::Debug + Clone, T: MapView<Value = V> + Clone> MapView for PrefixedM
     }
 
     fn iter(&self) -> Vec<(Identifier, Self::Value)> {
-        unimplemented!()
+        self.keys()
+            .into_iter()
+            .filter_map(|name| Some((name, self.get(name)?)))
+            .collect()
     }
 }
 
@@ -243,7 +249,10 @@ impl<V: fmt::Debug + Clone, T: MapView<Value = V> + Clone> MapView for LimitedMa
     }
 
     fn iter(&self) -> Vec<(Identifier, Self::Value)> {
-        unimplemented!()
+        self.keys()
+            .into_iter()
+            .filter_map(|name| Some((name, self.get(name)?)))
+            .collect()
     }
 }
 
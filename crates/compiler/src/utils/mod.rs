@@ -1,9 +1,11 @@
 pub(crate) use chars::*;
 pub(crate) use map_view::*;
+pub(crate) use random::*;
 pub(crate) use strings::*;
 
 mod chars;
 mod map_view;
+mod random;
 mod strings;
 
 #[allow(clippy::case_sensitive_file_extension_comparisons)]
@@ -0,0 +1,57 @@
+/// A small, seedable pseudorandom number generator backing `random()` and
+/// `unique-id()`
+///
+/// This is an implementation of SplitMix64. It is not cryptographically secure,
+/// but it is fast, has no dependencies, and produces the same sequence on every
+/// platform for a given seed.
+#[derive(Debug, Clone)]
+pub(crate) struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Create a generator from `seed` if one was given.
+    ///
+    /// Otherwise, with the `random` feature, the generator is seeded from the
+    /// thread-local RNG; without it, a fixed seed is used so that output is
+    /// still reproducible.
+    pub fn from_seed(seed: Option<u64>) -> Self {
+        match seed {
+            Some(seed) => Self::new(seed),
+            #[cfg(feature = "random")]
+            None => Self::new(rand::random()),
+            #[cfg(not(feature = "random"))]
+            None => Self::new(0),
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A float uniformly distributed in `[0, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// An integer in `[0, limit)`
+    pub fn next_below(&mut self, limit: u64) -> u64 {
+        debug_assert!(limit > 0);
+        ((u128::from(self.next_u64()) * u128::from(limit)) >> 64) as u64
+    }
+
+    /// An ASCII letter or digit
+    pub fn next_alphanumeric(&mut self) -> char {
+        const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+        CHARSET[self.next_below(CHARSET.len() as u64) as usize] as char
+    }
+}
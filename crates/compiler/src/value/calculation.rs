@@ -6,9 +6,9 @@ use codemap::Span;
 use crate::{
     common::BinaryOp,
     error::SassResult,
-    serializer::inspect_number,
+    serializer::{inspect_number, serialize_calculation_arg},
     unit::Unit,
-    value::{SassNumber, Value},
+    value::{fuzzy_round, Number, SassNumber, Value},
     Options,
 };
 
@@ -43,22 +43,121 @@ pub(crate) enum CalculationName {
     Min,
     Max,
     Clamp,
+    Round,
+    Mod,
+    Rem,
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    Atan2,
+    Pow,
+    Sqrt,
+    Hypot,
+    Log,
+    Exp,
+    Abs,
+    Sign,
 }
 
 impl fmt::Display for CalculationName {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            CalculationName::Calc => f.write_str("calc"),
-            CalculationName::Min => f.write_str("min"),
-            CalculationName::Max => f.write_str("max"),
-            CalculationName::Clamp => f.write_str("clamp"),
-        }
+        f.write_str(self.as_str())
     }
 }
 
 impl CalculationName {
-    pub fn in_min_or_max(self) -> bool {
-        self == CalculationName::Min || self == CalculationName::Max
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "calc" => CalculationName::Calc,
+            "min" => CalculationName::Min,
+            "max" => CalculationName::Max,
+            "clamp" => CalculationName::Clamp,
+            "round" => CalculationName::Round,
+            "mod" => CalculationName::Mod,
+            "rem" => CalculationName::Rem,
+            "sin" => CalculationName::Sin,
+            "cos" => CalculationName::Cos,
+            "tan" => CalculationName::Tan,
+            "asin" => CalculationName::Asin,
+            "acos" => CalculationName::Acos,
+            "atan" => CalculationName::Atan,
+            "atan2" => CalculationName::Atan2,
+            "pow" => CalculationName::Pow,
+            "sqrt" => CalculationName::Sqrt,
+            "hypot" => CalculationName::Hypot,
+            "log" => CalculationName::Log,
+            "exp" => CalculationName::Exp,
+            "abs" => CalculationName::Abs,
+            "sign" => CalculationName::Sign,
+            _ => return None,
+        })
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CalculationName::Calc => "calc",
+            CalculationName::Min => "min",
+            CalculationName::Max => "max",
+            CalculationName::Clamp => "clamp",
+            CalculationName::Round => "round",
+            CalculationName::Mod => "mod",
+            CalculationName::Rem => "rem",
+            CalculationName::Sin => "sin",
+            CalculationName::Cos => "cos",
+            CalculationName::Tan => "tan",
+            CalculationName::Asin => "asin",
+            CalculationName::Acos => "acos",
+            CalculationName::Atan => "atan",
+            CalculationName::Atan2 => "atan2",
+            CalculationName::Pow => "pow",
+            CalculationName::Sqrt => "sqrt",
+            CalculationName::Hypot => "hypot",
+            CalculationName::Log => "log",
+            CalculationName::Exp => "exp",
+            CalculationName::Abs => "abs",
+            CalculationName::Sign => "sign",
+        }
+    }
+
+    /// The maximum number of arguments this calculation accepts, or `None`
+    /// if it accepts any number of arguments
+    pub fn max_args(self) -> Option<usize> {
+        match self {
+            CalculationName::Calc
+            | CalculationName::Sin
+            | CalculationName::Cos
+            | CalculationName::Tan
+            | CalculationName::Asin
+            | CalculationName::Acos
+            | CalculationName::Atan
+            | CalculationName::Sqrt
+            | CalculationName::Exp
+            | CalculationName::Abs
+            | CalculationName::Sign => Some(1),
+            CalculationName::Mod
+            | CalculationName::Rem
+            | CalculationName::Atan2
+            | CalculationName::Pow
+            | CalculationName::Log => Some(2),
+            CalculationName::Round | CalculationName::Clamp => Some(3),
+            CalculationName::Min | CalculationName::Max | CalculationName::Hypot => None,
+        }
+    }
+
+    /// Whether this calculation shares its name with a global Sass function,
+    /// in which case its arguments are combined with Sass's more lenient unit
+    /// rules
+    pub fn in_legacy_sass_function(self) -> bool {
+        matches!(
+            self,
+            CalculationName::Min
+                | CalculationName::Max
+                | CalculationName::Round
+                | CalculationName::Abs
+        )
     }
 }
 
@@ -226,6 +325,414 @@ impl SassCalculation {
         }))
     }
 
+    pub fn round(args: Vec<CalculationArg>, options: &Options, span: Span) -> SassResult<Value> {
+        let mut args = Self::simplify_arguments(args);
+
+        match args.as_slice() {
+            [CalculationArg::Number(n)] => {
+                if !n.num.is_finite() {
+                    return Err(("Infinity or NaN toInt", span).into());
+                }
+
+                return Ok(Self::number(fuzzy_round(n.num.0), n.unit.clone()));
+            }
+            [CalculationArg::Number(number), CalculationArg::Number(step)] => {
+                Self::verify_compatible_numbers(&args, options, span)?;
+                if number.has_compatible_units(&step.unit) {
+                    return Ok(Self::round_with_step("nearest", number, step));
+                }
+            }
+            [strategy, CalculationArg::Number(..)]
+                if Self::rounding_strategy(strategy).is_some() =>
+            {
+                return Err(("If strategy is not null, step is required.", span).into());
+            }
+            [strategy, number, step] => match Self::rounding_strategy(strategy) {
+                Some(strategy) => {
+                    if let (CalculationArg::Number(number), CalculationArg::Number(step)) =
+                        (number, step)
+                    {
+                        Self::verify_compatible_numbers(&args[1..], options, span)?;
+                        if number.has_compatible_units(&step.unit) {
+                            return Ok(Self::round_with_step(strategy, number, step));
+                        }
+                    }
+                }
+                // A `var()` may expand to more than one argument
+                None if matches!(
+                    strategy,
+                    CalculationArg::String(s) | CalculationArg::Interpolation(s)
+                        if s.to_ascii_lowercase().starts_with("var(")
+                ) => {}
+                None => {
+                    return Err((
+                        format!(
+                            "{} must be either nearest, up, down or to-zero.",
+                            serialize_calculation_arg(strategy, options, span)?
+                        ),
+                        span,
+                    )
+                        .into());
+                }
+            },
+            _ => {}
+        }
+
+        let numbers_start = if args.len() == 3 { 1 } else { 0 };
+        Self::verify_compatible_numbers(&args[numbers_start..], options, span)?;
+
+        Ok(Value::Calculation(SassCalculation {
+            name: CalculationName::Round,
+            args: std::mem::take(&mut args),
+        }))
+    }
+
+    fn rounding_strategy(arg: &CalculationArg) -> Option<&'static str> {
+        match arg {
+            CalculationArg::String(s) | CalculationArg::Interpolation(s) => {
+                match s.to_ascii_lowercase().as_str() {
+                    "nearest" => Some("nearest"),
+                    "up" => Some("up"),
+                    "down" => Some("down"),
+                    "to-zero" => Some("to-zero"),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn round_with_step(strategy: &str, number: &SassNumber, step: &SassNumber) -> Value {
+        let unit = number.unit.clone();
+        let num = number.num.0;
+        let step = step.num.convert(&step.unit, &number.unit).0;
+
+        if step == 0.0 {
+            return Self::number(f64::NAN, unit);
+        }
+
+        if !num.is_finite() {
+            return Self::number(if step.is_finite() { num } else { f64::NAN }, unit);
+        }
+
+        if !step.is_finite() {
+            let zero = if num.is_sign_negative() { -0.0 } else { 0.0 };
+            return Self::number(
+                match strategy {
+                    "up" if num > 0.0 => f64::INFINITY,
+                    "down" if num < 0.0 => f64::NEG_INFINITY,
+                    _ => zero,
+                },
+                unit,
+            );
+        }
+
+        let quotient = num / step;
+
+        let rounded = match strategy {
+            "nearest" => fuzzy_round(quotient),
+            "up" if step < 0.0 => quotient.floor(),
+            "up" => quotient.ceil(),
+            "down" if step < 0.0 => quotient.ceil(),
+            "down" => quotient.floor(),
+            _ => quotient.trunc(),
+        };
+
+        Self::number(rounded * step, unit)
+    }
+
+    /// Both `mod()` and `rem()`, which differ only in whether the result takes
+    /// the sign of the divisor or of the dividend
+    pub fn modulo(
+        name: CalculationName,
+        args: Vec<CalculationArg>,
+        options: &Options,
+        span: Span,
+    ) -> SassResult<Value> {
+        let args = Self::simplify_arguments(args);
+        Self::verify_length(&args, 2, span)?;
+
+        if let [CalculationArg::Number(dividend), CalculationArg::Number(modulus)] = args.as_slice()
+        {
+            Self::verify_matching_numbers(dividend, modulus, options, span)?;
+
+            if dividend.has_compatible_units(&modulus.unit) {
+                let a = dividend.num.0;
+                let b = modulus.num.convert(&modulus.unit, &dividend.unit).0;
+
+                let result = if name == CalculationName::Mod
+                    && b.is_infinite()
+                    && a.is_finite()
+                    && a != 0.0
+                    && a.is_sign_negative() != b.is_sign_negative()
+                {
+                    f64::NAN
+                } else {
+                    let mut result = a % b;
+
+                    if name == CalculationName::Mod
+                        && result != 0.0
+                        && result.is_sign_negative() != b.is_sign_negative()
+                        && b.is_finite()
+                    {
+                        result += b;
+                    }
+
+                    result
+                };
+
+                return Ok(Self::number(result, dividend.unit.clone()));
+            }
+        }
+
+        Self::verify_compatible_numbers(&args, options, span)?;
+
+        Ok(Value::Calculation(SassCalculation { name, args }))
+    }
+
+    /// Calculations that take a single argument: trigonometric functions,
+    /// `sqrt()`, `exp()`, `abs()` and `sign()`
+    pub fn single_argument(
+        name: CalculationName,
+        arg: CalculationArg,
+        options: &Options,
+        span: Span,
+    ) -> SassResult<Value> {
+        let arg = Self::simplify(arg);
+
+        let number = match &arg {
+            CalculationArg::Number(number) => number,
+            _ => {
+                Self::verify_compatible_numbers(std::slice::from_ref(&arg), options, span)?;
+                return Ok(Value::Calculation(SassCalculation {
+                    name,
+                    args: vec![arg],
+                }));
+            }
+        };
+
+        let num = number.num.0;
+
+        Ok(match name {
+            CalculationName::Sin | CalculationName::Cos | CalculationName::Tan => {
+                let rad = Self::coerce_to_rad(number, options, span)?;
+                Self::number(
+                    match name {
+                        CalculationName::Sin => rad.sin(),
+                        CalculationName::Cos => rad.cos(),
+                        _ => rad.tan(),
+                    },
+                    Unit::None,
+                )
+            }
+            CalculationName::Asin | CalculationName::Acos | CalculationName::Atan => {
+                Self::assert_no_units(number, options, span)?;
+                let rad = match name {
+                    CalculationName::Asin => num.asin(),
+                    CalculationName::Acos => num.acos(),
+                    _ => num.atan(),
+                };
+                Self::number(rad.to_degrees(), Unit::Deg)
+            }
+            CalculationName::Sqrt => {
+                Self::assert_no_units(number, options, span)?;
+                Self::number(num.sqrt(), Unit::None)
+            }
+            CalculationName::Exp => {
+                Self::assert_no_units(number, options, span)?;
+                Self::number(num.exp(), Unit::None)
+            }
+            CalculationName::Abs => Self::number(num.abs(), number.unit.clone()),
+            CalculationName::Sign if number.unit == Unit::Percent => {
+                Value::Calculation(SassCalculation {
+                    name,
+                    args: vec![arg],
+                })
+            }
+            CalculationName::Sign => Self::number(
+                if num > 0.0 {
+                    1.0
+                } else if num < 0.0 {
+                    -1.0
+                } else {
+                    num
+                },
+                number.unit.clone(),
+            ),
+            _ => unreachable!("{} does not take a single argument", name),
+        })
+    }
+
+    pub fn atan2(args: Vec<CalculationArg>, options: &Options, span: Span) -> SassResult<Value> {
+        let args = Self::simplify_arguments(args);
+        Self::verify_length(&args, 2, span)?;
+
+        if let [CalculationArg::Number(y), CalculationArg::Number(x)] = args.as_slice() {
+            Self::verify_matching_numbers(y, x, options, span)?;
+
+            if y.has_compatible_units(&x.unit) {
+                let x = x.num.convert(&x.unit, &y.unit).0;
+                return Ok(Self::number(y.num.0.atan2(x).to_degrees(), Unit::Deg));
+            }
+        }
+
+        Self::verify_compatible_numbers(&args, options, span)?;
+
+        Ok(Value::Calculation(SassCalculation {
+            name: CalculationName::Atan2,
+            args,
+        }))
+    }
+
+    pub fn pow(args: Vec<CalculationArg>, options: &Options, span: Span) -> SassResult<Value> {
+        let args = Self::simplify_arguments(args);
+        Self::verify_length(&args, 2, span)?;
+
+        if let [CalculationArg::Number(base), CalculationArg::Number(exponent)] = args.as_slice() {
+            Self::assert_no_units(base, options, span)?;
+            Self::assert_no_units(exponent, options, span)?;
+
+            return Ok(Self::number(base.num.0.powf(exponent.num.0), Unit::None));
+        }
+
+        Self::verify_compatible_numbers(&args, options, span)?;
+
+        Ok(Value::Calculation(SassCalculation {
+            name: CalculationName::Pow,
+            args,
+        }))
+    }
+
+    pub fn log(args: Vec<CalculationArg>, options: &Options, span: Span) -> SassResult<Value> {
+        let args = Self::simplify_arguments(args);
+
+        match args.as_slice() {
+            [CalculationArg::Number(number)] => {
+                Self::assert_no_units(number, options, span)?;
+                return Ok(Self::number(number.num.0.ln(), Unit::None));
+            }
+            [CalculationArg::Number(number), CalculationArg::Number(base)] => {
+                Self::assert_no_units(number, options, span)?;
+                Self::assert_no_units(base, options, span)?;
+
+                let base = base.num.0;
+                let result = if base == 1.0 {
+                    f64::NAN
+                } else {
+                    number.num.0.ln() / base.ln()
+                };
+
+                return Ok(Self::number(result, Unit::None));
+            }
+            _ => {}
+        }
+
+        Self::verify_compatible_numbers(&args, options, span)?;
+
+        Ok(Value::Calculation(SassCalculation {
+            name: CalculationName::Log,
+            args,
+        }))
+    }
+
+    pub fn hypot(args: Vec<CalculationArg>, options: &Options, span: Span) -> SassResult<Value> {
+        let args = Self::simplify_arguments(args);
+        debug_assert!(!args.is_empty(), "hypot() must have at least one argument.");
+
+        Self::verify_compatible_numbers(&args, options, span)?;
+
+        let first = match &args[0] {
+            CalculationArg::Number(n) => n.clone(),
+            _ => {
+                return Ok(Value::Calculation(SassCalculation {
+                    name: CalculationName::Hypot,
+                    args,
+                }))
+            }
+        };
+
+        let mut sum = 0.0;
+
+        for arg in &args {
+            match arg {
+                CalculationArg::Number(n) if first.has_compatible_units(&n.unit) => {
+                    sum += n.num.convert(&n.unit, &first.unit).0.powi(2);
+                }
+                _ => {
+                    return Ok(Value::Calculation(SassCalculation {
+                        name: CalculationName::Hypot,
+                        args,
+                    }))
+                }
+            }
+        }
+
+        Ok(Self::number(sum.sqrt(), first.unit))
+    }
+
+    fn number(num: f64, unit: Unit) -> Value {
+        Value::Dimension(SassNumber {
+            num: Number(num),
+            unit,
+            as_slash: None,
+        })
+    }
+
+    fn coerce_to_rad(number: &SassNumber, options: &Options, span: Span) -> SassResult<f64> {
+        match number.unit {
+            Unit::None => Ok(number.num.0),
+            Unit::Rad | Unit::Deg | Unit::Grad | Unit::Turn => {
+                Ok(number.num.convert(&number.unit, &Unit::Rad).0)
+            }
+            _ => Err((
+                format!(
+                    "Expected {} to have an angle unit (deg, grad, rad, turn).",
+                    inspect_number(number, options, span)?
+                ),
+                span,
+            )
+                .into()),
+        }
+    }
+
+    fn assert_no_units(number: &SassNumber, options: &Options, span: Span) -> SassResult<()> {
+        if number.unit == Unit::None {
+            return Ok(());
+        }
+
+        Err((
+            format!(
+                "Expected {} to have no units.",
+                inspect_number(number, options, span)?
+            ),
+            span,
+        )
+            .into())
+    }
+
+    /// Unlike [`SassCalculation::verify_compatible_numbers`], this rejects a
+    /// unitless number paired with a number that has units
+    fn verify_matching_numbers(
+        number1: &SassNumber,
+        number2: &SassNumber,
+        options: &Options,
+        span: Span,
+    ) -> SassResult<()> {
+        if (number1.unit == Unit::None) == (number2.unit == Unit::None) {
+            return Ok(());
+        }
+
+        Err((
+            format!(
+                "{} and {} are incompatible.",
+                inspect_number(number1, options, span)?,
+                inspect_number(number2, options, span)?
+            ),
+            span,
+        )
+            .into())
+    }
+
     fn verify_length(args: &[CalculationArg], len: usize, span: Span) -> SassResult<()> {
         if args.len() == len {
             return Ok(());
@@ -315,10 +822,10 @@ impl SassCalculation {
     }
 
     pub fn operate_internal(
-        mut op: BinaryOp,
+        op: BinaryOp,
         left: CalculationArg,
         right: CalculationArg,
-        in_min_or_max: bool,
+        in_legacy_sass_function: bool,
         simplify: bool,
         options: &Options,
         span: Span,
@@ -332,70 +839,243 @@ impl SassCalculation {
         }
 
         let left = Self::simplify(left);
-        let mut right = Self::simplify(right);
+        let right = Self::simplify(right);
 
         if op == BinaryOp::Plus || op == BinaryOp::Minus {
-            match (&left, &right) {
-                (CalculationArg::Number(left), CalculationArg::Number(right))
-                    if if in_min_or_max {
-                        left.is_comparable_to(right)
-                    } else {
-                        left.has_compatible_units(&right.unit)
-                    } =>
-                {
-                    if op == BinaryOp::Plus {
-                        return Ok(CalculationArg::Number(left.clone() + right.clone()));
-                    } else {
-                        return Ok(CalculationArg::Number(left.clone() - right.clone()));
-                    }
-                }
-                _ => {}
+            Self::simplify_sum(op, left, right, in_legacy_sass_function, options, span)
+        } else {
+            Ok(Self::simplify_product(op, left, right))
+        }
+    }
+
+    /// Simplifies a sum node. As in dart-sass, only the two operands of this
+    /// node are combined, so compatible terms that aren't adjacent, such as
+    /// those in `calc(1px + 1% + 1px)`, are left as-is
+    fn simplify_sum(
+        mut op: BinaryOp,
+        left: CalculationArg,
+        right: CalculationArg,
+        in_legacy_sass_function: bool,
+        options: &Options,
+        span: Span,
+    ) -> SassResult<CalculationArg> {
+        if let (CalculationArg::Number(left), CalculationArg::Number(right)) = (&left, &right) {
+            let compatible = if in_legacy_sass_function {
+                left.is_comparable_to(right)
+            } else {
+                left.has_compatible_units(&right.unit)
+            };
+
+            if compatible {
+                return Ok(CalculationArg::Number(if op == BinaryOp::Plus {
+                    left.clone() + right.clone()
+                } else {
+                    left.clone() - right.clone()
+                }));
             }
+        }
 
-            Self::verify_compatible_numbers(&[left.clone(), right.clone()], options, span)?;
+        Self::verify_compatible_numbers(&[left.clone(), right.clone()], options, span)?;
 
-            if let CalculationArg::Number(mut n) = right {
-                if n.num.is_negative() {
-                    n.num.0 *= -1.0;
-                    op = if op == BinaryOp::Plus {
-                        BinaryOp::Minus
-                    } else {
-                        BinaryOp::Plus
-                    }
+        if let (CalculationArg::Number(number1), CalculationArg::Number(number2)) = (&left, &right)
+        {
+            if !in_legacy_sass_function {
+                Self::verify_matching_numbers(number1, number2, options, span)?;
+            }
+        }
+
+        let right = match right {
+            CalculationArg::Number(mut number) if number.num.is_negative() => {
+                number.num = -number.num;
+                op = if op == BinaryOp::Plus {
+                    BinaryOp::Minus
                 } else {
+                    BinaryOp::Plus
+                };
+                CalculationArg::Number(number)
+            }
+            right => right,
+        };
+
+        Ok(CalculationArg::Operation {
+            lhs: Box::new(left),
+            op,
+            rhs: Box::new(right),
+        })
+    }
+
+    /// Flattens a sum of numbers into `terms`, applying the sign of each
+    /// subtracted number
+    fn collect_sum_terms(arg: CalculationArg, negate: bool, terms: &mut Vec<SassNumber>) {
+        match arg {
+            CalculationArg::Operation { lhs, op, rhs } => {
+                Self::collect_sum_terms(*lhs, negate, terms);
+                Self::collect_sum_terms(*rhs, negate != (op == BinaryOp::Minus), terms);
+            }
+            CalculationArg::Number(mut number) => {
+                if negate {
+                    number.num = -number.num;
                 }
-                right = CalculationArg::Number(n);
+                terms.push(number);
             }
+            _ => unreachable!("only sums of numbers are collected"),
+        }
+    }
 
-            return Ok(CalculationArg::Operation {
-                lhs: Box::new(left),
+    fn is_number_sum(arg: &CalculationArg) -> bool {
+        match arg {
+            CalculationArg::Operation { lhs, op, rhs }
+                if *op == BinaryOp::Plus || *op == BinaryOp::Minus =>
+            {
+                [lhs, rhs].iter().all(|term| {
+                    matches!(&***term, CalculationArg::Number(..)) || Self::is_number_sum(term)
+                })
+            }
+            _ => false,
+        }
+    }
+
+    fn build_sum(terms: Vec<SassNumber>) -> CalculationArg {
+        let mut terms = terms.into_iter();
+
+        let mut sum = CalculationArg::Number(terms.next().expect("sums have at least one term"));
+
+        for mut number in terms {
+            let op = if number.num.is_negative() {
+                number.num = -number.num;
+                BinaryOp::Minus
+            } else {
+                BinaryOp::Plus
+            };
+
+            sum = CalculationArg::Operation {
+                lhs: Box::new(sum),
                 op,
-                rhs: Box::new(right),
-            });
+                rhs: Box::new(CalculationArg::Number(number)),
+            };
         }
 
+        sum
+    }
+
+    /// Simplifies a product node as described in CSS Values 4: numbers are
+    /// multiplied together, and a sum of numbers multiplied or divided by a
+    /// unitless number has that factor distributed across its terms
+    fn simplify_product(
+        op: BinaryOp,
+        left: CalculationArg,
+        right: CalculationArg,
+    ) -> CalculationArg {
         match (left, right) {
             (CalculationArg::Number(num1), CalculationArg::Number(num2)) => {
                 if op == BinaryOp::Mul {
-                    Ok(CalculationArg::Number(num1 * num2))
+                    CalculationArg::Number(num1 * num2)
                 } else {
-                    Ok(CalculationArg::Number(num1 / num2))
+                    CalculationArg::Number(num1 / num2)
                 }
             }
-            (left, right) => Ok(CalculationArg::Operation {
+            (CalculationArg::Number(factor), sum)
+                if op == BinaryOp::Mul
+                    && factor.unit == Unit::None
+                    && Self::is_number_sum(&sum) =>
+            {
+                Self::scale_sum(sum, factor.num.0)
+            }
+            (sum, CalculationArg::Number(factor))
+                if factor.unit == Unit::None
+                    && !factor.num.is_zero()
+                    && Self::is_number_sum(&sum) =>
+            {
+                Self::scale_sum(
+                    sum,
+                    if op == BinaryOp::Mul {
+                        factor.num.0
+                    } else {
+                        1.0 / factor.num.0
+                    },
+                )
+            }
+            (left, right) if op == BinaryOp::Mul => {
+                let mut factors = Vec::new();
+                Self::collect_product_factors(left, &mut factors);
+                Self::collect_product_factors(right, &mut factors);
+                Self::build_product(factors)
+            }
+            (left, right) => CalculationArg::Operation {
                 lhs: Box::new(left),
                 op,
                 rhs: Box::new(right),
-            }),
+            },
+        }
+    }
+
+    fn scale_sum(sum: CalculationArg, factor: f64) -> CalculationArg {
+        let mut terms = Vec::new();
+        Self::collect_sum_terms(sum, false, &mut terms);
+
+        for number in &mut terms {
+            number.num = Number(number.num.0 * factor);
+        }
+
+        Self::build_sum(terms)
+    }
+
+    fn collect_product_factors(arg: CalculationArg, factors: &mut Vec<CalculationArg>) {
+        match arg {
+            CalculationArg::Operation {
+                lhs,
+                op: BinaryOp::Mul,
+                rhs,
+            } => {
+                Self::collect_product_factors(*lhs, factors);
+                Self::collect_product_factors(*rhs, factors);
+            }
+            arg => factors.push(arg),
         }
+    }
+
+    /// Multiplies together the numeric factors of a product, so long as doing
+    /// so won't create a number with complex units
+    fn build_product(mut factors: Vec<CalculationArg>) -> CalculationArg {
+        let is_number = |factor: &CalculationArg| matches!(factor, CalculationArg::Number(..));
 
-        //   _verifyCompatibleNumbers([left, right]);
+        let unitful_numbers = factors
+            .iter()
+            .filter(|factor| matches!(factor, CalculationArg::Number(n) if n.unit != Unit::None))
+            .count();
+
+        if factors.iter().filter(|factor| is_number(factor)).count() > 1 && unitful_numbers <= 1 {
+            let first = factors.iter().position(is_number).unwrap();
+            let mut product = match factors[first].clone() {
+                CalculationArg::Number(n) => n,
+                _ => unreachable!(),
+            };
+
+            let mut i = first + 1;
+            while i < factors.len() {
+                if let CalculationArg::Number(n) = &factors[i] {
+                    product = product * n.clone();
+                    factors.remove(i);
+                } else {
+                    i += 1;
+                }
+            }
+
+            factors[first] = CalculationArg::Number(product);
+        }
+
+        let mut factors = factors.into_iter();
+        let mut product = factors.next().expect("products have at least one factor");
+
+        for factor in factors {
+            product = CalculationArg::Operation {
+                lhs: Box::new(product),
+                op: BinaryOp::Mul,
+                rhs: Box::new(factor),
+            };
+        }
 
-        // Ok(CalculationArg::Operation {
-        //     lhs: Box::new(left),
-        //     op,
-        //     rhs: Box::new(right),
-        // })
+        product
     }
 
     fn simplify(arg: CalculationArg) -> CalculationArg {
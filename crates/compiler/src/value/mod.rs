@@ -3,6 +3,7 @@ use std::{cmp::Ordering, sync::Arc};
 use codemap::{Span, Spanned};
 
 use crate::{
+    ast::Mixin,
     color::Color,
     common::{BinaryOp, Brackets, ListSeparator, QuoteKind},
     error::SassResult,
@@ -41,6 +42,8 @@ pub(crate) enum Value {
     ArgList(ArgList),
     /// Returned by `get-function()`
     FunctionRef(Box<SassFunction>),
+    /// Returned by `meta.get-mixin()`
+    MixinRef(Box<Mixin>),
     Calculation(SassCalculation),
 }
 
@@ -84,6 +87,13 @@ impl PartialEq for Value {
                     false
                 }
             }
+            Value::MixinRef(mixin1) => {
+                if let Value::MixinRef(mixin2) = other {
+                    mixin1 == mixin2
+                } else {
+                    false
+                }
+            }
             Value::Map(map1) => {
                 if let Value::Map(map2) = other {
                     map1 == map2
@@ -251,6 +261,7 @@ impl Value {
             Value::Dimension(..) => "number",
             Value::List(..) => "list",
             Value::FunctionRef(..) => "function",
+            Value::MixinRef(..) => "mixin",
             Value::ArgList(..) => "arglist",
             Value::True | Value::False => "bool",
             Value::Null => "null",
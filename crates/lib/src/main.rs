@@ -1,12 +1,15 @@
 use std::{
-    fs::OpenOptions,
+    env,
+    fs::{self, OpenOptions},
     io::{stdin, stdout, Read, Write},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 use clap::{arg_enum, App, AppSettings, Arg};
 
-use grass::{from_path, from_string, Options, OutputStyle};
+use grass::{
+    from_path, from_string, Comments, Config, Indent, LineEnding, Options, OutputStyle, PkgImporter,
+};
 
 // TODO remove this
 arg_enum! {
@@ -14,6 +17,8 @@ arg_enum! {
     pub enum Style {
         Expanded,
         Compressed,
+        Nested,
+        Compact,
     }
 }
 
@@ -51,13 +56,32 @@ fn main() -> std::io::Result<()> {
                 .takes_value(true)
                 .number_of_values(1)
         )
+        .arg(
+            Arg::with_name("PKG_IMPORTER")
+                .long("pkg-importer")
+                .help("Built-in importer to use for pkg: URLs.")
+                .possible_values(&["node"])
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("CONFIG")
+                .long("config")
+                .help("A grass.toml file to read settings from. Defaults to ./grass.toml if it exists.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("NO_CONFIG")
+                .long("no-config")
+                .help("Don't read settings from a grass.toml file.")
+                .conflicts_with("CONFIG")
+        )
         .arg(
             Arg::with_name("STYLE")
                 // this is required for compatibility with ruby sass
                 .short("t") // FIXME change this to short_alias later
                 .short("s")
                 .long("style")
-                .help("Minified or expanded output")
+                .help("Output style")
                 .default_value("expanded")
                 .case_insensitive(true)
                 .possible_values(&Style::variants())
@@ -160,9 +184,61 @@ fn main() -> std::io::Result<()> {
                 .long("quiet")
                 .help("Don't print warnings."),
         )
+        .arg(
+            Arg::with_name("OPTIMIZE")
+                .long("optimize")
+                .help("Remove redundant CSS, such as overridden declarations and duplicate rules."),
+        )
+        .arg(
+            Arg::with_name("COMMENTS")
+                .long("comments")
+                .takes_value(true)
+                .possible_values(&["keep", "keep-important", "strip"])
+                .help("Which loud comments to write. By default, compressed output only keeps /*! comments."),
+        )
+        .arg(
+            Arg::with_name("INDENT")
+                .long("indent")
+                .takes_value(true)
+                .value_name("INDENT")
+                .validator(|indent| {
+                    if indent == "tab" {
+                        return Ok(());
+                    }
+
+                    indent.parse::<usize>().map(|_| ()).map_err(|e| e.to_string())
+                })
+                .help("Indent with this many spaces, or with tabs if set to \"tab\"."),
+        )
+        .arg(
+            Arg::with_name("LINE_ENDING")
+                .long("line-ending")
+                .takes_value(true)
+                .possible_values(&["lf", "crlf"])
+                .help("The line break to write between lines."),
+        )
+        .arg(
+            Arg::with_name("TRAILING_NEWLINE")
+                .long("trailing-newline")
+                .overrides_with("NO_TRAILING_NEWLINE")
+                .help("End the output with a line break, even if it's compressed."),
+        )
+        .arg(
+            Arg::with_name("NO_TRAILING_NEWLINE")
+                .long("no-trailing-newline")
+                .overrides_with("TRAILING_NEWLINE")
+                .help("Don't end the output with a line break."),
+        )
+        .arg(
+            Arg::with_name("SEED")
+                .long("seed")
+                .takes_value(true)
+                .value_name("SEED")
+                .validator(|seed| seed.parse::<u64>().map(|_| ()).map_err(|e| e.to_string()))
+                .help("Seed random() and unique-id() so that output is reproducible."),
+        )
         .arg(
             Arg::with_name("INPUT")
-                .required_unless("STDIN")
                 .help("SCSS files"),
         )
         .arg(
@@ -179,23 +255,127 @@ fn main() -> std::io::Result<()> {
         )
         .get_matches();
 
+    let config = if matches.is_present("NO_CONFIG") {
+        None
+    } else if let Some(path) = matches.value_of("CONFIG") {
+        Some(Config::from_path(path))
+    } else if Path::new("grass.toml").is_file() {
+        Some(Config::from_path("grass.toml"))
+    } else {
+        None
+    }
+    .transpose()
+    .unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1)
+    });
+
     let load_paths = matches
         .values_of("LOAD_PATH")
-        .map_or_else(Vec::new, |vals| vals.map(Path::new).collect());
+        .map_or_else(Vec::new, |vals| vals.map(PathBuf::from).collect());
 
-    let style = match &matches.value_of("STYLE").unwrap().to_lowercase() as &str {
-        "expanded" => OutputStyle::Expanded,
-        "compressed" => OutputStyle::Compressed,
-        _ => unreachable!(),
-    };
+    // paths from `SASS_PATH` are searched after those passed on the commandline
+    // or listed in the config file, matching the behavior of dart-sass
+    let sass_path =
+        env::var_os("SASS_PATH").map_or_else(Vec::new, |paths| env::split_paths(&paths).collect());
 
-    let options = &Options::default()
-        .load_paths(&load_paths)
-        .style(style)
-        .quiet(matches.is_present("QUIET"))
+    let mut options = Options::default().load_paths(&load_paths);
+
+    if let Some(config) = &config {
+        options = config.apply(options);
+    }
+
+    options = options
+        .load_paths(&sass_path)
         .unicode_error_messages(!matches.is_present("NO_UNICODE"))
         .allows_charset(!matches.is_present("NO_CHARSET"));
 
+    // explicitly passed flags take precedence over the config file
+    if matches.occurrences_of("STYLE") > 0 {
+        options = options.style(
+            match &matches.value_of("STYLE").unwrap().to_lowercase() as &str {
+                "expanded" => OutputStyle::Expanded,
+                "compressed" => OutputStyle::Compressed,
+                "nested" => OutputStyle::Nested,
+                "compact" => OutputStyle::Compact,
+                _ => unreachable!(),
+            },
+        );
+    }
+
+    if let Some("node") = matches.value_of("PKG_IMPORTER") {
+        options = options.pkg_importer(PkgImporter::Node);
+    }
+
+    if matches.is_present("QUIET") {
+        options = options.quiet(true);
+    }
+
+    if matches.is_present("OPTIMIZE") {
+        options = options.optimize(true);
+    }
+
+    if let Some(comments) = matches.value_of("COMMENTS") {
+        options = options.comments(match comments {
+            "keep" => Comments::Keep,
+            "keep-important" => Comments::KeepImportant,
+            "strip" => Comments::Strip,
+            _ => unreachable!(),
+        });
+    }
+
+    match matches.value_of("INDENT") {
+        Some("tab") => options = options.indent(Indent::Tabs),
+        Some(width) => options = options.indent(Indent::Spaces(width.parse().unwrap())),
+        None => {}
+    }
+
+    match matches.value_of("LINE_ENDING") {
+        Some("lf") => options = options.line_ending(LineEnding::Lf),
+        Some("crlf") => options = options.line_ending(LineEnding::Crlf),
+        Some(..) => unreachable!(),
+        None => {}
+    }
+
+    if matches.is_present("TRAILING_NEWLINE") {
+        options = options.trailing_newline(true);
+    } else if matches.is_present("NO_TRAILING_NEWLINE") {
+        options = options.trailing_newline(false);
+    }
+
+    if let Some(seed) = matches.value_of("SEED") {
+        options = options.random_seed(seed.parse().unwrap());
+    }
+
+    let options = &options;
+
+    if matches.value_of("INPUT").is_none() && !matches.is_present("STDIN") {
+        let entrypoints = config.map_or_else(Vec::new, |config| config.entrypoints);
+
+        if entrypoints.is_empty() {
+            clap::Error::with_description(
+                "The following required arguments were not provided:\n    <INPUT>",
+                clap::ErrorKind::MissingRequiredArgument,
+            )
+            .exit();
+        }
+
+        for (input, output) in entrypoints {
+            let css = from_path(&input, options).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                std::process::exit(1)
+            });
+
+            if let Some(parent) = output.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            fs::write(output, css)?;
+        }
+
+        return Ok(());
+    }
+
     let (mut stdout_write, mut file_write);
     let buf_out: &mut dyn Write = if let Some(path) = matches.value_of("OUTPUT") {
         file_write = OpenOptions::new()
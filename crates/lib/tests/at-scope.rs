@@ -0,0 +1,111 @@
+#[macro_use]
+mod macros;
+
+test!(
+    start_only,
+    "@scope (.card) {
+        img {
+            a: b;
+        }
+    }",
+    "@scope (.card) {\n  img {\n    a: b;\n  }\n}\n"
+);
+test!(
+    start_and_end,
+    "@scope (.card)   to   ( .content ) {
+        img {
+            a: b;
+        }
+    }",
+    "@scope (.card) to (.content) {\n  img {\n    a: b;\n  }\n}\n"
+);
+test!(
+    end_only,
+    "@scope to (.content) {
+        img {
+            a: b;
+        }
+    }",
+    "@scope to (.content) {\n  img {\n    a: b;\n  }\n}\n"
+);
+test!(
+    no_prelude,
+    "@scope {
+        img {
+            a: b;
+        }
+    }",
+    "@scope {\n  img {\n    a: b;\n  }\n}\n"
+);
+test!(
+    selector_list_in_prelude,
+    "@scope (.a, .b > .c) {
+        img {
+            a: b;
+        }
+    }",
+    "@scope (.a, .b > .c) {\n  img {\n    a: b;\n  }\n}\n"
+);
+test!(
+    interpolated_prelude,
+    "$root: card;
+    @scope (.#{$root}) {
+        img {
+            a: b;
+        }
+    }",
+    "@scope (.card) {\n  img {\n    a: b;\n  }\n}\n"
+);
+test!(
+    declarations_allowed_at_top_level,
+    "@scope (.card) {
+        a: b;
+    }",
+    "@scope (.card) {\n  a: b;\n}\n"
+);
+test!(
+    bubbles_out_of_style_rule,
+    ".a {
+        @scope (.b) {
+            c: d;
+
+            img {
+                e: f;
+            }
+        }
+    }",
+    "@scope (.b) {\n  .a {\n    c: d;\n  }\n  .a img {\n    e: f;\n  }\n}\n"
+);
+test!(
+    compressed,
+    "@scope (.card) to (.content) {
+        img {
+            a: b;
+        }
+    }",
+    "@scope (.card) to (.content){img{a:b}}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(empty_body_is_removed, "@scope (.card) {}", "");
+test!(
+    extend_inside,
+    "@scope (.card) {
+        .a {
+            b: c;
+        }
+
+        .d {
+            @extend .a;
+        }
+    }",
+    "@scope (.card) {\n  .a, .d {\n    b: c;\n  }\n}\n"
+);
+error!(
+    unexpected_token_after_start,
+    "@scope (.a) foo {a {b: c}}", "Error: expected no more input."
+);
+error!(
+    invalid_selector,
+    "@scope (a[) {a {b: c}}", "Error: expected \"]\"."
+);
+error!(no_body, "@scope (.a);", "Error: expected \"{\".");
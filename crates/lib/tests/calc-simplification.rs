@@ -0,0 +1,165 @@
+#[macro_use]
+mod macros;
+
+test!(
+    sum_same_unit,
+    "a {\n  color: calc(1px + 2px);\n}\n",
+    "a {\n  color: 3px;\n}\n"
+);
+test!(
+    sum_convertible_units,
+    "a {\n  color: calc(1in + 10px);\n}\n",
+    "a {\n  color: 1.1041666667in;\n}\n"
+);
+test!(
+    sum_unitless,
+    "a {\n  color: calc(1 + 2 - 4);\n}\n",
+    "a {\n  color: -1;\n}\n"
+);
+test!(
+    sum_non_comparable_units,
+    "a {\n  color: calc(1px + 1%);\n}\n",
+    "a {\n  color: calc(1px + 1%);\n}\n"
+);
+test!(
+    sum_combines_adjacent_terms,
+    "a {\n  color: calc(1px + 2px + 1%);\n}\n",
+    "a {\n  color: calc(3px + 1%);\n}\n"
+);
+test!(
+    sum_does_not_combine_non_adjacent_terms,
+    "a {\n  color: calc(1px + 1% + 2px);\n}\n",
+    "a {\n  color: calc(1px + 1% + 2px);\n}\n"
+);
+test!(
+    sum_keeps_zero_terms,
+    "a {\n  color: calc(1px - 1px + 1%);\n}\n",
+    "a {\n  color: calc(0px + 1%);\n}\n"
+);
+test!(
+    sum_utility_framework_output,
+    "a {\n  color: calc(100% - (2 * 1rem) + 0px);\n}\n",
+    "a {\n  color: calc(100% - 2rem + 0px);\n}\n"
+);
+test!(
+    sum_around_var,
+    "a {\n  color: calc(var(--a) + 1px + var(--b) + 2px);\n}\n",
+    "a {\n  color: calc(var(--a) + 1px + var(--b) + 2px);\n}\n"
+);
+test!(
+    interpolation_is_not_simplified,
+    "a {\n  color: calc(1px + #{\"1rem\"} + 2px);\n}\n",
+    "a {\n  color: calc(1px + 1rem + 2px);\n}\n"
+);
+test!(
+    nested_calc_is_unwrapped,
+    "a {\n  color: calc(1px + calc(2% + 3px));\n}\n",
+    "a {\n  color: calc(1px + 2% + 3px);\n}\n"
+);
+test!(
+    nested_calc_is_simplified,
+    "a {\n  color: calc(calc(1px + 2px) * 2);\n}\n",
+    "a {\n  color: 6px;\n}\n"
+);
+test!(
+    nested_parens_are_not_combined,
+    "a {\n  color: calc(1px + (2px + 1rem));\n}\n",
+    "a {\n  color: calc(1px + 2px + 1rem);\n}\n"
+);
+test!(
+    subtracted_sum_is_preserved,
+    "a {\n  color: calc(1px - (2% - 3px));\n}\n",
+    "a {\n  color: calc(1px - (2% - 3px));\n}\n"
+);
+test!(
+    subtracted_sum_containing_var_is_preserved,
+    "a {\n  color: calc(1px - (var(--x) + 2px));\n}\n",
+    "a {\n  color: calc(1px - (var(--x) + 2px));\n}\n"
+);
+test!(
+    subtract_negative_number,
+    "a {\n  color: calc(1px - -2rem);\n}\n",
+    "a {\n  color: calc(1px + 2rem);\n}\n"
+);
+test!(
+    add_negative_number,
+    "a {\n  color: calc(1rem + -1px);\n}\n",
+    "a {\n  color: calc(1rem - 1px);\n}\n"
+);
+test!(
+    leading_negative_number,
+    "a {\n  color: calc(-1px + 1rem);\n}\n",
+    "a {\n  color: calc(-1px + 1rem);\n}\n"
+);
+test!(
+    product_of_numbers,
+    "a {\n  color: calc(2px * 3);\n}\n",
+    "a {\n  color: 6px;\n}\n"
+);
+test!(
+    product_combines_numbers_around_var,
+    "a {\n  color: calc(2 * var(--x) * 3);\n}\n",
+    "a {\n  color: calc(6 * var(--x));\n}\n"
+);
+test!(
+    product_distributes_over_sum,
+    "a {\n  color: calc(-1 * (1px + 1rem));\n}\n",
+    "a {\n  color: calc(-1px - 1rem);\n}\n"
+);
+test!(
+    product_distributes_over_sum_on_right,
+    "a {\n  color: calc((1px + 2%) * 2);\n}\n",
+    "a {\n  color: calc(2px + 4%);\n}\n"
+);
+test!(
+    quotient_distributes_over_sum,
+    "a {\n  color: calc((1px + 1rem) / 2);\n}\n",
+    "a {\n  color: calc(0.5px + 0.5rem);\n}\n"
+);
+test!(
+    quotient_of_sum_is_preserved,
+    "a {\n  color: calc(2 / (1px + 2%));\n}\n",
+    "a {\n  color: calc(2 / (1px + 2%));\n}\n"
+);
+test!(
+    product_does_not_distribute_over_var,
+    "a {\n  color: calc(2 * (1px + var(--x)));\n}\n",
+    "a {\n  color: calc(2 * (1px + var(--x)));\n}\n"
+);
+test!(
+    distributed_product_then_sum,
+    "a {\n  color: calc(0.5 * (2% + 4px) - 2px);\n}\n",
+    "a {\n  color: calc(1% + 2px - 2px);\n}\n"
+);
+test!(
+    min_allows_unitless_with_units,
+    "a {\n  color: min(1px + 2, 3%);\n}\n",
+    "a {\n  color: min(3px, 3%);\n}\n"
+);
+test!(
+    simplified_in_min,
+    "a {\n  color: min(1px + 1px + 1rem, 10%);\n}\n",
+    "a {\n  color: min(2px + 1rem, 10%);\n}\n"
+);
+test!(
+    unsimplified_in_supports,
+    "@supports (width: calc(1px + 2px + 1rem)) {\n  a {\n    color: red;\n  }\n}\n",
+    "@supports (width: calc(1px + 2px + 1rem)) {\n  a {\n    color: red;\n  }\n}\n"
+);
+error!(
+    sum_incompatible_units,
+    "a {\n  color: calc(1px + 1s);\n}\n", "Error: 1px and 1s are incompatible."
+);
+error!(
+    sum_unitless_and_unit,
+    "a {\n  color: calc(1px + 1);\n}\n", "Error: 1px and 1 are incompatible."
+);
+error!(
+    sum_incompatible_units_before_var,
+    "a {\n  color: calc(1s + 1px + var(--x));\n}\n", "Error: 1s and 1px are incompatible."
+);
+error!(
+    sum_complex_units,
+    "a {\n  color: calc(1px * 1px + 1rem);\n}\n",
+    "Error: Number 1px*px isn't compatible with CSS calculations."
+);
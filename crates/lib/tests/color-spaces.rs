@@ -0,0 +1,274 @@
+#[macro_use]
+mod macros;
+
+test!(
+    lab_function,
+    "a {\n  color: lab(50% 20 30);\n}\n",
+    "a {\n  color: lab(50% 20 30);\n}\n"
+);
+test!(
+    lab_function_with_alpha,
+    "a {\n  color: lab(50% 20 30 / 0.5);\n}\n",
+    "a {\n  color: lab(50% 20 30 / 0.5);\n}\n"
+);
+test!(
+    lab_function_with_unitless_lightness,
+    "a {\n  color: lab(50 20 30);\n}\n",
+    "a {\n  color: lab(50% 20 30);\n}\n"
+);
+test!(
+    lab_lightness_is_clamped,
+    "a {\n  color: lab(150% 20 30);\n}\n",
+    "a {\n  color: lab(100% 20 30);\n}\n"
+);
+test!(
+    lch_function,
+    "a {\n  color: lch(50% 30 120deg);\n}\n",
+    "a {\n  color: lch(50% 30 120deg);\n}\n"
+);
+test!(
+    lch_hue_is_normalized,
+    "a {\n  color: lch(50% 30 -0.25turn);\n}\n",
+    "a {\n  color: lch(50% 30 270deg);\n}\n"
+);
+test!(
+    oklab_function,
+    "a {\n  color: oklab(60% 0.1 -0.1);\n}\n",
+    "a {\n  color: oklab(60% 0.1 -0.1);\n}\n"
+);
+test!(
+    oklab_percent_channels,
+    "a {\n  color: oklab(0.6 25% -50%);\n}\n",
+    "a {\n  color: oklab(60% 0.1 -0.2);\n}\n"
+);
+test!(
+    oklch_function,
+    "a {\n  color: oklch(60% 0.1 120);\n}\n",
+    "a {\n  color: oklch(60% 0.1 120deg);\n}\n"
+);
+test!(
+    missing_channel,
+    "a {\n  color: oklch(60% none 120);\n}\n",
+    "a {\n  color: oklch(60% none 120deg);\n}\n"
+);
+test!(
+    color_function_display_p3,
+    "a {\n  color: color(display-p3 1 0 0);\n}\n",
+    "a {\n  color: color(display-p3 1 0 0);\n}\n"
+);
+test!(
+    color_function_with_alpha,
+    "a {\n  color: color(display-p3 1 50% 0 / 50%);\n}\n",
+    "a {\n  color: color(display-p3 1 0.5 0 / 0.5);\n}\n"
+);
+test!(
+    color_function_xyz_alias,
+    "a {\n  color: color(xyz-d65 0.1 0.2 0.3);\n}\n",
+    "a {\n  color: color(xyz 0.1 0.2 0.3);\n}\n"
+);
+test!(
+    lab_with_var_is_plain_css,
+    "a {\n  color: lab(var(--l) 20 30);\n}\n",
+    "a {\n  color: lab(var(--l) 20 30);\n}\n"
+);
+test!(
+    color_function_with_var_is_plain_css,
+    "a {\n  color: color(display-p3 var(--r) 0 0);\n}\n",
+    "a {\n  color: color(display-p3 var(--r) 0 0);\n}\n"
+);
+test!(
+    compressed_lab_with_alpha,
+    "a {\n  color: lab(50% 20 30 / 0.5);\n}\n",
+    "a{color:lab(50% 20 30/.5)}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    space_of_legacy_and_non_legacy_colors,
+    "@use \"sass:color\";\na {\n  color: color.space(red), color.space(hsl(0, 50%, 50%)), color.space(lab(50% 20 30)), color.space(color(srgb 1 0 0));\n}\n",
+    "a {\n  color: rgb, hsl, lab, srgb;\n}\n"
+);
+test!(
+    to_space_lab,
+    "@use \"sass:color\";\na {\n  color: color.to-space(red, lab);\n}\n",
+    "a {\n  color: lab(54.290542947% 80.8049203346 69.890988259);\n}\n"
+);
+test!(
+    to_space_oklch,
+    "@use \"sass:color\";\na {\n  color: color.to-space(red, oklch);\n}\n",
+    "a {\n  color: oklch(62.7955363921% 0.2576833038 29.2338802796deg);\n}\n"
+);
+test!(
+    to_space_display_p3,
+    "@use \"sass:color\";\na {\n  color: color.to-space(red, display-p3);\n}\n",
+    "a {\n  color: color(display-p3 0.9174875573 0.2002868077 0.1385605912);\n}\n"
+);
+test!(
+    to_space_round_trip,
+    "@use \"sass:color\";\na {\n  color: color.to-space(color.to-space(#abcdef, lab), rgb);\n}\n",
+    "a {\n  color: #abcdef;\n}\n"
+);
+test!(
+    to_space_achromatic_hue_is_missing,
+    "@use \"sass:color\";\na {\n  color: color.to-space(white, oklch);\n}\n",
+    "a {\n  color: oklch(100% 0 none);\n}\n"
+);
+test!(
+    channel_in_own_space,
+    "@use \"sass:color\";\na {\n  color: color.channel(lab(50% 20 30), \"lightness\"), color.channel(red, \"red\");\n}\n",
+    "a {\n  color: 50%, 255;\n}\n"
+);
+test!(
+    channel_in_other_space,
+    "@use \"sass:color\";\na {\n  color: color.channel(red, \"hue\", $space: oklch), color.channel(red, \"lightness\", $space: hsl);\n}\n",
+    "a {\n  color: 29.2338802796deg, 50%;\n}\n"
+);
+test!(
+    channel_oklch_lightness_is_percent,
+    "@use \"sass:color\";\na {\n  color: color.channel(oklch(70% 0.1 200), \"lightness\"), color.channel(oklab(0.5 0.1 0.1), \"lightness\");\n}\n",
+    "a {\n  color: 70%, 50%;\n}\n"
+);
+test!(
+    channel_alpha,
+    "@use \"sass:color\";\na {\n  color: color.channel(lab(50% 20 30 / 0.25), \"alpha\");\n}\n",
+    "a {\n  color: 0.25;\n}\n"
+);
+test!(
+    missing_channel_is_zero,
+    "@use \"sass:color\";\na {\n  color: color.channel(oklch(60% none 120), \"chroma\");\n}\n",
+    "a {\n  color: 0;\n}\n"
+);
+test!(
+    is_legacy,
+    "@use \"sass:color\";\na {\n  color: color.is-legacy(red), color.is-legacy(hsl(0, 50%, 50%)), color.is-legacy(oklch(60% 0.1 120));\n}\n",
+    "a {\n  color: true, true, false;\n}\n"
+);
+test!(
+    is_missing,
+    "@use \"sass:color\";\na {\n  color: color.is-missing(oklch(60% none 120), \"chroma\"), color.is-missing(oklch(60% none 120), \"hue\"), color.is-missing(red, \"red\");\n}\n",
+    "a {\n  color: true, false, false;\n}\n"
+);
+test!(
+    is_in_gamut,
+    "@use \"sass:color\";\na {\n  color: color.is-in-gamut(color(display-p3 1 0 0), srgb), color.is-in-gamut(red, display-p3), color.is-in-gamut(lab(50% 200 30));\n}\n",
+    "a {\n  color: false, true, true;\n}\n"
+);
+test!(
+    to_gamut_clip,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(color(srgb 1.5 0.5 -0.5), $method: clip);\n}\n",
+    "a {\n  color: color(srgb 1 0.5 0);\n}\n"
+);
+test!(
+    to_gamut_local_minde_keeps_space,
+    "@use \"sass:color\";\na {\n  color: color.space(color.to-gamut(oklch(70% 0.4 150), $space: srgb, $method: local-minde));\n}\n",
+    "a {\n  color: oklch;\n}\n"
+);
+test!(
+    to_gamut_local_minde_is_in_gamut,
+    "@use \"sass:color\";\na {\n  color: color.is-in-gamut(color.to-gamut(oklch(70% 0.4 150), $space: srgb, $method: local-minde), srgb);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    same_across_spaces,
+    "@use \"sass:color\";\na {\n  color: color.same(red, color.to-space(red, lab)), color.same(red, blue);\n}\n",
+    "a {\n  color: true, false;\n}\n"
+);
+test!(
+    equality_same_space,
+    "a {\n  color: lab(50% 20 30) == lab(50% 20 30), lab(50% 20 30) == lab(50% 20 31);\n}\n",
+    "a {\n  color: true, false;\n}\n"
+);
+test!(
+    equality_different_spaces,
+    "@use \"sass:color\";\na {\n  color: red == color.to-space(red, lab), oklch(60% none 120) == oklch(60% 0 120);\n}\n",
+    "a {\n  color: false, false;\n}\n"
+);
+test!(
+    adjust_non_legacy_color,
+    "@use \"sass:color\";\na {\n  color: color.adjust(lab(50% 20 30), $lightness: 10%, $b: -10);\n}\n",
+    "a {\n  color: lab(60% 20 20);\n}\n"
+);
+test!(
+    adjust_legacy_color_in_space,
+    "@use \"sass:color\";\na {\n  color: color.adjust(red, $lightness: -20%, $space: oklch);\n}\n",
+    "a {\n  color: #b40000;\n}\n"
+);
+test!(
+    scale_non_legacy_color,
+    "@use \"sass:color\";\na {\n  color: color.scale(oklch(60% 0.1 120), $chroma: 50%, $alpha: -50%);\n}\n",
+    "a {\n  color: oklch(60% 0.25 120deg / 0.5);\n}\n"
+);
+test!(
+    change_non_legacy_color,
+    "@use \"sass:color\";\na {\n  color: color.change(color(display-p3 1 0 0), $green: 0.5);\n}\n",
+    "a {\n  color: color(display-p3 1 0.5 0);\n}\n"
+);
+test!(
+    change_keeps_original_space,
+    "@use \"sass:color\";\na {\n  color: color.space(color.change(lab(50% 20 30), $hue: 90deg, $space: hsl));\n}\n",
+    "a {\n  color: lab;\n}\n"
+);
+test!(
+    mix_in_space,
+    "@use \"sass:color\";\na {\n  color: color.mix(lab(50% 20 30), lab(70% -20 10), 50%, lab);\n}\n",
+    "a {\n  color: lab(60% 0 20);\n}\n"
+);
+test!(
+    mix_in_polar_space_uses_shorter_hue,
+    "@use \"sass:color\";\na {\n  color: color.mix(oklch(60% 0.1 350), oklch(60% 0.1 30), $space: oklch);\n}\n",
+    "a {\n  color: oklch(60% 0.1 10deg);\n}\n"
+);
+test!(
+    mix_in_rgb_space_matches_legacy_mix,
+    "@use \"sass:color\";\na {\n  color: color.mix(red, blue, $space: rgb);\n}\n",
+    "a {\n  color: purple;\n}\n"
+);
+test!(
+    fade_out_keeps_space,
+    "a {\n  color: fade-out(lab(50% 20 30), 0.25);\n}\n",
+    "a {\n  color: lab(50% 20 30 / 0.75);\n}\n"
+);
+error!(
+    unknown_space,
+    "@use \"sass:color\";\na {\n  color: color.to-space(red, foo);\n}\n",
+    "Error: $space: Unknown color space \"foo\"."
+);
+error!(
+    color_function_unknown_space,
+    "a {\n  color: color(lab 1 0 0);\n}\n", "Error: $description: Unknown color space \"lab\"."
+);
+error!(
+    lab_channel_with_invalid_unit,
+    "a {\n  color: lab(50px 20 30);\n}\n",
+    "Error: $lightness: Expected 50px to have no units or \"%\"."
+);
+error!(
+    channel_not_in_space,
+    "@use \"sass:color\";\na {\n  color: color.channel(lab(50% 20 30), \"red\");\n}\n",
+    "Error: $channel: Color lab(50% 20 30) has no channel named red."
+);
+error!(
+    to_gamut_without_method,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(red);\n}\n",
+    "Error: $method: color.to-gamut() requires a $method argument for forwards-compatibility with changes in the CSS spec. Suggestion:"
+);
+error!(
+    mix_non_legacy_without_space,
+    "@use \"sass:color\";\na {\n  color: color.mix(lab(50% 20 30), red);\n}\n",
+    "Error: $space: To use color.mix() with non-legacy colors, you must provide a $space."
+);
+error!(
+    adjust_unknown_channel_for_space,
+    "@use \"sass:color\";\na {\n  color: color.adjust(lab(50% 20 30), $red: 10);\n}\n",
+    "Error: No argument named $red."
+);
+error!(
+    scale_hue,
+    "@use \"sass:color\";\na {\n  color: color.scale(oklch(60% 0.1 120), $hue: 10%);\n}\n",
+    "Error: $hue: Channel isn't scalable."
+);
+test!(
+    color_space_functions_allowed_in_plain_css,
+    "a {\n  color: oklch(60% 0.1 120);\n  background: color(display-p3 1 0 0);\n}\n",
+    "a {\n  color: oklch(60% 0.1 120);\n  background: color(display-p3 1 0 0);\n}\n",
+    grass::Options::default().input_syntax(grass::InputSyntax::Css)
+);
@@ -1,6 +1,18 @@
+use grass::{Comments, OutputStyle};
+
 #[macro_use]
 mod macros;
 
+fn comments(comments: Comments) -> grass::Options<'static> {
+    grass::Options::default().comments(comments)
+}
+
+fn compressed_comments(comments: Comments) -> grass::Options<'static> {
+    grass::Options::default()
+        .style(OutputStyle::Compressed)
+        .comments(comments)
+}
+
 test!(
     removes_inner_comments,
     "a {\n  color: red/* hi */;\n}\n",
@@ -173,3 +185,39 @@ test!(
 test!(silent_comment_as_child, "a {\n// silent\n}\n", "");
 test!(single_hash_in_loud_comment, "/*#*/", "/*#*/\n");
 error!(unclosed_loud_comment, "/*", "Error: expected more input.");
+test!(
+    strip_removes_all_loud_comments,
+    "/*! license */\n/* doc */\na {\n  /* inner */\n  color: red;\n}\n",
+    "a {\n  color: red;\n}\n",
+    comments(Comments::Strip)
+);
+test!(
+    strip_removes_rule_containing_only_comments,
+    "a {\n  /* inner */\n}\nb {\n  color: red;\n}\n",
+    "b {\n  color: red;\n}\n",
+    comments(Comments::Strip)
+);
+test!(
+    keep_important_keeps_only_important_comments,
+    "/*! license */\n/* doc */\na {\n  /*! inner */\n  /* inner */\n  color: red;\n}\n",
+    "/*! license */\na {\n  /*! inner */\n  color: red;\n}\n",
+    comments(Comments::KeepImportant)
+);
+test!(
+    keep_writes_comments_in_compressed_output,
+    "/*! license */\n/* doc */\na {\n  /* inner */\n  color: red;\n}\n",
+    "/*! license *//* doc */a{/* inner */color:red}",
+    compressed_comments(Comments::Keep)
+);
+test!(
+    strip_removes_important_comments_in_compressed_output,
+    "/*! license */\na {\n  color: red;\n}\n",
+    "a{color:red}",
+    compressed_comments(Comments::Strip)
+);
+test!(
+    compressed_removes_rule_containing_only_comments,
+    "a {\n  /* inner */\n}\nb {\n  color: red;\n}\n",
+    "b{color:red}",
+    grass::Options::default().style(OutputStyle::Compressed)
+);
@@ -0,0 +1,45 @@
+#[macro_use]
+mod macros;
+
+test!(
+    compact_simple_rule,
+    "a {\n  color: red;\n}\n",
+    "a { color: red; }\n",
+    grass::Options::default().style(grass::OutputStyle::Compact)
+);
+test!(
+    compact_rule_with_many_styles,
+    "a {\n  color: red;\n  color: green;\n  color: blue;\n}\n",
+    "a { color: red; color: green; color: blue; }\n",
+    grass::Options::default().style(grass::OutputStyle::Compact)
+);
+test!(
+    compact_nested_rules_on_consecutive_lines,
+    "a {\n  color: red;\n  b {\n    color: blue;\n  }\n}\n",
+    "a { color: red; }\na b { color: blue; }\n",
+    grass::Options::default().style(grass::OutputStyle::Compact)
+);
+test!(
+    compact_separates_groups,
+    "a {\n  color: red;\n}\nb {\n  color: blue;\n}\n",
+    "a { color: red; }\n\nb { color: blue; }\n",
+    grass::Options::default().style(grass::OutputStyle::Compact)
+);
+test!(
+    compact_media_rule,
+    "@media foo {\n  a {\n    color: red;\n  }\n  b {\n    color: blue;\n  }\n}\n",
+    "@media foo { a { color: red; } b { color: blue; } }\n",
+    grass::Options::default().style(grass::OutputStyle::Compact)
+);
+test!(
+    compact_keeps_spaces_in_values,
+    "a {\n  color: foo(a, b) c;\n}\n",
+    "a { color: foo(a, b) c; }\n",
+    grass::Options::default().style(grass::OutputStyle::Compact)
+);
+test!(
+    compact_comment,
+    "/* foo */\na {\n  /* bar */\n  color: red;\n}\n",
+    "/* foo */\na { /* bar */ color: red; }\n",
+    grass::Options::default().style(grass::OutputStyle::Compact)
+);
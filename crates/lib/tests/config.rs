@@ -0,0 +1,137 @@
+use std::{io::Write, path::Path};
+
+#[macro_use]
+mod macros;
+
+#[test]
+fn config_load_paths() {
+    tempfile!(
+        "config_load_paths__a.scss",
+        "$a: red;",
+        dir = "dir-config_load_paths"
+    );
+    tempfile!(
+        "config_load_paths.toml",
+        "load-paths = [\"dir-config_load_paths\"]"
+    );
+
+    let input = "@import \"config_load_paths__a\";\na {\n color: $a;\n}";
+
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::from_string(
+            input.to_owned(),
+            &grass::Options::from_config("config_load_paths.toml").unwrap()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn config_load_paths_relative_to_config_file() {
+    tempfile!(
+        "config_load_paths_relative.toml",
+        "load-paths = [\"vendor\"]",
+        dir = "dir-config_load_paths_relative"
+    );
+
+    let config =
+        grass::Config::from_path("dir-config_load_paths_relative/config_load_paths_relative.toml")
+            .unwrap();
+
+    assert_eq!(
+        config.load_paths,
+        vec![Path::new("dir-config_load_paths_relative/vendor")]
+    );
+}
+
+#[test]
+fn config_style() {
+    tempfile!("config_style.toml", "style = \"compressed\"");
+
+    assert_eq!(
+        "a{color:red}",
+        grass::from_string(
+            "a {\n color: red;\n}".to_owned(),
+            &grass::Options::from_config("config_style.toml").unwrap()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn config_nested_style() {
+    tempfile!("config_nested_style.toml", "style = \"nested\"");
+
+    assert_eq!(
+        "a {\n  color: red; }\n  a b {\n    color: blue; }\n",
+        grass::from_string(
+            "a {\n color: red;\n b { color: blue; }\n}".to_owned(),
+            &grass::Options::from_config("config_nested_style.toml").unwrap()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn config_entrypoints_and_sections() {
+    tempfile!(
+        "config_entrypoints.toml",
+        r#"
+[entrypoints]
+"src/b.scss" = "dist/b.css"
+"src/a.scss" = "dist/a.css"
+
+[deprecations]
+silence = ["slash-div"]
+fatal = ["import"]
+
+[source-map]
+enabled = true
+urls = "absolute"
+embed-sources = true
+"#
+    );
+
+    let config = grass::Config::from_path("config_entrypoints.toml").unwrap();
+
+    assert_eq!(
+        config.entrypoints,
+        vec![
+            (
+                Path::new("src/a.scss").into(),
+                Path::new("dist/a.css").into()
+            ),
+            (
+                Path::new("src/b.scss").into(),
+                Path::new("dist/b.css").into()
+            ),
+        ]
+    );
+    assert_eq!(config.deprecations.silence, vec!["slash-div".to_owned()]);
+    assert_eq!(config.deprecations.fatal, vec!["import".to_owned()]);
+    assert!(config.source_map.enabled);
+    assert_eq!(config.source_map.urls, grass::SourceMapUrls::Absolute);
+    assert!(config.source_map.embed_sources);
+    assert!(!config.source_map.embed_source_map);
+}
+
+#[test]
+fn config_unknown_style() {
+    tempfile!("config_unknown_style.toml", "style = \"nested-ish\"");
+
+    match grass::Options::from_config("config_unknown_style.toml") {
+        Err(e)
+            if e.to_string()
+                .contains("unknown output style \"nested-ish\"") => {}
+        Err(e) => panic!("failed in the wrong way: {}", e),
+        Ok(..) => panic!("did not fail"),
+    }
+}
+
+#[test]
+fn config_unknown_key() {
+    tempfile!("config_unknown_key.toml", "load-pathz = []");
+
+    assert!(grass::Options::from_config("config_unknown_key.toml").is_err());
+}
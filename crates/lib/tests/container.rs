@@ -0,0 +1,186 @@
+#[macro_use]
+mod macros;
+
+test!(
+    size_query,
+    "@container (min-width: 400px) {
+        a {
+            b: c;
+        }
+    }",
+    "@container (min-width: 400px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    named_query,
+    "@container sidebar (min-width: 400px) {
+        a {
+            b: c;
+        }
+    }",
+    "@container sidebar (min-width: 400px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    name_only,
+    "@container sidebar {
+        a {
+            b: c;
+        }
+    }",
+    "@container sidebar {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    range_syntax,
+    "@container (400px <= width <= 700px) {
+        a {
+            b: c;
+        }
+    }",
+    "@container (400px <= width <= 700px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    not_condition,
+    "@container not (width < 400px) {
+        a {
+            b: c;
+        }
+    }",
+    "@container not (width < 400px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    and_conditions_normalize_whitespace,
+    "@container card   (width > 1px)   and   (height > 1px) {
+        a {
+            b: c;
+        }
+    }",
+    "@container card (width > 1px) and (height > 1px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    or_conditions,
+    "@container (width > 1px) or (height > 1px) {
+        a {
+            b: c;
+        }
+    }",
+    "@container (width > 1px) or (height > 1px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    style_query,
+    "@container style(--theme: dark) {
+        a {
+            b: c;
+        }
+    }",
+    "@container style(--theme: dark) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    named_style_query,
+    "@container card style(--theme: dark) and (width > 1px) {
+        a {
+            b: c;
+        }
+    }",
+    "@container card style(--theme: dark) and (width > 1px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    query_list,
+    "@container card,(min-width: 1px) {
+        a {
+            b: c;
+        }
+    }",
+    "@container card, (min-width: 1px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    query_list_compressed,
+    "@container card, (min-width: 1px) {
+        a {
+            b: c;
+        }
+    }",
+    "@container card,(min-width: 1px){a{b:c}}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    interpolated_query,
+    "$width: 400px;
+    @container (min-width: #{$width}) {
+        a {
+            b: c;
+        }
+    }",
+    "@container (min-width: 400px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    bubbles_out_of_style_rule,
+    "a {
+        @container (min-width: 1px) {
+            b: c;
+        }
+    }",
+    "@container (min-width: 1px) {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    nested_containers_do_not_merge,
+    "a {
+        @container (min-width: 1px) {
+            @container (max-width: 2px) {
+                b: c;
+            }
+        }
+    }",
+    "@container (min-width: 1px) {\n  @container (max-width: 2px) {\n    a {\n      b: c;\n    }\n  }\n}\n"
+);
+test!(empty_body_is_removed, "@container (min-width: 1px) {}", "");
+test!(
+    extend_from_outside,
+    ".a {
+        @container (x) {
+            @extend .b;
+        }
+    }
+
+    @container (x) {
+        .b {
+            c: d;
+        }
+    }",
+    "@container (x) {\n  .b, .a {\n    c: d;\n  }\n}\n"
+);
+test!(
+    extend_inside,
+    "@container (x) {
+        .a {
+            b: c;
+        }
+
+        .d {
+            @extend .a;
+        }
+    }",
+    "@container (x) {\n  .a, .d {\n    b: c;\n  }\n}\n"
+);
+test!(
+    at_root_without_container,
+    "@container (x) {
+        @at-root (without: container) {
+            a {
+                b: c;
+            }
+        }
+    }",
+    "a {\n  b: c;\n}\n"
+);
+error!(
+    reserved_name,
+    "@container none (x) {a {b: c}}", "Error: Invalid container name \"none\"."
+);
+error!(
+    mixed_and_or,
+    "@container (x) and (y) or (z) {a {b: c}}", "Error: expected no more input."
+);
+error!(
+    missing_condition_parens,
+    "@container not x {a {b: c}}", "Error: expected \"(\"."
+);
+error!(no_body, "@container (x);", "Error: expected \"{\".");
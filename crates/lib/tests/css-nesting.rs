@@ -0,0 +1,210 @@
+use grass::{InputSyntax, Nesting};
+
+#[macro_use]
+mod macros;
+
+fn flatten() -> grass::Options<'static> {
+    grass::Options::default().input_syntax(InputSyntax::Css)
+}
+
+fn preserve() -> grass::Options<'static> {
+    grass::Options::default()
+        .input_syntax(InputSyntax::Css)
+        .nesting(Nesting::Preserve)
+}
+
+test!(
+    flatten_parent_pseudo,
+    ".card {
+        color: red;
+        &:hover {
+            color: blue;
+        }
+    }",
+    ".card {\n  color: red;\n}\n.card:hover {\n  color: blue;\n}\n",
+    flatten()
+);
+test!(
+    flatten_implicit_descendant,
+    ".card {
+        .title {
+            a: b;
+        }
+    }",
+    ".card .title {\n  a: b;\n}\n",
+    flatten()
+);
+test!(
+    flatten_type_selector_with_pseudo,
+    ".card {
+        a:hover {
+            b: c;
+        }
+    }",
+    ".card a:hover {\n  b: c;\n}\n",
+    flatten()
+);
+test!(
+    flatten_relative_selector,
+    ".card {
+        > a {
+            b: c;
+        }
+        + .x {
+            d: e;
+        }
+    }",
+    ".card > a {\n  b: c;\n}\n.card + .x {\n  d: e;\n}\n",
+    flatten()
+);
+test!(
+    flatten_parent_not_first,
+    ".card {
+        .x & {
+            a: b;
+        }
+    }",
+    ".x .card {\n  a: b;\n}\n",
+    flatten()
+);
+test!(
+    flatten_parent_after_type_selector,
+    ".card {
+        div& {
+            a: b;
+        }
+    }",
+    "div.card {\n  a: b;\n}\n",
+    flatten()
+);
+test!(
+    flatten_type_selector_after_parent,
+    ".card {
+        &div {
+            a: b;
+        }
+    }",
+    "div.card {\n  a: b;\n}\n",
+    flatten()
+);
+test!(
+    flatten_parent_in_pseudo_selector,
+    ".card {
+        :is(&, .x) {
+            a: b;
+        }
+    }",
+    ":is(.card, .x) {\n  a: b;\n}\n",
+    flatten()
+);
+test!(
+    flatten_parent_list,
+    ".a, .b {
+        & + & {
+            c: d;
+        }
+    }",
+    ".a + .a, .a + .b, .b + .a, .b + .b {\n  c: d;\n}\n",
+    flatten()
+);
+test!(
+    flatten_nested_at_rules,
+    ".card {
+        @media print {
+            a: b;
+            .inner {
+                c: d;
+            }
+        }
+        @supports (display: grid) {
+            e: f;
+        }
+    }",
+    "@media print {\n  .card {\n    a: b;\n  }\n  .card .inner {\n    c: d;\n  }\n}\n@supports (display: grid) {\n  .card {\n    e: f;\n  }\n}\n",
+    flatten()
+);
+test!(
+    declaration_hacks_are_not_rules,
+    ".card {
+        *zoom: 1;
+        background: url(data:image/png;base64,AAA);
+        --custom: { a: b };
+    }",
+    ".card {\n  *zoom: 1;\n  background: url(data:image/png;base64,AAA);\n  --custom: { a: b };\n}\n",
+    flatten()
+);
+test!(
+    preserve_nested_rules,
+    ".card {
+        color: red;
+        &:hover {
+            color: blue;
+        }
+        .title {
+            a: b;
+        }
+    }",
+    ".card {\n  color: red;\n  &:hover {\n    color: blue;\n  }\n  .title {\n    a: b;\n  }\n}\n",
+    preserve()
+);
+test!(
+    preserve_relative_and_trailing_parent,
+    ".card {
+        > a {
+            b: c;
+        }
+        .x & {
+            d: e;
+        }
+        div& {
+            f: g;
+        }
+    }",
+    ".card {\n  > a {\n    b: c;\n  }\n  .x & {\n    d: e;\n  }\n  div& {\n    f: g;\n  }\n}\n",
+    preserve()
+);
+test!(
+    preserve_nested_at_rules,
+    ".card {
+        @media print {
+            a: b;
+            .inner {
+                c: d;
+            }
+        }
+        @layer base {
+            e: f;
+        }
+    }",
+    ".card {\n  @media print {\n    a: b;\n    .inner {\n      c: d;\n    }\n  }\n  @layer base {\n    e: f;\n  }\n}\n",
+    preserve()
+);
+test!(
+    preserve_compressed,
+    ".card {
+        color: red;
+        &:hover {
+            color: blue;
+        }
+    }",
+    ".card{color:red;&:hover{color:blue}}",
+    preserve().style(grass::OutputStyle::Compressed)
+);
+error!(
+    top_level_parent,
+    "& {
+        a: b;
+    }",
+    "Error: Top-level selectors may not contain the parent selector \"&\".",
+    flatten()
+);
+error!(
+    parent_incompatible_type,
+    "span {
+        div& {
+            a: b;
+        }
+    }",
+    "Error: Parent \"span\" is incompatible with this selector.",
+    flatten()
+);
@@ -0,0 +1,373 @@
+use grass::{CssAtRule, CssDeclaration, CssNode, CssStyleRule, Options, OutputStyle};
+
+#[macro_use]
+mod macros;
+
+const INPUT: &str = r#"@use "sass:math";
+@import "foo.css";
+@import url(bar.css) screen;
+@charset "UTF-8";
+
+/* loud comment
+   spanning lines */
+%placeholder {
+  margin: 0;
+}
+
+a, b > c {
+  color: red !important;
+  width: math.div(10px, 3);
+  --custom:   { raw }  ;
+  font: {
+    family: serif;
+    size: 12px;
+  }
+
+  &:hover {
+    color: blue;
+  }
+
+  @media screen and (min-width: 100px) {
+    color: green;
+  }
+}
+
+.extender {
+  @extend %placeholder;
+}
+
+@supports (display: grid) {
+  .grid {
+    display: grid;
+  }
+}
+
+@media print {
+  .x {
+    display: none;
+  }
+}
+
+@keyframes spin {
+  from {
+    transform: rotate(0deg);
+  }
+  50%, 75% {
+    transform: rotate(180deg);
+  }
+}
+
+@font-face {
+  font-family: "Foo";
+}
+
+@layer base, components;
+@layer base {
+  p {
+    margin: 0;
+  }
+}
+
+@container sidebar (min-width: 400px) {
+  .card {
+    display: grid;
+  }
+}
+
+@scope (.card) to (.content) {
+  img {
+    border: 1px solid black;
+  }
+}
+
+@unknown foo bar;
+"#;
+
+fn round_trip(options: &Options) {
+    let expected = grass::from_string(INPUT.to_owned(), options).unwrap();
+    let tree = grass::compile_to_tree(INPUT.to_owned(), options).unwrap();
+    assert_eq!(tree.to_css(options).unwrap(), expected);
+}
+
+#[test]
+fn round_trip_expanded() {
+    round_trip(&Options::default());
+}
+
+#[test]
+fn round_trip_compressed() {
+    round_trip(&Options::default().style(OutputStyle::Compressed));
+}
+
+#[test]
+fn round_trip_compact() {
+    round_trip(&Options::default().style(OutputStyle::Compact));
+}
+
+#[test]
+fn round_trip_nested_css() {
+    round_trip(&Options::default().nesting(grass::Nesting::Preserve));
+}
+
+#[test]
+fn round_trip_source_comments() {
+    let mut fs = macros::TestFs::new();
+    fs.add_file("_button.scss", ".button {\n  color: red;\n}\n");
+
+    let options = Options::default().fs(&fs).source_comments(true);
+    let input = "@use 'button';\n\na {\n  color: blue;\n}\n";
+
+    let expected = grass::from_string(input.to_owned(), &options).unwrap();
+    let tree = grass::compile_to_tree(input.to_owned(), &options).unwrap();
+    assert_eq!(tree.to_css(&options).unwrap(), expected);
+}
+
+#[test]
+fn style_rules_and_declarations() {
+    let tree = grass::compile_to_tree(
+        "a, b > c {\n  color: red !important;\n  --x: {a: b};\n  font: {\n    size: 1px;\n  }\n}\n"
+            .to_owned(),
+        &Options::default(),
+    )
+    .unwrap();
+
+    assert_eq!(tree.children.len(), 1);
+    let rule = match &tree.children[0] {
+        CssNode::StyleRule(rule) => rule,
+        node => panic!("expected a style rule, found {:?}", node),
+    };
+    assert_eq!(rule.selectors, ["a", "b > c"]);
+    assert_eq!(rule.span.as_ref().unwrap().begin.line, 0);
+
+    let declarations: Vec<(&str, &str, bool)> = rule
+        .children
+        .iter()
+        .map(|node| match node {
+            CssNode::Declaration(declaration) => (
+                declaration.name.as_str(),
+                declaration.value.as_str(),
+                declaration.important,
+            ),
+            node => panic!("expected a declaration, found {:?}", node),
+        })
+        .collect();
+    assert_eq!(
+        declarations,
+        [
+            ("color", "red", true),
+            ("--x", " {a: b}", false),
+            ("font-size", "1px", false)
+        ]
+    );
+
+    let lines: Vec<usize> = rule
+        .children
+        .iter()
+        .map(|node| match node {
+            CssNode::Declaration(declaration) => declaration.span.as_ref().unwrap().begin.line,
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(lines, [1, 2, 4]);
+}
+
+#[test]
+fn at_rules() {
+    let tree = grass::compile_to_tree(
+        "@import 'a.css' print;\n@media (min-width: 1px) {\n  a {\n    color: red;\n  }\n}\n@keyframes k {\n  to {\n    color: red;\n  }\n}\n@layer a, b;\n"
+            .to_owned(),
+        &Options::default(),
+    )
+    .unwrap();
+
+    let at_rules: Vec<&CssAtRule> = tree
+        .children
+        .iter()
+        .map(|node| match node {
+            CssNode::AtRule(at_rule) => at_rule,
+            node => panic!("expected an at-rule, found {:?}", node),
+        })
+        .collect();
+
+    let summary: Vec<(&str, &str, Option<usize>)> = at_rules
+        .iter()
+        .map(|at_rule| {
+            (
+                at_rule.name.as_str(),
+                at_rule.prelude.as_str(),
+                at_rule.children.as_ref().map(Vec::len),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("import", "'a.css' print", None),
+            ("media", "(min-width: 1px)", Some(1)),
+            ("keyframes", "k", Some(1)),
+            ("layer", "a, b", None),
+        ]
+    );
+
+    let lines: Vec<usize> = at_rules
+        .iter()
+        .map(|at_rule| at_rule.span.as_ref().unwrap().begin.line)
+        .collect();
+    assert_eq!(lines, [0, 1, 6, 11]);
+
+    match &at_rules[2].children.as_ref().unwrap()[0] {
+        CssNode::StyleRule(keyframe) => assert_eq!(keyframe.selectors, ["to"]),
+        node => panic!("expected a keyframe block, found {:?}", node),
+    }
+}
+
+#[test]
+fn comments() {
+    let tree = grass::compile_to_tree(
+        "// silent\n/* loud */\na {\n  /* inner */\n  color: red;\n}\n".to_owned(),
+        &Options::default(),
+    )
+    .unwrap();
+
+    match &tree.children[0] {
+        CssNode::Comment(comment) => {
+            assert_eq!(comment.text, "/* loud */");
+            assert_eq!(comment.span.as_ref().unwrap().begin.line, 1);
+        }
+        node => panic!("expected a comment, found {:?}", node),
+    }
+
+    match &tree.children[1] {
+        CssNode::StyleRule(rule) => {
+            assert!(matches!(&rule.children[0], CssNode::Comment(c) if c.text == "/* inner */"));
+        }
+        node => panic!("expected a style rule, found {:?}", node),
+    }
+}
+
+#[test]
+fn omits_invisible_rules() {
+    let tree = grass::compile_to_tree(
+        "%a {\n  color: red;\n}\nb {}\nc, %d {\n  color: red;\n}\n".to_owned(),
+        &Options::default(),
+    )
+    .unwrap();
+
+    assert_eq!(tree.children.len(), 1);
+    match &tree.children[0] {
+        CssNode::StyleRule(rule) => assert_eq!(rule.selectors, ["c"]),
+        node => panic!("expected a style rule, found {:?}", node),
+    }
+}
+
+#[test]
+fn spans_point_into_modules() {
+    let mut fs = macros::TestFs::new();
+    fs.add_file("_a.scss", "\n\na {\n  color: red;\n}\n");
+
+    let tree =
+        grass::compile_to_tree("@use 'a';\n".to_owned(), &Options::default().fs(&fs)).unwrap();
+
+    match &tree.children[0] {
+        CssNode::StyleRule(rule) => {
+            let span = rule.span.as_ref().unwrap();
+            assert!(span.file.name().ends_with("_a.scss"));
+            assert_eq!(span.begin.line, 2);
+        }
+        node => panic!("expected a style rule, found {:?}", node),
+    }
+}
+
+#[test]
+fn serializes_modified_tree() {
+    let options = Options::default();
+    let mut tree =
+        grass::compile_to_tree("a {\n  color: red;\n  margin: 0;\n}\n".to_owned(), &options)
+            .unwrap();
+
+    match &mut tree.children[0] {
+        CssNode::StyleRule(rule) => {
+            rule.selectors = vec!["a".to_owned(), ".b".to_owned()];
+            rule.children.remove(1);
+            if let CssNode::Declaration(declaration) = &mut rule.children[0] {
+                declaration.value = "blue".to_owned();
+                declaration.important = true;
+            }
+            rule.children.push(CssNode::Declaration(CssDeclaration {
+                name: "padding".to_owned(),
+                value: "1px 2px".to_owned(),
+                ..CssDeclaration::default()
+            }));
+        }
+        node => panic!("expected a style rule, found {:?}", node),
+    }
+
+    tree.children.push(CssNode::AtRule(CssAtRule {
+        name: "media".to_owned(),
+        prelude: "print".to_owned(),
+        children: Some(vec![CssNode::StyleRule(CssStyleRule {
+            selectors: vec!["c".to_owned()],
+            children: vec![CssNode::Declaration(CssDeclaration {
+                name: "display".to_owned(),
+                value: "none".to_owned(),
+                ..CssDeclaration::default()
+            })],
+            ..CssStyleRule::default()
+        })]),
+        ..CssAtRule::default()
+    }));
+
+    assert_eq!(
+        tree.to_css(&options).unwrap(),
+        "a, .b {\n  color: blue !important;\n  padding: 1px 2px;\n}\n\n@media print {\n  c {\n    display: none;\n  }\n}\n"
+    );
+    assert_eq!(
+        tree.to_css(&Options::default().style(OutputStyle::Compressed))
+            .unwrap(),
+        "a,.b{color:blue !important;padding:1px 2px}@media print{c{display:none}}"
+    );
+}
+
+#[test]
+fn serializes_new_tree() {
+    let tree = grass::CssStylesheet {
+        children: vec![CssNode::StyleRule(CssStyleRule {
+            selectors: vec!["a".to_owned()],
+            children: vec![CssNode::StyleRule(CssStyleRule {
+                selectors: vec!["&:hover".to_owned()],
+                children: vec![CssNode::Declaration(CssDeclaration {
+                    name: "color".to_owned(),
+                    value: "red".to_owned(),
+                    ..CssDeclaration::default()
+                })],
+                ..CssStyleRule::default()
+            })],
+            ..CssStyleRule::default()
+        })],
+        span: None,
+    };
+
+    assert_eq!(
+        tree.to_css(&Options::default()).unwrap(),
+        "a {\n  &:hover {\n    color: red;\n  }\n}\n"
+    );
+}
+
+#[test]
+fn invalid_selector_in_modified_tree() {
+    let options = Options::default();
+    let mut tree = grass::compile_to_tree("a {\n  color: red;\n}\n".to_owned(), &options).unwrap();
+
+    if let CssNode::StyleRule(rule) = &mut tree.children[0] {
+        rule.selectors = vec!["a[".to_owned()];
+    }
+
+    assert!(tree.to_css(&options).is_err());
+}
+
+#[test]
+fn compile_error() {
+    assert!(
+        grass::compile_to_tree("a { color: $undefined; }".to_owned(), &Options::default()).is_err()
+    );
+}
@@ -1936,7 +1936,6 @@ error!(
     "Error: Parent selectors aren't allowed here."
 );
 error!(
-    #[ignore = "we do not currently respect this"]
     extend_across_media_boundary,
     "a {
         display: none;
@@ -1950,13 +1949,116 @@ error!(
     "Error: You may not @extend selectors across media queries."
 );
 error!(
-    #[ignore = "we do not error for this"]
     extend_target_does_not_exist,
     "a {
         @extend dne;
     }",
     "Error: The target selector was not found."
 );
+error!(
+    extend_target_does_not_exist_in_media,
+    "@media screen {
+        a {
+            @extend dne;
+        }
+    }",
+    "Error: The target selector was not found."
+);
+error!(
+    extend_target_does_not_exist_mandatory_and_optional,
+    "a {
+        @extend dne !optional;
+    }
+
+    a {
+        @extend dne;
+    }",
+    "Error: The target selector was not found."
+);
+error!(
+    extend_into_media_from_different_media,
+    "@media screen {
+        a {
+            color: red;
+        }
+    }
+
+    @media print {
+        b {
+            @extend a;
+        }
+    }",
+    "Error: You may not @extend selectors across media queries."
+);
+error!(
+    extend_same_selector_in_different_media,
+    "@media screen {
+        a {
+            color: red;
+        }
+    }
+
+    @media print {
+        a {
+            color: blue;
+        }
+
+        b {
+            @extend a;
+        }
+    }",
+    "Error: You may not @extend selectors across media queries."
+);
+error!(
+    extend_same_selector_at_top_level_and_in_media,
+    "a {
+        color: red;
+    }
+
+    @media print {
+        a {
+            color: blue;
+        }
+
+        b {
+            @extend a;
+        }
+    }",
+    "Error: You may not @extend selectors across media queries."
+);
+test!(
+    extend_same_selector_in_different_media_from_top_level,
+    "@media screen {
+        a {
+            color: red;
+        }
+    }
+
+    @media print {
+        a {
+            color: blue;
+        }
+    }
+
+    b {
+        @extend a;
+    }",
+    "@media screen {\n  a, b {\n    color: red;\n  }\n}\n@media print {\n  a, b {\n    color: blue;\n  }\n}\n"
+);
+#[test]
+fn extend_across_media_boundary_points_to_media_rule() {
+    let input = "a {\n  color: red;\n}\n@media screen {\n  b {\n    @extend a;\n  }\n}\n";
+
+    match grass::from_string(input.to_string(), &grass::Options::default()) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert_eq!(
+            "Error: You may not @extend selectors across media queries.\n  \
+            ╷\n6 │     @extend a;\n  │     ^^^^^^^^^\n  ╵\n  \
+            ╷\n4 │ @media screen {\n  │ ───────────── media\n  ╵\n./stdin:6:5\n",
+            e.to_string()
+        ),
+    }
+}
 error!(
     #[ignore = "crash"]
     extends_self_is_has_invalid_combinator,
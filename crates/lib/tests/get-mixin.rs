@@ -0,0 +1,247 @@
+use std::io::Write;
+
+#[macro_use]
+mod macros;
+
+test!(
+    inspect_user_defined_mixin,
+    "@use 'sass:meta';
+    @mixin foo {}
+    a {b: meta.inspect(meta.get-mixin(foo))}",
+    "a {\n  b: get-mixin(\"foo\");\n}\n"
+);
+test!(
+    inspect_builtin_mixin,
+    "@use 'sass:meta';
+    a {b: meta.inspect(meta.get-mixin(load-css, meta))}",
+    "a {\n  b: get-mixin(\"load-css\");\n}\n"
+);
+test!(
+    type_of_mixin,
+    "@use 'sass:meta';
+    @mixin foo {}
+    a {b: meta.type-of(meta.get-mixin(foo))}",
+    "a {\n  b: mixin;\n}\n"
+);
+test!(
+    get_mixin_normalizes_underscores,
+    "@use 'sass:meta';
+    @mixin foo-bar {}
+    a {b: meta.inspect(meta.get-mixin(foo_bar))}",
+    "a {\n  b: get-mixin(\"foo-bar\");\n}\n"
+);
+test!(
+    same_mixin_equal,
+    "@use 'sass:meta';
+    @mixin foo {}
+    a {b: meta.get-mixin(foo) == meta.get-mixin(foo)}",
+    "a {\n  b: true;\n}\n"
+);
+test!(
+    different_mixin_same_body_not_equal,
+    "@use 'sass:meta';
+    @mixin foo {}
+    $first: meta.get-mixin(foo);
+    @mixin foo {}
+    a {b: $first == meta.get-mixin(foo)}",
+    "a {\n  b: false;\n}\n"
+);
+test!(
+    different_name_not_equal,
+    "@use 'sass:meta';
+    @mixin foo {}
+    @mixin bar {}
+    a {b: meta.get-mixin(foo) == meta.get-mixin(bar)}",
+    "a {\n  b: false;\n}\n"
+);
+test!(
+    mixin_as_map_key,
+    "@use 'sass:meta';
+    @mixin foo {}
+    $map: (meta.get-mixin(foo): a);
+    a {b: map-get($map, meta.get-mixin(foo))}",
+    "a {\n  b: a;\n}\n"
+);
+test!(
+    accepts_content_true,
+    "@use 'sass:meta';
+    @mixin foo {@content}
+    a {b: meta.accepts-content(meta.get-mixin(foo))}",
+    "a {\n  b: true;\n}\n"
+);
+test!(
+    accepts_content_false,
+    "@use 'sass:meta';
+    @mixin foo {}
+    a {b: meta.accepts-content(meta.get-mixin(foo))}",
+    "a {\n  b: false;\n}\n"
+);
+test!(
+    accepts_content_builtin,
+    "@use 'sass:meta';
+    a {
+        b: meta.accepts-content(meta.get-mixin(load-css, meta));
+        c: meta.accepts-content(meta.get-mixin(apply, meta));
+    }",
+    "a {\n  b: false;\n  c: true;\n}\n"
+);
+test!(
+    apply_no_args,
+    "@use 'sass:meta';
+    @mixin foo {b: c}
+    a {@include meta.apply(meta.get-mixin(foo))}",
+    "a {\n  b: c;\n}\n"
+);
+test!(
+    apply_positional_and_named_args,
+    "@use 'sass:meta';
+    @mixin foo($a, $b: 2, $c: 3) {b: $a $b $c}
+    a {@include meta.apply(meta.get-mixin(foo), 1, $c: 4)}",
+    "a {\n  b: 1 2 4;\n}\n"
+);
+test!(
+    apply_rest_args,
+    "@use 'sass:meta';
+    @mixin foo($args...) {b: $args}
+    $list: 1, 2, 3;
+    a {@include meta.apply(meta.get-mixin(foo), $list...)}",
+    "a {\n  b: 1, 2, 3;\n}\n"
+);
+test!(
+    apply_passes_content,
+    "@use 'sass:meta';
+    @mixin foo {a {@content}}
+    @include meta.apply(meta.get-mixin(foo)) {b: c}",
+    "a {\n  b: c;\n}\n"
+);
+test!(
+    apply_passes_content_args,
+    "@use 'sass:meta';
+    @mixin foo {a {@content(1)}}
+    @include meta.apply(meta.get-mixin(foo)) using ($x) {b: $x}",
+    "a {\n  b: 1;\n}\n"
+);
+test!(
+    apply_content_exists,
+    "@use 'sass:meta';
+    @mixin foo {a {b: content-exists()}}
+    @include meta.apply(meta.get-mixin(foo));",
+    "a {\n  b: false;\n}\n"
+);
+test!(
+    apply_nested,
+    "@use 'sass:meta';
+    @mixin foo {a {@content}}
+    @mixin bar {@include meta.apply(meta.get-mixin(foo)) {@content}}
+    @include bar {b: c}",
+    "a {\n  b: c;\n}\n"
+);
+test!(
+    apply_uses_definition_scope,
+    "@use 'sass:meta';
+    $x: outer;
+    @mixin foo {a {b: $x}}
+    c {
+        $x: inner;
+        @include meta.apply(meta.get-mixin(foo));
+    }",
+    "c a {\n  b: outer;\n}\n"
+);
+test!(
+    module_mixins_empty,
+    "@use 'sass:meta';
+    @use 'sass:math';
+    a {b: meta.inspect(meta.module-mixins(math))}",
+    "a {\n  b: ();\n}\n"
+);
+test!(
+    module_mixins_builtin,
+    "@use 'sass:meta';
+    @use 'sass:map';
+    a {b: map.has-key(meta.module-mixins(meta), load-css)}",
+    "a {\n  b: true;\n}\n"
+);
+error!(
+    get_mixin_not_found,
+    "@use 'sass:meta';
+    a {b: meta.get-mixin(foo)}",
+    "Error: Mixin not found: foo"
+);
+error!(
+    get_mixin_name_not_string,
+    "@use 'sass:meta';
+    a {b: meta.get-mixin(1)}",
+    "Error: $name: 1 is not a string."
+);
+error!(
+    get_mixin_module_not_found,
+    "@use 'sass:meta';
+    a {b: meta.get-mixin(foo, bar)}",
+    "Error: There is no module with the namespace \"bar\"."
+);
+error!(
+    get_mixin_not_in_module,
+    "@use 'sass:meta';
+    @use 'sass:math';
+    a {b: meta.get-mixin(foo, math)}",
+    "Error: Undefined mixin."
+);
+error!(
+    mixin_not_valid_css,
+    "@use 'sass:meta';
+    @mixin foo {}
+    a {b: meta.get-mixin(foo)}",
+    "Error: get-mixin(\"foo\") isn't a valid CSS value."
+);
+error!(
+    accepts_content_not_mixin,
+    "@use 'sass:meta';
+    a {b: meta.accepts-content(foo)}",
+    "Error: $mixin: foo is not a mixin reference."
+);
+error!(
+    apply_not_mixin,
+    "@use 'sass:meta';
+    a {@include meta.apply(foo)}",
+    "Error: $mixin: foo is not a mixin reference."
+);
+error!(
+    apply_content_to_mixin_without_content,
+    "@use 'sass:meta';
+    @mixin foo {}
+    a {@include meta.apply(meta.get-mixin(foo)) {b: c}}",
+    "Error: Mixin doesn't accept a content block."
+);
+error!(
+    apply_missing_arg,
+    "@use 'sass:meta';
+    @mixin foo($a) {}
+    a {@include meta.apply(meta.get-mixin(foo))}",
+    "Error: Missing argument $a."
+);
+
+#[test]
+fn get_mixin_from_module() {
+    let input = "@use \"sass:meta\";\n@use \"get_mixin_from_module\" as module;\na {\n  @include meta.apply(meta.get-mixin(foo, module), red);\n}";
+    tempfile!(
+        "get_mixin_from_module.scss",
+        "@mixin foo($color) { color: $color; }"
+    );
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn module_mixins_user_defined() {
+    let input = "@use \"sass:meta\";\n@use \"module_mixins_user_defined\" as module;\na {\n  b: meta.inspect(meta.module-mixins(module));\n}";
+    tempfile!(
+        "module_mixins_user_defined.scss",
+        "@mixin foo {} @mixin bar {} @mixin -private {}"
+    );
+    assert_eq!(
+        "a {\n  b: (\"foo\": get-mixin(\"foo\"), \"bar\": get-mixin(\"bar\"));\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
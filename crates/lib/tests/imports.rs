@@ -156,6 +156,31 @@ fn basic_load_path() {
     );
 }
 
+#[test]
+fn load_path_explicit_file_extension() {
+    tempfile!(
+        "load_path_explicit_file_extension__a.scss",
+        "@import \"load_path_explicit_file_extension__b.scss\";\na {\n color: $a;\n}",
+        dir = "dir-load_path_explicit_file_extension__a"
+    );
+    tempfile!(
+        "load_path_explicit_file_extension__b.scss",
+        "$a: red;",
+        dir = "dir-load_path_explicit_file_extension__b"
+    );
+
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::from_path(
+            "dir-load_path_explicit_file_extension__a/load_path_explicit_file_extension__a.scss",
+            &grass::Options::default().load_path(std::path::Path::new(
+                "dir-load_path_explicit_file_extension__b"
+            ))
+        )
+        .unwrap()
+    );
+}
+
 #[test]
 fn load_path_same_directory() {
     tempfile!(
@@ -664,3 +689,56 @@ error!(
 // todo: test for absolute paths (how?)
 // todo: test for @import accessing things declared beforehand
 // e.g. b { @import } | $a: red; @import
+
+#[test]
+fn import_loop() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"@import "b";"#);
+    fs.add_file("_b.scss", r#"@import "a";"#);
+
+    let input = r#"
+        @import "a";
+    "#;
+
+    assert_err!(
+        input,
+        "Error: This file is already being loaded.",
+        grass::Options::default().fs(&fs)
+    );
+}
+
+#[test]
+fn import_self_loop() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"@import "a";"#);
+
+    let input = r#"
+        @import "a";
+    "#;
+
+    assert_err!(
+        input,
+        "Error: This file is already being loaded.",
+        grass::Options::default().fs(&fs)
+    );
+}
+
+#[test]
+fn import_loop_through_use() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"@use "b";"#);
+    fs.add_file("_b.scss", r#"@import "a";"#);
+
+    let input = r#"
+        @import "a";
+    "#;
+
+    assert_err!(
+        input,
+        "Error: This file is already being loaded.",
+        grass::Options::default().fs(&fs)
+    );
+}
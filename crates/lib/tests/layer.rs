@@ -0,0 +1,226 @@
+#[macro_use]
+mod macros;
+
+test!(statement_single_name, "@layer a;", "@layer a;\n");
+test!(
+    statement_multiple_names,
+    "@layer a  ,b,   c.d;",
+    "@layer a, b, c.d;\n"
+);
+test!(
+    statement_compressed,
+    "@layer a, b;",
+    "@layer a,b;",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    block_named,
+    "@layer a {
+        b {
+            c: d;
+        }
+    }",
+    "@layer a {\n  b {\n    c: d;\n  }\n}\n"
+);
+test!(
+    block_anonymous,
+    "@layer {
+        b {
+            c: d;
+        }
+    }",
+    "@layer {\n  b {\n    c: d;\n  }\n}\n"
+);
+test!(block_empty_is_emitted, "@layer a {}", "@layer a {}\n");
+test!(
+    name_is_interpolated,
+    "$name: foo;
+    @layer #{$name}.bar {
+        a {
+            b: c;
+        }
+    }",
+    "@layer foo.bar {\n  a {\n    b: c;\n  }\n}\n"
+);
+test!(
+    bubbles_out_of_style_rule,
+    "a {
+        @layer b {
+            c: d;
+        }
+    }",
+    "@layer b {\n  a {\n    c: d;\n  }\n}\n"
+);
+test!(
+    statement_bubbles_out_of_style_rule,
+    "a {
+        b: c;
+        @layer d, e;
+    }",
+    "a {\n  b: c;\n}\n@layer d, e;\n"
+);
+test!(
+    nested_named_layers_merge,
+    "@layer a {
+        @layer b {
+            c {
+                d: e;
+            }
+        }
+    }",
+    "@layer a {}\n@layer a.b {\n  c {\n    d: e;\n  }\n}\n"
+);
+test!(
+    nested_named_layers_merge_through_style_rule,
+    "@layer a {
+        b {
+            @layer c {
+                d: e;
+            }
+        }
+    }",
+    "@layer a {}\n@layer a.c {\n  b {\n    d: e;\n  }\n}\n"
+);
+test!(
+    nested_layers_merge_transitively,
+    "@layer a {
+        @layer b {
+            @layer c {
+                d {
+                    e: f;
+                }
+            }
+        }
+    }",
+    "@layer a {}\n@layer a.b {}\n@layer a.b.c {\n  d {\n    e: f;\n  }\n}\n"
+);
+test!(
+    merged_layer_preserves_order_of_siblings,
+    "@layer a {
+        b {
+            c: d;
+        }
+
+        @layer e {
+            f {
+                g: h;
+            }
+        }
+
+        i {
+            j: k;
+        }
+    }",
+    "@layer a {\n  b {\n    c: d;\n  }\n}\n@layer a.e {\n  f {\n    g: h;\n  }\n}\n@layer a {\n  i {\n    j: k;\n  }\n}\n"
+);
+test!(
+    nested_in_anonymous_layer_does_not_merge,
+    "@layer {
+        @layer a {
+            b {
+                c: d;
+            }
+        }
+    }",
+    "@layer {\n  @layer a {\n    b {\n      c: d;\n    }\n  }\n}\n"
+);
+test!(
+    anonymous_in_named_layer_does_not_merge,
+    "@layer a {
+        @layer {
+            b {
+                c: d;
+            }
+        }
+    }",
+    "@layer a {\n  @layer {\n    b {\n      c: d;\n    }\n  }\n}\n"
+);
+test!(
+    nested_statement_does_not_merge,
+    "@layer a {
+        @layer b, c;
+    }",
+    "@layer a {\n  @layer b, c;\n}\n"
+);
+test!(
+    does_not_merge_through_media,
+    "@layer a {
+        @media print {
+            @layer b {
+                c {
+                    d: e;
+                }
+            }
+        }
+    }",
+    "@layer a {\n  @media print {\n    @layer b {\n      c {\n        d: e;\n      }\n    }\n  }\n}\n"
+);
+test!(
+    at_root_without_layer,
+    "@layer a {
+        @at-root (without: layer) {
+            b {
+                c: d;
+            }
+        }
+    }",
+    "@layer a {}\nb {\n  c: d;\n}\n"
+);
+test!(
+    declarations_allowed_at_top_level,
+    "@layer a {
+        b: c;
+    }",
+    "@layer a {\n  b: c;\n}\n"
+);
+test!(
+    extend_inside_layer,
+    "@layer a {
+        b {
+            c: d;
+        }
+
+        e {
+            @extend b;
+        }
+    }",
+    "@layer a {\n  b, e {\n    c: d;\n  }\n}\n"
+);
+test!(
+    plain_css,
+    "@layer a {
+        b {
+            c: d;
+        }
+    }",
+    "@layer a {\n  b {\n    c: d;\n  }\n}\n",
+    grass::Options::default().input_syntax(grass::InputSyntax::Css)
+);
+error!(
+    block_multiple_names,
+    "@layer a, b {}", "Error: @layer blocks may only declare a single name."
+);
+error!(statement_no_names, "@layer;", "Error: Expected identifier.");
+error!(
+    name_starts_with_digit,
+    "@layer 1a;", "Error: Expected identifier."
+);
+error!(
+    name_trailing_period,
+    "@layer a.;", "Error: Expected identifier."
+);
+error!(
+    name_trailing_comma,
+    "@layer a,;", "Error: Expected identifier."
+);
+error!(
+    within_nested_declaration,
+    "a {
+        b: {
+            @layer c {
+                d: e;
+            }
+        }
+    }",
+    "Error: This at-rule is not allowed here."
+);
@@ -339,3 +339,103 @@ error!(
     nothing_after_first_comma,
     "$map: (a: b,", "Error: expected \")\"."
 );
+test!(
+    map_get_nested,
+    "@use 'sass:map'; a {b: map.get((c: (d: (e: f))), c, d, e)}",
+    "a {\n  b: f;\n}\n"
+);
+test!(
+    map_get_nested_missing,
+    "@use 'sass:map'; a {b: inspect(map.get((c: (d: e)), c, x, e))}",
+    "a {\n  b: null;\n}\n"
+);
+test!(
+    map_get_nested_not_map,
+    "@use 'sass:map'; a {b: inspect(map.get((c: d), c, d))}",
+    "a {\n  b: null;\n}\n"
+);
+test!(
+    map_has_key_nested,
+    "@use 'sass:map'; a {b: map.has-key((c: (d: (e: f))), c, d, e)}",
+    "a {\n  b: true;\n}\n"
+);
+test!(
+    map_has_key_nested_null_value,
+    "@use 'sass:map'; a {b: map.has-key((c: (d: null)), c, d)}",
+    "a {\n  b: true;\n}\n"
+);
+test!(
+    map_has_key_nested_missing,
+    "@use 'sass:map'; a {b: map.has-key((c: (d: e)), c, e)}",
+    "a {\n  b: false;\n}\n"
+);
+test!(
+    map_merge_nested_keys,
+    "@use 'sass:map'; a {b: inspect(map.merge((c: (d: e, f: g)), c, (d: h, i: j)))}",
+    "a {\n  b: (c: (d: h, f: g, i: j));\n}\n"
+);
+test!(
+    map_merge_nested_creates_maps,
+    "@use 'sass:map'; a {b: inspect(map.merge((c: d), c, e, (f: g)))}",
+    "a {\n  b: (c: (e: (f: g)));\n}\n"
+);
+error!(
+    map_set_missing_value,
+    "@use 'sass:map'; a {b: map.set((c: d), c)}", "Error: Expected $args to contain a value."
+);
+test!(
+    map_deep_merge_nested,
+    "@use 'sass:map';
+    $a: (theme: (primary: blue, secondary: (light: #eee, dark: #333)));
+    $b: (theme: (secondary: (dark: #000), danger: red));
+    a {b: inspect(map.deep-merge($a, $b))}",
+    "a {\n  b: (theme: (primary: blue, secondary: (light: #eee, dark: #000), danger: red));\n}\n"
+);
+test!(
+    map_deep_merge_replaces_non_map,
+    "@use 'sass:map'; a {b: inspect(map.deep-merge((c: d), (c: (e: f))))}",
+    "a {\n  b: (c: (e: f));\n}\n"
+);
+test!(
+    map_deep_merge_map_with_non_map,
+    "@use 'sass:map'; a {b: inspect(map.deep-merge((c: (e: f)), (c: d)))}",
+    "a {\n  b: (c: d);\n}\n"
+);
+test!(
+    map_deep_merge_empty_maps,
+    "@use 'sass:map'; a {b: inspect(map.deep-merge((), (c: d)))}",
+    "a {\n  b: (c: d);\n}\n"
+);
+test!(
+    map_deep_merge_named_args,
+    "@use 'sass:map'; a {b: inspect(map.deep-merge($map1: (c: (d: e)), $map2: (c: (f: g))))}",
+    "a {\n  b: (c: (d: e, f: g));\n}\n"
+);
+error!(
+    map_deep_merge_not_map,
+    "@use 'sass:map'; a {b: map.deep-merge((c: d), 1)}", "Error: $map2: 1 is not a map."
+);
+test!(
+    map_deep_remove_top_level,
+    "@use 'sass:map'; a {b: inspect(map.deep-remove((c: d, e: f), c))}",
+    "a {\n  b: (e: f);\n}\n"
+);
+test!(
+    map_deep_remove_nested,
+    "@use 'sass:map'; a {b: inspect(map.deep-remove((c: (d: (e: f, g: h))), c, d, e))}",
+    "a {\n  b: (c: (d: (g: h)));\n}\n"
+);
+test!(
+    map_deep_remove_missing_path,
+    "@use 'sass:map'; a {b: inspect(map.deep-remove((c: d), x, y))}",
+    "a {\n  b: (c: d);\n}\n"
+);
+test!(
+    map_deep_remove_through_non_map,
+    "@use 'sass:map'; a {b: inspect(map.deep-remove((c: d), c, d))}",
+    "a {\n  b: (c: d);\n}\n"
+);
+error!(
+    map_deep_remove_not_map,
+    "@use 'sass:map'; a {b: map.deep-remove(1, c)}", "Error: $map: 1 is not a map."
+);
@@ -0,0 +1,262 @@
+#[macro_use]
+mod macros;
+
+test!(
+    round_single_argument,
+    "a {\n  color: round(1.5px);\n}\n",
+    "a {\n  color: 2px;\n}\n"
+);
+test!(
+    round_with_step,
+    "a {\n  color: round(10px, 3px);\n}\n",
+    "a {\n  color: 9px;\n}\n"
+);
+test!(
+    round_up_with_step,
+    "a {\n  color: round(up, 10px, 3px);\n}\n",
+    "a {\n  color: 12px;\n}\n"
+);
+test!(
+    round_down_with_step,
+    "a {\n  color: round(down, 11px, 3px);\n}\n",
+    "a {\n  color: 9px;\n}\n"
+);
+test!(
+    round_to_zero_negative,
+    "a {\n  color: round(to-zero, -11px, 3px);\n}\n",
+    "a {\n  color: -9px;\n}\n"
+);
+test!(
+    round_nearest_converts_step,
+    "a {\n  color: round(nearest, 1.1in, 1px);\n}\n",
+    "a {\n  color: 1.1041666667in;\n}\n"
+);
+test!(
+    round_strategy_is_case_insensitive,
+    "a {\n  color: round(UP, 10px, 3px);\n}\n",
+    "a {\n  color: 12px;\n}\n"
+);
+test!(
+    round_var_is_unsimplified,
+    "a {\n  color: round(up, var(--x), 3px);\n}\n",
+    "a {\n  color: round(up, var(--x), 3px);\n}\n"
+);
+test!(
+    round_incomparable_units_unsimplified,
+    "a {\n  color: round(10%, 3px);\n}\n",
+    "a {\n  color: round(10%, 3px);\n}\n"
+);
+error!(
+    round_strategy_without_step,
+    "a {\n  color: round(up, 10px);\n}\n", "Error: If strategy is not null, step is required."
+);
+error!(
+    round_invalid_strategy,
+    "a {\n  color: round(foo, 10px, 3px);\n}\n",
+    "Error: foo must be either nearest, up, down or to-zero."
+);
+error!(
+    round_incompatible_units,
+    "a {\n  color: round(10px, 3s);\n}\n", "Error: 10px and 3s are incompatible."
+);
+test!(
+    mod_positive,
+    "a {\n  color: mod(10px, 3px);\n}\n",
+    "a {\n  color: 1px;\n}\n"
+);
+test!(
+    mod_takes_sign_of_divisor,
+    "a {\n  color: mod(-10px, 3px);\n}\n",
+    "a {\n  color: 2px;\n}\n"
+);
+test!(
+    rem_takes_sign_of_dividend,
+    "a {\n  color: rem(-10px, 3px);\n}\n",
+    "a {\n  color: -1px;\n}\n"
+);
+test!(
+    mod_converts_units,
+    "a {\n  color: mod(1in, 5px);\n}\n",
+    "a {\n  color: 0.0104166667in;\n}\n"
+);
+test!(
+    mod_with_var,
+    "a {\n  color: mod(var(--x), 5px);\n}\n",
+    "a {\n  color: mod(var(--x), 5px);\n}\n"
+);
+error!(
+    mod_unitless_and_unit,
+    "a {\n  color: mod(10px, 3);\n}\n", "Error: 10px and 3 are incompatible."
+);
+error!(
+    mod_single_argument,
+    "a {\n  color: mod(10px);\n}\n", "Error: 2 arguments required, but only 1 was passed."
+);
+test!(
+    sin_unitless,
+    "a {\n  color: sin(0);\n}\n",
+    "a {\n  color: 0;\n}\n"
+);
+test!(
+    sin_deg,
+    "a {\n  color: sin(90deg);\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    cos_turn,
+    "a {\n  color: cos(0.5turn);\n}\n",
+    "a {\n  color: -1;\n}\n"
+);
+test!(
+    tan_rad,
+    "a {\n  color: tan(0rad);\n}\n",
+    "a {\n  color: 0;\n}\n"
+);
+error!(
+    sin_non_angle,
+    "a {\n  color: sin(1px);\n}\n",
+    "Error: Expected 1px to have an angle unit (deg, grad, rad, turn)."
+);
+test!(
+    asin_returns_deg,
+    "a {\n  color: asin(1);\n}\n",
+    "a {\n  color: 90deg;\n}\n"
+);
+test!(
+    acos_returns_deg,
+    "a {\n  color: acos(-1);\n}\n",
+    "a {\n  color: 180deg;\n}\n"
+);
+test!(
+    atan_returns_deg,
+    "a {\n  color: atan(1);\n}\n",
+    "a {\n  color: 45deg;\n}\n"
+);
+error!(
+    asin_with_unit,
+    "a {\n  color: asin(1px);\n}\n", "Error: Expected 1px to have no units."
+);
+test!(
+    atan2_same_units,
+    "a {\n  color: atan2(1px, -1px);\n}\n",
+    "a {\n  color: 135deg;\n}\n"
+);
+test!(
+    atan2_converts_units,
+    "a {\n  color: atan2(1in, 96px);\n}\n",
+    "a {\n  color: 45deg;\n}\n"
+);
+test!(
+    pow_unitless,
+    "a {\n  color: pow(2, 10);\n}\n",
+    "a {\n  color: 1024;\n}\n"
+);
+error!(
+    pow_with_unit,
+    "a {\n  color: pow(2px, 10);\n}\n", "Error: Expected 2px to have no units."
+);
+test!(
+    sqrt_unitless,
+    "a {\n  color: sqrt(16);\n}\n",
+    "a {\n  color: 4;\n}\n"
+);
+test!(
+    hypot_same_units,
+    "a {\n  color: hypot(3px, 4px);\n}\n",
+    "a {\n  color: 5px;\n}\n"
+);
+test!(
+    hypot_converts_to_first_unit,
+    "a {\n  color: hypot(1in, 72pt);\n}\n",
+    "a {\n  color: 1.4142135624in;\n}\n"
+);
+test!(
+    hypot_with_percent_unsimplified,
+    "a {\n  color: hypot(3px, 4%);\n}\n",
+    "a {\n  color: hypot(3px, 4%);\n}\n"
+);
+test!(
+    log_natural,
+    "a {\n  color: log(1);\n}\n",
+    "a {\n  color: 0;\n}\n"
+);
+test!(
+    log_with_base,
+    "a {\n  color: log(8, 2);\n}\n",
+    "a {\n  color: 3;\n}\n"
+);
+test!(
+    exp_zero,
+    "a {\n  color: exp(0);\n}\n",
+    "a {\n  color: 1;\n}\n"
+);
+test!(
+    abs_keeps_unit,
+    "a {\n  color: abs(-10px);\n}\n",
+    "a {\n  color: 10px;\n}\n"
+);
+test!(
+    abs_with_var,
+    "a {\n  color: abs(var(--x));\n}\n",
+    "a {\n  color: abs(var(--x));\n}\n"
+);
+test!(
+    sign_negative,
+    "a {\n  color: sign(-10px);\n}\n",
+    "a {\n  color: -1px;\n}\n"
+);
+test!(
+    sign_percent_unsimplified,
+    "a {\n  color: sign(10%);\n}\n",
+    "a {\n  color: sign(10%);\n}\n"
+);
+test!(
+    nested_in_calc,
+    "a {\n  color: calc(1px + abs(-2px));\n}\n",
+    "a {\n  color: 3px;\n}\n"
+);
+test!(
+    interpolation_is_unsimplified,
+    "a {\n  color: sin(#{\"1\" + \"0deg\"});\n}\n",
+    "a {\n  color: sin(10deg);\n}\n"
+);
+test!(
+    operation_with_var_is_unsimplified,
+    "a {\n  color: pow(var(--x) + 1, 2);\n}\n",
+    "a {\n  color: pow(var(--x) + 1, 2);\n}\n"
+);
+test!(
+    user_defined_function_takes_precedence,
+    "@function pow($a, $b) {\n  @return $a + $b;\n}\n\na {\n  color: pow(2, 3);\n}\n",
+    "a {\n  color: 5;\n}\n"
+);
+test!(
+    sass_arguments_fall_back_to_function,
+    "@function sin($args...) {\n  @return length($args);\n}\n\na {\n  color: sin(1, 2, 3...);\n}\n",
+    "a {\n  color: 3;\n}\n"
+);
+test!(
+    abs_legacy_unit_arithmetic,
+    "a {\n  color: abs(-10px + 5);\n}\n",
+    "a {\n  color: 5px;\n}\n"
+);
+test!(
+    calc_name,
+    "@use \"sass:meta\";\n\na {\n  color: meta.calc-name(round(up, var(--x), 3px));\n}\n",
+    "a {\n  color: \"round\";\n}\n"
+);
+test!(
+    calc_args,
+    "@use \"sass:meta\";\n\na {\n  color: meta.calc-args(mod(var(--x), 3px));\n}\n",
+    "a {\n  color: var(--x), 3px;\n}\n"
+);
+test!(
+    calc_args_with_strategy,
+    "@use \"sass:meta\";\n\na {\n  color: meta.calc-args(round(up, var(--x), 3px));\n}\n",
+    "a {\n  color: up, var(--x), 3px;\n}\n"
+);
+test!(
+    type_of_unsimplified,
+    "@use \"sass:meta\";\n\na {\n  color: meta.type-of(sign(var(--x)));\n}\n",
+    "a {\n  color: calculation;\n}\n"
+);
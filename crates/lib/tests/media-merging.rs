@@ -0,0 +1,90 @@
+use grass::MediaMerging;
+
+#[macro_use]
+mod macros;
+
+fn merge() -> grass::Options<'static> {
+    grass::Options::default().media_merging(MediaMerging::Merge)
+}
+
+fn mobile_first() -> grass::Options<'static> {
+    grass::Options::default().media_merging(MediaMerging::MobileFirst)
+}
+
+test!(
+    merges_identical_queries_at_last_occurrence,
+    "@media (min-width: 768px) {\n  .a {\n    color: red;\n  }\n}\n.b {\n  margin: 0;\n}\n@media (min-width: 768px) {\n  .c {\n    color: blue;\n  }\n}\n",
+    ".b {\n  margin: 0;\n}\n\n@media (min-width: 768px) {\n  .a {\n    color: red;\n  }\n  .c {\n    color: blue;\n  }\n}\n",
+    merge()
+);
+test!(
+    merges_queries_from_mixins,
+    "@mixin md {\n  @media (min-width: 768px) {\n    @content;\n  }\n}\n.card {\n  padding: 1px;\n  @include md {\n    padding: 2px;\n  }\n}\n.title {\n  font-size: 1em;\n  @include md {\n    font-size: 2em;\n  }\n}\n",
+    ".card {\n  padding: 1px;\n}\n\n.title {\n  font-size: 1em;\n}\n@media (min-width: 768px) {\n  .card {\n    padding: 2px;\n  }\n  .title {\n    font-size: 2em;\n  }\n}\n",
+    merge()
+);
+test!(
+    does_not_merge_past_conflicting_rule,
+    "@media (min-width: 768px) {\n  .a {\n    color: red;\n  }\n}\n.a {\n  color: blue;\n}\n@media (min-width: 768px) {\n  .b {\n    color: green;\n  }\n}\n",
+    "@media (min-width: 768px) {\n  .a {\n    color: red;\n  }\n}\n.a {\n  color: blue;\n}\n\n@media (min-width: 768px) {\n  .b {\n    color: green;\n  }\n}\n",
+    merge()
+);
+test!(
+    does_not_merge_past_shorthand_property,
+    "@media print {\n  .a {\n    margin-top: 0;\n  }\n}\n.b {\n  margin: 1px;\n}\n@media print {\n  .c {\n    color: red;\n  }\n}\n",
+    "@media print {\n  .a {\n    margin-top: 0;\n  }\n}\n.b {\n  margin: 1px;\n}\n\n@media print {\n  .c {\n    color: red;\n  }\n}\n",
+    merge()
+);
+test!(
+    merges_past_rule_with_different_specificity,
+    "@media print {\n  .a {\n    color: red;\n  }\n}\n#b {\n  color: blue;\n}\n@media print {\n  .c {\n    color: green;\n  }\n}\n",
+    "#b {\n  color: blue;\n}\n\n@media print {\n  .a {\n    color: red;\n  }\n  .c {\n    color: green;\n  }\n}\n",
+    merge()
+);
+test!(
+    merges_past_rule_for_different_element,
+    "@media print {\n  a {\n    color: red;\n  }\n}\nb {\n  color: blue;\n}\n@media print {\n  c {\n    color: green;\n  }\n}\n",
+    "b {\n  color: blue;\n}\n\n@media print {\n  a {\n    color: red;\n  }\n  c {\n    color: green;\n  }\n}\n",
+    merge()
+);
+test!(
+    merges_into_unmovable_occurrence,
+    "@media print {\n  .a {\n    color: red;\n  }\n}\n@media print {\n  .b {\n    color: red;\n  }\n}\n.b {\n  color: blue;\n}\n@media print {\n  .c {\n    color: green;\n  }\n}\n",
+    "@media print {\n  .a {\n    color: red;\n  }\n  .b {\n    color: red;\n  }\n}\n.b {\n  color: blue;\n}\n\n@media print {\n  .c {\n    color: green;\n  }\n}\n",
+    merge()
+);
+test!(
+    does_not_merge_different_queries,
+    "@media print {\n  a {\n    color: red;\n  }\n}\n@media screen {\n  a {\n    color: blue;\n  }\n}\n",
+    "@media print {\n  a {\n    color: red;\n  }\n}\n@media screen {\n  a {\n    color: blue;\n  }\n}\n",
+    merge()
+);
+test!(
+    merges_within_supports_rule,
+    "@supports (display: grid) {\n  @media print {\n    a {\n      color: red;\n    }\n  }\n  b {\n    margin: 0;\n  }\n  @media print {\n    c {\n      color: blue;\n    }\n  }\n}\n",
+    "@supports (display: grid) {\n  b {\n    margin: 0;\n  }\n  @media print {\n    a {\n      color: red;\n    }\n    c {\n      color: blue;\n    }\n  }\n}\n",
+    merge()
+);
+test!(
+    mobile_first_sorts_min_width_queries,
+    "@media (min-width: 1024px) {\n  .a {\n    color: red;\n  }\n}\n.b {\n  margin: 0;\n}\n@media (min-width: 768px) {\n  .c {\n    margin: 1px;\n  }\n}\n@media (min-width: 48em) {\n  .d {\n    padding: 1px;\n  }\n}\n",
+    ".b {\n  margin: 0;\n}\n\n@media (min-width: 768px) {\n  .c {\n    margin: 1px;\n  }\n}\n\n@media (min-width: 48em) {\n  .d {\n    padding: 1px;\n  }\n}\n\n@media (min-width: 1024px) {\n  .a {\n    color: red;\n  }\n}\n",
+    mobile_first()
+);
+test!(
+    mobile_first_supports_range_syntax,
+    "@media (width >= 1024px) {\n  .a {\n    color: red;\n  }\n}\n@media screen and (min-width: 768px) {\n  .b {\n    margin: 1px;\n  }\n}\n",
+    "@media screen and (min-width: 768px) {\n  .b {\n    margin: 1px;\n  }\n}\n\n@media (width >= 1024px) {\n  .a {\n    color: red;\n  }\n}\n",
+    mobile_first()
+);
+test!(
+    mobile_first_does_not_sort_conflicting_queries,
+    "@media (min-width: 1024px) {\n  .a {\n    color: red;\n  }\n}\n@media (min-width: 768px) {\n  .a {\n    color: blue;\n  }\n}\n",
+    "@media (min-width: 1024px) {\n  .a {\n    color: red;\n  }\n}\n@media (min-width: 768px) {\n  .a {\n    color: blue;\n  }\n}\n",
+    mobile_first()
+);
+test!(
+    preserves_media_rules_by_default,
+    "@media print {\n  a {\n    color: red;\n  }\n}\n@media print {\n  b {\n    color: blue;\n  }\n}\n",
+    "@media print {\n  a {\n    color: red;\n  }\n}\n@media print {\n  b {\n    color: blue;\n  }\n}\n"
+);
@@ -630,3 +630,181 @@ error!(
     empty_query_after_resolving_interpolation,
     "@media #{null} {}", "Error: Expected identifier."
 );
+test!(
+    range_is_emitted_as_written,
+    "@media (width >= 600px) {
+        a {
+            color: red;
+        }
+    }",
+    "@media (width >= 600px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    range_with_value_first,
+    "@media (600px < width) {
+        a {
+            color: red;
+        }
+    }",
+    "@media (600px < width) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    descending_double_range_is_emitted_as_written,
+    "@media (800px > width > 400px) {
+        a {
+            color: red;
+        }
+    }",
+    "@media (800px > width > 400px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    descending_double_range_with_inclusive_bound_is_emitted_as_written,
+    "@media (600px > width >= 400px) {
+        a {
+            color: red;
+        }
+    }",
+    "@media (600px > width >= 400px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_ranges_on_same_feature_are_merged,
+    "@media (width < 1200px) {
+        @media (width >= 600px) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media (600px <= width < 1200px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_range_narrows_double_range,
+    "@media (400px < width < 800px) {
+        @media (width > 600px) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media (600px < width < 800px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_range_narrows_descending_double_range,
+    "@media (800px > width > 400px) {
+        @media (width > 600px) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media (800px > width > 600px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_ranges_meeting_at_inclusive_bounds_merge_to_equality,
+    "@media (width <= 600px) {
+        @media (width >= 600px) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media (width = 600px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_ranges_with_variable_bound,
+    "$breakpoint: 600px;
+    @media (width >= $breakpoint) {
+        @media (width = 700px) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media (width = 700px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    disjoint_nested_ranges_are_removed,
+    "@media (width < 600px) {
+        @media (width >= 800px) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    ""
+);
+test!(
+    nested_ranges_meeting_at_exclusive_bound_are_removed,
+    "@media (width < 600px) {
+        @media (width >= 600px) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    ""
+);
+test!(
+    nested_ranges_with_different_units_are_not_merged,
+    "@media screen and (width < 50em) {
+        @media (width > 600px) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media screen and (width < 50em) and (width > 600px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_ranges_on_different_features_are_not_merged,
+    "@media (1px < width) {
+        @media (height > 2px) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media (1px < width) and (height > 2px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_or_condition_is_merged,
+    "@media (hover) or (pointer: fine) {
+        @media (width > 600px) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media ((hover) or (pointer: fine)) and (width > 600px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_or_condition_is_merged_with_type,
+    "@media screen {
+        @media (hover) or (color) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media screen and ((hover) or (color)) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_not_condition_is_merged,
+    "@media not (color) {
+        @media (width > 1px) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media (not (color)) and (width > 1px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_boolean_logic,
+    "@media ((hover) and (color)) or (not (width > 1px)) {
+        a {
+            color: red;
+        }
+    }",
+    "@media ((hover) and (color)) or (not (width > 1px)) {\n  a {\n    color: red;\n  }\n}\n"
+);
@@ -1,5 +1,7 @@
 use std::io::Write;
 
+use macros::TestFs;
+
 #[macro_use]
 mod macros;
 
@@ -72,3 +74,246 @@ fn load_css_non_map_with() {
     let input = "@use \"sass:meta\";\na {\n @include meta.load-css(foo, 2);\n}";
     assert_err!("Error: $with: 2 is not a map.", input);
 }
+
+#[test]
+fn load_css_with() {
+    let mut fs = TestFs::new();
+
+    fs.add_file(
+        "_theme.scss",
+        r#"$primary: blue !default; .btn { color: $primary; }"#,
+    );
+
+    let input = r#"
+        @use "sass:meta";
+        .dark {
+            @include meta.load-css("theme", $with: (primary: red));
+        }
+    "#;
+
+    assert_eq!(
+        ".dark .btn {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn load_css_with_interpolated_url() {
+    let mut fs = TestFs::new();
+
+    fs.add_file(
+        "themes/_dark.scss",
+        r#"$primary: blue !default; .btn { color: $primary; }"#,
+    );
+
+    let input = r#"
+        @use "sass:meta";
+        $name: dark;
+        $brand: green;
+        .theme {
+            @include meta.load-css("themes/#{$name}", $with: (primary: $brand));
+        }
+    "#;
+
+    assert_eq!(
+        ".theme .btn {\n  color: green;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn load_css_with_underscore_key() {
+    let mut fs = TestFs::new();
+
+    fs.add_file(
+        "_theme.scss",
+        r#"$primary-color: blue !default; a { color: $primary-color; }"#,
+    );
+
+    let input = r#"
+        @use "sass:meta";
+        @include meta.load-css("theme", $with: (primary_color: red));
+    "#;
+
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn load_css_with_variable_not_default() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_theme.scss", r#"$primary: blue; a { color: $primary; }"#);
+
+    let input = r#"
+        @use "sass:meta";
+        @include meta.load-css("theme", $with: (primary: red));
+    "#;
+
+    assert_err!(
+        input,
+        "Error: $primary was not declared with !default in the @used module.",
+        grass::Options::default().fs(&fs)
+    );
+}
+
+#[test]
+fn load_css_with_undeclared_variable() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_theme.scss", r#"a { color: red; }"#);
+
+    let input = r#"
+        @use "sass:meta";
+        @include meta.load-css("theme", $with: (primary: red));
+    "#;
+
+    assert_err!(
+        input,
+        "Error: $primary was not declared with !default in the @used module.",
+        grass::Options::default().fs(&fs)
+    );
+}
+
+#[test]
+fn load_css_with_variable_configured_twice() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_theme.scss", r#"$a-b: blue !default;"#);
+
+    let input = r#"
+        @use "sass:meta";
+        @include meta.load-css("theme", $with: (a-b: red, a_b: green));
+    "#;
+
+    assert_err!(
+        input,
+        "Error: The variable $a-b was configured twice.",
+        grass::Options::default().fs(&fs)
+    );
+}
+
+#[test]
+fn load_css_with_builtin_module() {
+    let input = r#"
+        @use "sass:meta";
+        @include meta.load-css("sass:color", $with: (a: red));
+    "#;
+
+    assert_err!(
+        "Error: Built-in module sass:color can't be configured.",
+        input
+    );
+}
+
+#[test]
+fn load_css_with_already_loaded_module() {
+    let mut fs = TestFs::new();
+
+    fs.add_file(
+        "_theme.scss",
+        r#"$primary: blue !default; a { color: $primary; }"#,
+    );
+
+    let input = r#"
+        @use "sass:meta";
+        @use "theme";
+        @include meta.load-css("theme", $with: (primary: red));
+    "#;
+
+    assert_err!(
+        input,
+        "Error: _theme.scss was already loaded, so it can't be configured using \"with\".",
+        grass::Options::default().fs(&fs)
+    );
+}
+
+#[test]
+fn load_css_twice_with_configuration() {
+    let mut fs = TestFs::new();
+
+    fs.add_file(
+        "_theme.scss",
+        r#"$primary: blue !default; a { color: $primary; }"#,
+    );
+
+    let input = r#"
+        @use "sass:meta";
+        @include meta.load-css("theme", $with: (primary: red));
+        @include meta.load-css("theme", $with: (primary: green));
+    "#;
+
+    assert_err!(
+        input,
+        "Error: _theme.scss was already loaded, so it can't be configured using \"with\".",
+        grass::Options::default().fs(&fs)
+    );
+}
+
+#[test]
+fn load_css_already_loaded_module_reuses_configuration() {
+    let mut fs = TestFs::new();
+
+    fs.add_file(
+        "_theme.scss",
+        r#"$primary: blue !default; a { color: $primary; }"#,
+    );
+
+    let input = r#"
+        @use "sass:meta";
+        @use "theme" with ($primary: red);
+        b {
+            @include meta.load-css("theme");
+        }
+    "#;
+
+    assert_eq!(
+        "a {\n  color: red;\n}\n\nb a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn load_css_twice_emits_css_twice() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_theme.scss", r#"a { color: red; }"#);
+
+    let input = r#"
+        @use "sass:meta";
+        b {
+            @include meta.load-css("theme");
+        }
+        c {
+            @include meta.load-css("theme");
+        }
+    "#;
+
+    assert_eq!(
+        "b a {\n  color: red;\n}\n\nc a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn load_css_module_loop() {
+    let mut fs = TestFs::new();
+
+    fs.add_file(
+        "_theme.scss",
+        r#"@use "sass:meta"; @include meta.load-css("theme");"#,
+    );
+
+    let input = r#"
+        @use "sass:meta";
+        @include meta.load-css("theme");
+    "#;
+
+    assert_err!(
+        input,
+        "Error: Module loop: _theme.scss is already being loaded.",
+        grass::Options::default().fs(&fs)
+    );
+}
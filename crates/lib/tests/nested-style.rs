@@ -0,0 +1,77 @@
+#[macro_use]
+mod macros;
+
+test!(
+    nested_simple_rule,
+    "a {\n  color: red;\n}\n",
+    "a {\n  color: red; }\n",
+    grass::Options::default().style(grass::OutputStyle::Nested)
+);
+test!(
+    nested_rule_with_many_styles,
+    "a {\n  color: red;\n  color: green;\n  color: blue;\n}\n",
+    "a {\n  color: red;\n  color: green;\n  color: blue; }\n",
+    grass::Options::default().style(grass::OutputStyle::Nested)
+);
+test!(
+    nested_indents_by_source_depth,
+    "a {\n  color: red;\n  b {\n    color: blue;\n    c {\n      d: e;\n    }\n  }\n}\n",
+    "a {\n  color: red; }\n  a b {\n    color: blue; }\n    a b c {\n      d: e; }\n",
+    grass::Options::default().style(grass::OutputStyle::Nested)
+);
+test!(
+    nested_separates_groups,
+    "a {\n  b {\n    color: red;\n  }\n}\nc {\n  color: blue;\n}\n",
+    "a b {\n  color: red; }\n\nc {\n  color: blue; }\n",
+    grass::Options::default().style(grass::OutputStyle::Nested)
+);
+test!(
+    nested_media_rule,
+    "@media foo {\n  a {\n    color: red;\n  }\n}\n",
+    "@media foo {\n  a {\n    color: red; } }\n",
+    grass::Options::default().style(grass::OutputStyle::Nested)
+);
+test!(
+    nested_media_rule_within_style_rule,
+    "a {\n  b {\n    @media foo {\n      color: red;\n    }\n  }\n}\n",
+    "@media foo {\n  a b {\n    color: red; } }\n",
+    grass::Options::default().style(grass::OutputStyle::Nested)
+);
+test!(
+    nested_media_rule_indented_by_source_depth,
+    "a {\n  color: red;\n  @media foo {\n    color: blue;\n  }\n}\n",
+    "a {\n  color: red; }\n  @media foo {\n    a {\n      color: blue; } }\n",
+    grass::Options::default().style(grass::OutputStyle::Nested)
+);
+test!(
+    nested_at_rules_indented_by_source_depth,
+    "a {\n  color: red;\n  b {\n    color: blue;\n    @supports (c: d) {\n      e: f;\n    }\n    @foo {\n      g: h;\n    }\n  }\n}\n",
+    "a {\n  color: red; }\n  a b {\n    color: blue; }\n    @supports (c: d) {\n      a b {\n        e: f; } }\n    @foo {\n      a b {\n        g: h; } }\n",
+    grass::Options::default().style(grass::OutputStyle::Nested)
+);
+test!(
+    nested_rule_within_media_rule,
+    "@media foo {\n  a {\n    color: red;\n    b {\n      color: blue;\n    }\n  }\n}\n",
+    "@media foo {\n  a {\n    color: red; }\n    a b {\n      color: blue; } }\n",
+    grass::Options::default().style(grass::OutputStyle::Nested)
+);
+test!(
+    nested_keyframes,
+    "@keyframes foo {\n  from {\n    a: b;\n  }\n  to {\n    a: c;\n  }\n}\n",
+    "@keyframes foo {\n  from {\n    a: b; }\n  to {\n    a: c; } }\n",
+    grass::Options::default().style(grass::OutputStyle::Nested)
+);
+test!(
+    nested_comment_within_rule,
+    "a {\n  /* foo */\n  color: red;\n}\n",
+    "a {\n  /* foo */\n  color: red; }\n",
+    grass::Options::default().style(grass::OutputStyle::Nested)
+);
+test!(
+    nested_preserved_css_nesting_is_not_indented_twice,
+    "a {\n  color: red;\n  b {\n    color: blue;\n  }\n}\n",
+    "a {\n  color: red;\n  b {\n    color: blue; } }\n",
+    grass::Options::default()
+        .style(grass::OutputStyle::Nested)
+        .nesting(grass::Nesting::Preserve)
+);
@@ -0,0 +1,320 @@
+use grass::Nesting;
+
+#[macro_use]
+mod macros;
+
+fn preserve() -> grass::Options<'static> {
+    grass::Options::default().nesting(Nesting::Preserve)
+}
+
+test!(
+    preserves_parent_and_descendant,
+    ".a {
+        color: red;
+        &:hover {
+            color: blue;
+        }
+        .b {
+            c: d;
+        }
+    }",
+    ".a {\n  color: red;\n  &:hover {\n    color: blue;\n  }\n  .b {\n    c: d;\n  }\n}\n",
+    preserve()
+);
+test!(
+    preserves_leading_combinator_and_trailing_parent,
+    ".a {
+        > .b {
+            c: d;
+        }
+        + .e {
+            f: g;
+        }
+        .x & {
+            h: i;
+        }
+    }",
+    ".a {\n  > .b {\n    c: d;\n  }\n  + .e {\n    f: g;\n  }\n  .x & {\n    h: i;\n  }\n}\n",
+    preserve()
+);
+test!(
+    preserves_rules_from_mixin,
+    "@mixin m {
+        &:hover {
+            a: b;
+        }
+    }
+    .c {
+        @include m;
+    }",
+    ".c {\n  &:hover {\n    a: b;\n  }\n}\n",
+    preserve()
+);
+test!(
+    parent_selector_in_sassscript_is_resolved,
+    ".a {
+        .b {
+            c: &;
+        }
+    }",
+    ".a {\n  .b {\n    c: .a .b;\n  }\n}\n",
+    preserve()
+);
+test!(
+    flattens_parent_suffix,
+    ".a {
+        b: c;
+        &-d {
+            e: f;
+        }
+        g: h;
+    }",
+    ".a {\n  b: c;\n  g: h;\n}\n.a-d {\n  e: f;\n}\n",
+    preserve()
+);
+test!(
+    flattens_parent_suffix_in_pseudo,
+    ".a {
+        :not(&-b) {
+            c: d;
+        }
+    }",
+    ":not(.a-b) {\n  c: d;\n}\n",
+    preserve()
+);
+test!(
+    flattens_children_of_trailing_combinator,
+    ".a {
+        .b > {
+            .c {
+                d: e;
+            }
+        }
+    }",
+    ".a .b > .c {\n  d: e;\n}\n",
+    preserve()
+);
+test!(
+    flattens_at_root,
+    ".a {
+        @at-root .b {
+            c: d;
+        }
+    }",
+    ".b {\n  c: d;\n}\n",
+    preserve()
+);
+test!(
+    preserves_nesting_within_at_root,
+    ".a {
+        @at-root {
+            .b & {
+                c: d;
+                .e {
+                    f: g;
+                }
+            }
+        }
+    }",
+    ".b .a {\n  c: d;\n  .e {\n    f: g;\n  }\n}\n",
+    preserve()
+);
+test!(
+    flattens_extended_rule,
+    ".a {
+        c: d;
+        .b {
+            e: f;
+        }
+    }
+    .x {
+        @extend .b;
+    }",
+    ".a {\n  c: d;\n}\n.a .b, .a .x {\n  e: f;\n}\n",
+    preserve()
+);
+test!(
+    flattens_rule_extended_before_it_is_defined,
+    ".x {
+        @extend .b;
+    }
+    .a {
+        .b {
+            e: f;
+        }
+    }",
+    ".a .b, .a .x {\n  e: f;\n}\n",
+    preserve()
+);
+test!(
+    flattens_rule_with_extended_parent,
+    ".a {
+        .b {
+            c: d;
+        }
+    }
+    .x {
+        @extend .a;
+    }",
+    ".a .b, .x .b {\n  c: d;\n}\n",
+    preserve()
+);
+test!(
+    flattens_deeply_nested_extended_rule,
+    ".a {
+        .b {
+            .c {
+                d: e;
+            }
+        }
+    }
+    .f {
+        @extend .c;
+    }",
+    ".a .b .c, .a .b .f {\n  d: e;\n}\n",
+    preserve()
+);
+test!(
+    flattens_extended_placeholder,
+    "%p {
+        .b {
+            c: d;
+        }
+    }
+    .x {
+        @extend %p;
+    }",
+    ".x .b {\n  c: d;\n}\n",
+    preserve()
+);
+test!(
+    extended_rule_keeps_enclosing_media,
+    ".a {
+        @media print {
+            .b {
+                c: d;
+            }
+        }
+    }
+    .x {
+        @extend .b;
+    }",
+    "@media print {\n  .a .b, .a .x {\n    c: d;\n  }\n}\n",
+    preserve()
+);
+test!(
+    preserves_nested_media_without_merging,
+    "@media screen {
+        .a {
+            @media (min-width: 1px) {
+                b: c;
+            }
+        }
+    }",
+    "@media screen {\n  .a {\n    @media (min-width: 1px) {\n      b: c;\n    }\n  }\n}\n",
+    preserve()
+);
+test!(
+    preserves_nested_supports_and_container,
+    ".a {
+        @supports (b: c) {
+            d: e;
+        }
+        @container (min-width: 1px) {
+            f: g;
+        }
+    }",
+    ".a {\n  @supports (b: c) {\n    d: e;\n  }\n  @container (min-width: 1px) {\n    f: g;\n  }\n}\n",
+    preserve()
+);
+test!(
+    keyframes_still_bubble,
+    ".a {
+        @keyframes b {
+            to {
+                c: d;
+            }
+        }
+    }",
+    "@keyframes b {\n  to {\n    c: d;\n  }\n}\n",
+    preserve()
+);
+test!(
+    unknown_at_rule_still_bubbles,
+    ".a {
+        @font-face {
+            b: c;
+        }
+    }",
+    "@font-face {\n  .a {\n    b: c;\n  }\n}\n",
+    preserve()
+);
+test!(
+    preserves_compressed,
+    ".a {
+        b: c;
+        &:hover {
+            d: e;
+        }
+    }",
+    ".a{b:c;&:hover{d:e}}",
+    preserve().style(grass::OutputStyle::Compressed)
+);
+test!(
+    flattens_within_list_of_mixed_specificity,
+    ".a, #x {
+        .b {
+            c: d;
+        }
+    }",
+    ".a .b, #x .b {\n  c: d;\n}\n",
+    preserve()
+);
+test!(
+    flattens_media_within_list_of_mixed_specificity,
+    ".a, #x {
+        @media screen {
+            c: d;
+        }
+    }",
+    "@media screen {\n  .a, #x {\n    c: d;\n  }\n}\n",
+    preserve()
+);
+test!(
+    preserves_within_list_of_same_specificity,
+    ".a, .x {
+        .b {
+            c: d;
+        }
+    }",
+    ".a, .x {\n  .b {\n    c: d;\n  }\n}\n",
+    preserve()
+);
+test!(
+    preserves_list_of_mixed_specificity_within_parent,
+    ".p {
+        .a, #x {
+            c: d;
+
+            .b {
+                e: f;
+            }
+        }
+    }",
+    ".p {\n  .a, #x {\n    c: d;\n  }\n}\n.p .a .b, .p #x .b {\n  e: f;\n}\n",
+    preserve()
+);
+test!(
+    flattens_within_parent_extended_to_mixed_specificity,
+    ".a {
+        .b {
+            c: d;
+        }
+    }
+
+    #x {
+        @extend .a;
+    }",
+    ".a .b, #x .b {\n  c: d;\n}\n",
+    preserve()
+);
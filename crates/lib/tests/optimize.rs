@@ -0,0 +1,156 @@
+#[macro_use]
+mod macros;
+
+fn optimize() -> grass::Options<'static> {
+    grass::Options::default().optimize(true)
+}
+
+test!(
+    removes_overridden_declaration,
+    "a {\n  color: red;\n  color: blue;\n}\n",
+    "a {\n  color: blue;\n}\n",
+    optimize()
+);
+test!(
+    removes_duplicate_declaration,
+    "a {\n  color: red;\n  margin: 0;\n  color: red;\n}\n",
+    "a {\n  margin: 0;\n  color: red;\n}\n",
+    optimize()
+);
+test!(
+    keeps_vendor_prefixed_fallback,
+    "a {\n  display: -webkit-box;\n  display: flex;\n}\n",
+    "a {\n  display: -webkit-box;\n  display: flex;\n}\n",
+    optimize()
+);
+test!(
+    keeps_function_fallback,
+    "a {\n  width: 10px;\n  width: calc(100% - 10px);\n}\n",
+    "a {\n  width: 10px;\n  width: calc(100% - 10px);\n}\n",
+    optimize()
+);
+test!(
+    keeps_important_declaration_before_normal_one,
+    "a {\n  color: red !important;\n  color: blue;\n}\n",
+    "a {\n  color: red !important;\n  color: blue;\n}\n",
+    optimize()
+);
+test!(
+    removes_declaration_overridden_by_important,
+    "a {\n  color: red;\n  color: blue !important;\n}\n",
+    "a {\n  color: blue !important;\n}\n",
+    optimize()
+);
+test!(
+    merges_adjacent_rules_with_same_selector,
+    "a {\n  color: red;\n}\na {\n  margin: 0;\n  color: blue;\n}\n",
+    "a {\n  margin: 0;\n  color: blue;\n}\n",
+    optimize()
+);
+test!(
+    merges_adjacent_rules_with_same_declarations,
+    "a {\n  color: red;\n}\nb {\n  color: red;\n}\n",
+    "a, b {\n  color: red;\n}\n",
+    optimize()
+);
+test!(
+    does_not_merge_non_adjacent_rules,
+    "a {\n  color: red;\n}\nb {\n  color: blue;\n}\na {\n  color: green;\n}\n",
+    "a {\n  color: red;\n}\n\nb {\n  color: blue;\n}\n\na {\n  color: green;\n}\n",
+    optimize()
+);
+test!(
+    does_not_merge_vendor_specific_selectors,
+    "::-moz-selection {\n  color: red;\n}\n::selection {\n  color: red;\n}\n",
+    "::-moz-selection {\n  color: red;\n}\n\n::selection {\n  color: red;\n}\n",
+    optimize()
+);
+test!(
+    does_not_merge_selectors_without_universal_support,
+    ".c {\n  x: 1;\n}\n.d {\n  x: 1;\n}\n.e:focus-visible {\n  x: 1;\n}\n",
+    ".c, .d {\n  x: 1;\n}\n\n.e:focus-visible {\n  x: 1;\n}\n",
+    optimize()
+);
+test!(
+    does_not_merge_selectors_with_selector_arguments,
+    "a {\n  x: 1;\n}\nb:is(.c) {\n  x: 1;\n}\n",
+    "a {\n  x: 1;\n}\n\nb:is(.c) {\n  x: 1;\n}\n",
+    optimize()
+);
+test!(
+    merges_selectors_with_universal_support,
+    "a:hover {\n  x: 1;\n}\n[b] > c::before {\n  x: 1;\n}\n#d li:nth-child(2n + 1) {\n  x: 1;\n}\n",
+    "a:hover, [b] > c::before, #d li:nth-child(2n+1) {\n  x: 1;\n}\n",
+    optimize()
+);
+test!(
+    merges_rules_within_media_rule,
+    "@media print {\n  a {\n    color: red;\n  }\n  a {\n    margin: 0;\n  }\n}\n",
+    "@media print {\n  a {\n    color: red;\n    margin: 0;\n  }\n}\n",
+    optimize()
+);
+test!(
+    shortens_zero_lengths,
+    "a {\n  top: 0px;\n  left: 0em;\n  width: 0%;\n  transition: 0s;\n}\n",
+    "a {\n  top: 0;\n  left: 0;\n  width: 0%;\n  transition: 0s;\n}\n",
+    optimize()
+);
+test!(
+    keeps_zero_flex_basis,
+    "a {\n  flex: 1 1 0px;\n}\n",
+    "a {\n  flex: 1 1 0px;\n}\n",
+    optimize()
+);
+test!(
+    collapses_box_shorthand,
+    "a {\n  margin: 0 0 0 0;\n  padding: 1px 2px 1px 2px;\n  border-width: 1px 2px 3px 2px;\n}\n",
+    "a {\n  margin: 0;\n  padding: 1px 2px;\n  border-width: 1px 2px 3px;\n}\n",
+    optimize()
+);
+test!(
+    collapses_important_box_shorthand,
+    "a {\n  margin: 0px 0px !important;\n}\n",
+    "a {\n  margin: 0 !important;\n}\n",
+    optimize()
+);
+test!(
+    does_not_collapse_equal_lengths_with_different_units,
+    "a {\n  margin: 1in 96px;\n}\n",
+    "a {\n  margin: 1in 96px;\n}\n",
+    optimize()
+);
+test!(
+    shortens_colors,
+    "a {\n  color: #FFFFFF;\n  background: rgb(255, 0, 0);\n  border-color: #aabbcc;\n}\n",
+    "a {\n  color: #fff;\n  background: red;\n  border-color: #abc;\n}\n",
+    optimize()
+);
+test!(
+    does_not_shorten_unquoted_strings,
+    "a {\n  grid-area: unquote(\"#AABBCC\");\n  color: unquote(\"#FFFFFF\");\n}\n",
+    "a {\n  grid-area: #AABBCC;\n  color: #FFFFFF;\n}\n",
+    optimize()
+);
+test!(
+    does_not_change_custom_properties,
+    "a {\n  --a: #ffffff 0px;\n  --a: red;\n}\n",
+    "a {\n  --a: red;\n}\n",
+    optimize()
+);
+test!(
+    removes_empty_rules,
+    "a {}\nb {\n  c: d;\n}\n@media print {\n  e {}\n}\n",
+    "b {\n  c: d;\n}\n",
+    optimize()
+);
+test!(
+    optimizes_compressed_output,
+    "a {\n  margin: 0px 0px;\n  color: #ffffff;\n}\nb {\n  margin: 0;\n  color: white;\n}\n",
+    "a,b{margin:0;color:#fff}",
+    optimize().style(grass::OutputStyle::Compressed)
+);
+test!(
+    does_not_optimize_by_default,
+    "a {\n  color: red;\n  color: blue;\n}\n",
+    "a {\n  color: red;\n  color: blue;\n}\n"
+);
@@ -0,0 +1,115 @@
+use grass::{Indent, LineEnding, OutputStyle};
+
+#[macro_use]
+mod macros;
+
+fn indent(indent: Indent) -> grass::Options<'static> {
+    grass::Options::default().indent(indent)
+}
+
+fn crlf() -> grass::Options<'static> {
+    grass::Options::default().line_ending(LineEnding::Crlf)
+}
+
+test!(
+    indent_with_four_spaces,
+    "@media screen {\n  a {\n    color: red;\n  }\n}\n",
+    "@media screen {\n    a {\n        color: red;\n    }\n}\n",
+    indent(Indent::Spaces(4))
+);
+test!(
+    indent_with_tabs,
+    "@media screen {\n  a {\n    color: red;\n  }\n}\n",
+    "@media screen {\n\ta {\n\t\tcolor: red;\n\t}\n}\n",
+    indent(Indent::Tabs)
+);
+test!(
+    indent_with_no_spaces,
+    "a {\n  color: red;\n}\n",
+    "a {\ncolor: red;\n}\n",
+    indent(Indent::Spaces(0))
+);
+test!(
+    indent_nested_style_with_tabs,
+    "a {\n  color: red;\n\n  b {\n    color: blue;\n  }\n}\n",
+    "a {\n\tcolor: red; }\n\ta b {\n\t\tcolor: blue; }\n",
+    indent(Indent::Tabs).style(OutputStyle::Nested)
+);
+test!(
+    indent_source_comments_with_tabs,
+    "@media screen {\n  a {\n    color: red;\n  }\n}\n",
+    "@media screen {\n\t/* line 2, stdin */\n\ta {\n\t\tcolor: red;\n\t}\n}\n",
+    indent(Indent::Tabs).source_comments(true)
+);
+test!(
+    indent_has_no_effect_on_compressed_output,
+    "a {\n  color: red;\n}\n",
+    "a{color:red}",
+    indent(Indent::Tabs).style(OutputStyle::Compressed)
+);
+test!(
+    crlf_line_endings,
+    "a {\n  color: red;\n}\n\nb {\n  color: blue;\n}\n",
+    "a {\r\n  color: red;\r\n}\r\n\r\nb {\r\n  color: blue;\r\n}\r\n",
+    crlf()
+);
+test!(
+    crlf_line_endings_within_comments,
+    "/* a\n   b */\n",
+    "/* a\r\n   b */\r\n",
+    crlf()
+);
+test!(
+    crlf_line_endings_are_not_doubled,
+    "/* a\r\n   b */\r\n",
+    "/* a\r\n   b */\r\n",
+    crlf()
+);
+test!(
+    crlf_line_endings_in_source_comments,
+    "a {\n  color: red;\n}\n",
+    "/* line 1, stdin */\r\na {\r\n  color: red;\r\n}\r\n",
+    crlf().source_comments(true)
+);
+test!(
+    crlf_line_endings_after_charset,
+    "a {\n  content: \"é\";\n}\n",
+    "@charset \"UTF-8\";\r\na {\r\n  content: \"é\";\r\n}\r\n",
+    crlf()
+);
+test!(
+    no_trailing_newline,
+    "a {\n  color: red;\n}\n",
+    "a {\n  color: red;\n}",
+    grass::Options::default().trailing_newline(false)
+);
+test!(
+    trailing_newline_in_compressed_output,
+    "a {\n  color: red;\n}\n",
+    "a{color:red}\n",
+    grass::Options::default()
+        .style(OutputStyle::Compressed)
+        .trailing_newline(true)
+);
+test!(
+    trailing_newline_with_crlf_line_endings,
+    "a {\n  color: red;\n}\n",
+    "a{color:red}\r\n",
+    crlf().style(OutputStyle::Compressed).trailing_newline(true)
+);
+test!(
+    no_trailing_newline_for_empty_output,
+    "",
+    "",
+    grass::Options::default().trailing_newline(true)
+);
+
+#[test]
+fn tree_uses_formatting_options() {
+    let options = indent(Indent::Tabs)
+        .line_ending(LineEnding::Crlf)
+        .trailing_newline(false);
+    let tree = grass::compile_to_tree("a {\n  color: red;\n}\n".to_owned(), &options).unwrap();
+
+    assert_eq!(tree.to_css(&options).unwrap(), "a {\r\n\tcolor: red;\r\n}");
+}
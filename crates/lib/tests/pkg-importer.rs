@@ -0,0 +1,98 @@
+//! The fixtures for these tests live in `tests/fixtures/pkg_importer`. Entry
+//! points are in the `src` directory, so resolution must walk up a directory
+//! to find `node_modules`.
+
+fn compile(name: &str) -> grass::Result<String> {
+    grass::from_path(
+        format!("tests/fixtures/pkg_importer/src/{}.scss", name),
+        &grass::Options::default().pkg_importer(grass::PkgImporter::Node),
+    )
+}
+
+fn error_message(name: &str) -> String {
+    match compile(name) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => e.to_string().chars().take_while(|c| *c != '\n').collect(),
+    }
+}
+
+#[test]
+fn scoped_package_exports_with_sass_condition() {
+    assert_eq!(
+        "a {\n  color: red;\n  background: blue;\n}\n",
+        compile("scoped").unwrap()
+    );
+}
+
+#[test]
+fn package_module_can_be_configured() {
+    assert_eq!("a {\n  color: black;\n}\n", compile("configured").unwrap());
+}
+
+#[test]
+fn sass_field_and_subpath_without_exports() {
+    assert_eq!(
+        ".btn {\n  color: green;\n}\n\na {\n  width: 4px;\n}\n",
+        compile("sass_field").unwrap()
+    );
+}
+
+#[test]
+fn webpack_style_tilde_import() {
+    assert_eq!("a {\n  width: 6px;\n}\n", compile("tilde").unwrap());
+}
+
+#[test]
+fn style_field_index_file_and_subpath_pattern() {
+    assert_eq!(
+        ".styled {\n  color: purple;\n}\n\n.plain {\n  color: orange;\n}\n\n.pattern-a {\n  color: teal;\n}\n",
+        compile("fields").unwrap()
+    );
+}
+
+#[test]
+fn export_resolves_to_non_sass_file() {
+    assert_eq!(
+        "Error: The export for 'root' in 'not-sass' resolved to 'tests/fixtures/pkg_importer/node_modules/not-sass/index.js', which is not a '.scss', '.sass', or '.css' file.",
+        error_message("not_sass")
+    );
+}
+
+#[test]
+fn missing_package() {
+    assert_eq!(
+        "Error: Can't find stylesheet to import.",
+        error_message("missing")
+    );
+}
+
+#[test]
+fn pkg_url_without_importer() {
+    match grass::from_path(
+        "tests/fixtures/pkg_importer/src/scoped.scss",
+        &grass::Options::default(),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert!(e
+            .to_string()
+            .starts_with("Error: Can't find stylesheet to import.\n")),
+    }
+}
+
+#[test]
+fn tilde_url_without_importer_is_relative() {
+    assert_eq!(
+        "Error: Can't find stylesheet to import.",
+        match grass::from_path(
+            "tests/fixtures/pkg_importer/src/tilde.scss",
+            &grass::Options::default(),
+        ) {
+            Ok(..) => panic!("did not fail"),
+            Err(e) => e
+                .to_string()
+                .chars()
+                .take_while(|c| *c != '\n')
+                .collect::<String>(),
+        }
+    );
+}
@@ -0,0 +1,97 @@
+#[macro_use]
+mod macros;
+
+const INPUT: &str = "@use 'sass:math';
+@use 'sass:string';
+a {
+  float: random();
+  int: random(100);
+  module-float: math.random();
+  module-int: math.random(100);
+  id: unique-id();
+  module-id: string.unique-id();
+}
+";
+
+fn seeded(seed: u64) -> grass::Options<'static> {
+    grass::Options::default().random_seed(seed)
+}
+
+fn compile(seed: u64) -> String {
+    grass::from_string(INPUT.to_owned(), &seeded(seed)).unwrap()
+}
+
+#[test]
+fn same_seed_produces_same_output() {
+    assert_eq!(compile(0), compile(0));
+    assert_eq!(compile(42), compile(42));
+}
+
+#[test]
+fn different_seeds_produce_different_output() {
+    assert_ne!(compile(1), compile(2));
+}
+
+#[test]
+fn seed_is_shared_across_modules() {
+    let mut fs = macros::TestFs::new();
+    fs.add_file("_a.scss", "a { id: unique-id(); }");
+    fs.add_file("_b.scss", "b { id: unique-id(); }");
+
+    let input = "@use 'a';\n@use 'b';\nc { id: unique-id(); }\n";
+    let compile = || grass::from_string(input.to_owned(), &seeded(7).fs(&fs)).unwrap();
+
+    let first = compile();
+    assert_eq!(first, compile());
+
+    let ids: Vec<&str> = first
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("id: "))
+        .collect();
+    assert_eq!(ids.len(), 3);
+    assert_ne!(ids[0], ids[1]);
+    assert_ne!(ids[1], ids[2]);
+}
+
+test!(
+    seeded_random_float_is_in_range,
+    "@for $_ from 0 to 100 {
+        $n: random();
+        @if $n < 0 or $n >= 1 or not unitless($n) {
+            @error 'out of range: #{$n}';
+        }
+    }",
+    "",
+    seeded(3)
+);
+test!(
+    seeded_random_int_is_in_range,
+    "@for $_ from 0 to 100 {
+        $n: random(5);
+        @if $n < 1 or $n > 5 or round($n) != $n {
+            @error 'out of range: #{$n}';
+        }
+    }",
+    "",
+    seeded(3)
+);
+test!(
+    seeded_unique_id_is_unique,
+    "$init: unique-id();
+    @for $_ from 0 to 100 {
+        @if $init == unique-id() {
+            @error 'got duplicate unique id: #{$init}';
+        }
+    }",
+    "",
+    seeded(3)
+);
+test!(
+    seeded_unique_id_format,
+    "$id: unique-id();
+    @if str-length($id) != 15 or str-slice($id, 1, 3) != 'id-' {
+        @error 'unexpected format: #{$id}';
+    }",
+    "",
+    seeded(3)
+);
@@ -0,0 +1,81 @@
+#[macro_use]
+mod macros;
+
+fn source_comments() -> grass::Options<'static> {
+    grass::Options::default().source_comments(true)
+}
+
+test!(
+    comment_above_style_rule,
+    "a {\n  color: red;\n}\n",
+    "/* line 1, stdin */\na {\n  color: red;\n}\n",
+    source_comments()
+);
+test!(
+    comment_points_to_nested_rule,
+    "a {\n  color: red;\n\n  b {\n    color: blue;\n  }\n}\n",
+    "/* line 1, stdin */\na {\n  color: red;\n}\n/* line 4, stdin */\na b {\n  color: blue;\n}\n",
+    source_comments()
+);
+test!(
+    comment_within_media_rule_is_indented,
+    "@media screen {\n  a {\n    color: red;\n  }\n}\n",
+    "@media screen {\n  /* line 2, stdin */\n  a {\n    color: red;\n  }\n}\n",
+    source_comments()
+);
+test!(
+    comment_for_extended_rule_points_to_original,
+    "a {\n  color: red;\n}\n\nb {\n  @extend a;\n}\n",
+    "/* line 1, stdin */\na, b {\n  color: red;\n}\n",
+    source_comments()
+);
+test!(
+    comment_in_nested_style,
+    "a {\n  color: red;\n  b {\n    color: blue;\n  }\n}\n",
+    "/* line 1, stdin */\na {\n  color: red; }\n  /* line 3, stdin */\n  a b {\n    color: blue; }\n",
+    source_comments().style(grass::OutputStyle::Nested)
+);
+test!(
+    no_comment_in_compressed_style,
+    "a {\n  color: red;\n}\n",
+    "a{color:red}",
+    source_comments().style(grass::OutputStyle::Compressed)
+);
+test!(
+    no_comment_by_default,
+    "a {\n  color: red;\n}\n",
+    "a {\n  color: red;\n}\n"
+);
+
+#[test]
+fn comment_path_is_relative_to_entrypoint() {
+    let mut fs = macros::TestFs::new();
+    fs.add_file(
+        "src/main.scss",
+        "@import 'components/button';\n\na {\n  color: red;\n}\n",
+    );
+    fs.add_file(
+        "src/components/_button.scss",
+        "\n.btn {\n  color: blue;\n}\n",
+    );
+
+    assert_eq!(
+        "/* line 2, components/_button.scss */\n.btn {\n  color: blue;\n}\n\n/* line 3, main.scss */\na {\n  color: red;\n}\n",
+        &grass::from_path("src/main.scss", &source_comments().fs(&fs)).unwrap()
+    );
+}
+
+#[test]
+fn comment_path_for_module() {
+    let mut fs = macros::TestFs::new();
+    fs.add_file("_a.scss", "a {\n  color: red;\n}\n");
+
+    assert_eq!(
+        "/* line 1, _a.scss */\na {\n  color: red;\n}\n\n/* line 2, stdin */\nb {\n  color: blue;\n}\n",
+        &grass::from_string(
+            "@use 'a';\nb {\n  color: blue;\n}\n".to_owned(),
+            &source_comments().fs(&fs)
+        )
+        .unwrap()
+    );
+}
@@ -308,3 +308,98 @@ test!(
     ",
     "a {\n  color: \"aaa\";\n}\n"
 );
+test!(
+    str_length_counts_code_points_of_emoji_modifier,
+    "a {\n  color: str-length(\"👍🏽\");\n}\n",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    str_length_counts_code_points_of_combining_sequence,
+    "a {\n  color: str-length(\"e\\0301\");\n}\n",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    str_slice_splits_zwj_sequence_by_code_point,
+    "a {\n  color: str-slice(\"👨‍👩‍👧\", 1, 3);\n}\n",
+    "@charset \"UTF-8\";\na {\n  color: \"👨‍👩\";\n}\n"
+);
+test!(
+    str_slice_negative_indices_count_code_points,
+    "a {\n  color: str-slice(\"a👍🏽b\", -3, -2);\n}\n",
+    "@charset \"UTF-8\";\na {\n  color: \"👍🏽\";\n}\n"
+);
+test!(
+    str_index_after_emoji,
+    "a {\n  color: str-index(\"👍🏽ab\", \"b\");\n}\n",
+    "a {\n  color: 4;\n}\n"
+);
+test!(
+    str_insert_after_emoji,
+    "a {\n  color: str-insert(\"👍🏽ab\", \"X\", 3);\n}\n",
+    "@charset \"UTF-8\";\na {\n  color: \"👍🏽Xab\";\n}\n"
+);
+test!(
+    str_insert_negative_index_after_emoji,
+    "a {\n  color: str-insert(\"a👍🏽\", \"X\", -2);\n}\n",
+    "@charset \"UTF-8\";\na {\n  color: \"a👍X🏽\";\n}\n"
+);
+test!(
+    split_font_stack,
+    "@use 'sass:string';\na {\n  color: string.split(\"Helvetica Neue, Arial, sans-serif\", \", \");\n}\n",
+    "a {\n  color: [\"Helvetica Neue\", \"Arial\", \"sans-serif\"];\n}\n"
+);
+test!(
+    split_unquoted,
+    "@use 'sass:string';\na {\n  color: string.split(a-b-c, \"-\");\n}\n",
+    "a {\n  color: [a, b, c];\n}\n"
+);
+test!(
+    split_with_limit,
+    "@use 'sass:string';\na {\n  color: string.split(a-b-c-d, \"-\", 2);\n}\n",
+    "a {\n  color: [a, b, c-d];\n}\n"
+);
+test!(
+    split_named_limit,
+    "@use 'sass:string';\na {\n  color: string.split(a-b-c-d, \"-\", $limit: 1);\n}\n",
+    "a {\n  color: [a, b-c-d];\n}\n"
+);
+test!(
+    split_separator_not_found,
+    "@use 'sass:string';\na {\n  color: string.split(abc, \",\");\n}\n",
+    "a {\n  color: [abc];\n}\n"
+);
+test!(
+    split_empty_string,
+    "@use 'sass:string';\na {\n  color: string.split(\"\", \",\");\n}\n",
+    "a {\n  color: [];\n}\n"
+);
+test!(
+    split_empty_separator_splits_code_points,
+    "@use 'sass:string';\na {\n  color: string.split(\"a👍🏽\", \"\");\n}\n",
+    "@charset \"UTF-8\";\na {\n  color: [\"a\", \"👍\", \"🏽\"];\n}\n"
+);
+test!(
+    split_leading_and_trailing_separators,
+    "@use 'sass:string';\na {\n  color: string.split(\",a,\", \",\");\n}\n",
+    "a {\n  color: [\"\", \"a\", \"\"];\n}\n"
+);
+test!(
+    split_returns_comma_list,
+    "@use 'sass:string';\n@use 'sass:list';\na {\n  color: list.separator(string.split(\"a b\", \" \"));\n}\n",
+    "a {\n  color: comma;\n}\n"
+);
+error!(
+    split_limit_zero,
+    "@use 'sass:string';\na {\n  color: string.split(abc, \",\", 0);\n}\n",
+    "Error: $limit: Must be 1 or greater, was 0."
+);
+error!(
+    split_limit_not_int,
+    "@use 'sass:string';\na {\n  color: string.split(abc, \",\", 1.5);\n}\n",
+    "Error: $limit: 1.5 is not an int."
+);
+error!(
+    split_separator_not_string,
+    "@use 'sass:string';\na {\n  color: string.split(abc, 1);\n}\n",
+    "Error: $separator: 1 is not a string."
+);
@@ -232,9 +232,46 @@ error!(
     "a {\n  color: 1X + 1dppx;\n}\n", "Error: Incompatible units dppx and X."
 );
 error!(
-    // note: dart-sass has error "Error: 1x and 1dppx have incompatible units."
-    lowercase_x_is_not_alias_for_dppx,
-    "a {\n  color: 1x + 1dppx;\n}\n", "Error: Incompatible units dppx and x."
+    container_unit_is_not_compatible_with_px,
+    "a {\n  color: 1cqw + 1px;\n}\n", "Error: Incompatible units px and cqw."
+);
+error!(
+    dynamic_viewport_unit_is_not_compatible_with_vh,
+    "a {\n  color: 1dvh + 1vh;\n}\n", "Error: Incompatible units vh and dvh."
+);
+error!(
+    container_unit_is_not_compatible_with_time_in_calc,
+    "a {\n  color: calc(1cqi + 1s);\n}\n", "Error: 1cqi and 1s are incompatible."
+);
+test!(
+    container_units_in_calc_are_not_simplified,
+    "a {\n  color: calc(1cqi + 1px);\n}\n",
+    "a {\n  color: calc(1cqi + 1px);\n}\n"
+);
+test!(
+    math_compatible_x_and_dppx,
+    "@use \"sass:math\";\na {\n  color: math.compatible(1x, 1dppx);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    math_compatible_x_and_dpi,
+    "@use \"sass:math\";\na {\n  color: math.compatible(1x, 1dpi);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    math_compatible_different_container_units,
+    "@use \"sass:math\";\na {\n  color: math.compatible(1cqw, 1cqh);\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    math_compatible_same_viewport_unit,
+    "@use \"sass:math\";\na {\n  color: math.compatible(1svh, 2svh);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    math_compatible_root_font_unit_and_font_unit,
+    "@use \"sass:math\";\na {\n  color: math.compatible(1rex, 1ex);\n}\n",
+    "a {\n  color: false;\n}\n"
 );
 error!(
     display_single_div_with_non_comparable_numerator,
@@ -279,6 +316,34 @@ test!(
         color: 1vmax;
         color: 1vi;
         color: 1vb;
+        color: 1rcap;
+        color: 1rch;
+        color: 1rex;
+        color: 1ric;
+        color: 1svw;
+        color: 1svh;
+        color: 1svi;
+        color: 1svb;
+        color: 1svmin;
+        color: 1svmax;
+        color: 1lvw;
+        color: 1lvh;
+        color: 1lvi;
+        color: 1lvb;
+        color: 1lvmin;
+        color: 1lvmax;
+        color: 1dvw;
+        color: 1dvh;
+        color: 1dvi;
+        color: 1dvb;
+        color: 1dvmin;
+        color: 1dvmax;
+        color: 1cqw;
+        color: 1cqh;
+        color: 1cqi;
+        color: 1cqb;
+        color: 1cqmin;
+        color: 1cqmax;
         color: 1deg;
         color: 1grad;
         color: 1rad;
@@ -290,9 +355,10 @@ test!(
         color: 1dpi;
         color: 1dpcm;
         color: 1dppx;
+        color: 1x;
         color: 1fr;
         color: 1foo;
-    }", "a {\n  color: 1px;\n  color: 1mm;\n  color: 1in;\n  color: 1cm;\n  color: 1q;\n  color: 1pt;\n  color: 1pc;\n  color: 1em;\n  color: 1rem;\n  color: 1lh;\n  color: 1%;\n  color: 1ex;\n  color: 1ch;\n  color: 1cap;\n  color: 1ic;\n  color: 1rlh;\n  color: 1vw;\n  color: 1vh;\n  color: 1vmin;\n  color: 1vmax;\n  color: 1vi;\n  color: 1vb;\n  color: 1deg;\n  color: 1grad;\n  color: 1rad;\n  color: 1turn;\n  color: 1s;\n  color: 1ms;\n  color: 1Hz;\n  color: 1kHz;\n  color: 1dpi;\n  color: 1dpcm;\n  color: 1dppx;\n  color: 1fr;\n  color: 1foo;\n}\n"
+    }", "a {\n  color: 1px;\n  color: 1mm;\n  color: 1in;\n  color: 1cm;\n  color: 1q;\n  color: 1pt;\n  color: 1pc;\n  color: 1em;\n  color: 1rem;\n  color: 1lh;\n  color: 1%;\n  color: 1ex;\n  color: 1ch;\n  color: 1cap;\n  color: 1ic;\n  color: 1rlh;\n  color: 1vw;\n  color: 1vh;\n  color: 1vmin;\n  color: 1vmax;\n  color: 1vi;\n  color: 1vb;\n  color: 1rcap;\n  color: 1rch;\n  color: 1rex;\n  color: 1ric;\n  color: 1svw;\n  color: 1svh;\n  color: 1svi;\n  color: 1svb;\n  color: 1svmin;\n  color: 1svmax;\n  color: 1lvw;\n  color: 1lvh;\n  color: 1lvi;\n  color: 1lvb;\n  color: 1lvmin;\n  color: 1lvmax;\n  color: 1dvw;\n  color: 1dvh;\n  color: 1dvi;\n  color: 1dvb;\n  color: 1dvmin;\n  color: 1dvmax;\n  color: 1cqw;\n  color: 1cqh;\n  color: 1cqi;\n  color: 1cqb;\n  color: 1cqmin;\n  color: 1cqmax;\n  color: 1deg;\n  color: 1grad;\n  color: 1rad;\n  color: 1turn;\n  color: 1s;\n  color: 1ms;\n  color: 1Hz;\n  color: 1kHz;\n  color: 1dpi;\n  color: 1dpcm;\n  color: 1dppx;\n  color: 1x;\n  color: 1fr;\n  color: 1foo;\n}\n"
 );
 
 macro_rules! test_unit_addition {
@@ -406,3 +472,15 @@ test_unit_addition!(dpcm, dppx, "38.7952755906");
 test_unit_addition!(dppx, dpi, "1.0104166667");
 test_unit_addition!(dppx, dpcm, "1.0264583333");
 test_unit_addition!(dppx, dppx, "2");
+test_unit_addition!(dppx, x, "2");
+
+test_unit_addition!(x, dpi, "1.0104166667");
+test_unit_addition!(x, dpcm, "1.0264583333");
+test_unit_addition!(x, dppx, "2");
+test_unit_addition!(x, x, "2");
+
+test_unit_addition!(dpi, x, "97");
+
+test_unit_addition!(cqw, cqw, "2");
+test_unit_addition!(dvh, dvh, "2");
+test_unit_addition!(rcap, rcap, "2");
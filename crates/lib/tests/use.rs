@@ -765,7 +765,6 @@ fn import_module_using_same_builtin_module_has_styles() {
 }
 
 #[test]
-#[ignore = "we don't hermetically evaluate @extend"]
 fn use_module_with_extend() {
     let mut fs = TestFs::new();
 
@@ -792,4 +791,375 @@ fn use_module_with_extend() {
     );
 }
 
+#[test]
+fn use_module_loop() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"@use "b";"#);
+    fs.add_file("_b.scss", r#"@use "a";"#);
+
+    let input = r#"
+        @use "a";
+    "#;
+
+    assert_err!(
+        input,
+        "Error: Module loop: this module is already being loaded.",
+        grass::Options::default().fs(&fs)
+    );
+}
+
+#[test]
+fn use_module_loop_lists_load_chain() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"@use "b";"#);
+    fs.add_file("_b.scss", r#"@use "a";"#);
+
+    let input = r#"
+        @use "a";
+    "#;
+
+    match grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert!(
+            e.to_string().starts_with(
+                "Error: Module loop: this module is already being loaded.\n\nLoad chain:\n  _a.scss\n  _b.scss\n  _a.scss\n"
+            ),
+            "{}",
+            e
+        ),
+    }
+}
+
+#[test]
+fn use_self_module_loop() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"@use "a";"#);
+
+    let input = r#"
+        @use "a";
+    "#;
+
+    assert_err!(
+        input,
+        "Error: Module loop: this module is already being loaded.",
+        grass::Options::default().fs(&fs)
+    );
+}
+
+#[test]
+fn forward_module_loop() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"@forward "b";"#);
+    fs.add_file("_b.scss", r#"@use "a";"#);
+
+    let input = r#"
+        @use "a";
+    "#;
+
+    assert_err!(
+        input,
+        "Error: Module loop: this module is already being loaded.",
+        grass::Options::default().fs(&fs)
+    );
+}
+
+#[test]
+fn use_same_module_twice_is_not_a_loop() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"$a: red;"#);
+    fs.add_file("_b.scss", r#"@use "a"; $b: a.$a;"#);
+
+    let input = r#"
+        @use "a";
+        @use "b";
+        a {
+            color: a.$a;
+            color: b.$b;
+        }
+    "#;
+
+    assert_eq!(
+        "a {\n  color: red;\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn use_module_css_emitted_once() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"a { color: red; }"#);
+    fs.add_file("_b.scss", r#"@use "a"; b { color: blue; }"#);
+
+    let input = r#"
+        @use "a";
+        @use "b";
+    "#;
+
+    assert_eq!(
+        "a {\n  color: red;\n}\n\nb {\n  color: blue;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn use_configured_module_shared_with_dependents() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"$x: 1 !default;"#);
+    fs.add_file("_b.scss", r#"@use "a"; b { c: a.$x; }"#);
+
+    let input = r#"
+        @use "a" with ($x: 2);
+        @use "b";
+    "#;
+
+    assert_eq!(
+        "b {\n  c: 2;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn use_already_loaded_module_with_configuration() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"$x: 1 !default;"#);
+    fs.add_file("_b.scss", r#"@use "a";"#);
+
+    let input = r#"
+        @use "b";
+        @use "a" with ($x: 2);
+    "#;
+
+    assert_err!(
+        input,
+        "Error: This module was already loaded, so it can't be configured using \"with\".",
+        grass::Options::default().fs(&fs)
+    );
+}
+
+#[test]
+fn use_module_extended_by_downstream_module() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"a { color: red; }"#);
+    fs.add_file("_b.scss", r#"@use "a"; b { @extend a; }"#);
+
+    let input = r#"
+        @use "b";
+    "#;
+
+    assert_eq!(
+        "a, b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn use_module_extended_transitively_by_entrypoint() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"a { color: red; }"#);
+    fs.add_file("_b.scss", r#"@use "a"; b { @extend a; }"#);
+
+    let input = r#"
+        @use "b";
+        c { @extend a; }
+    "#;
+
+    assert_eq!(
+        "a, b, c {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn use_extend_does_not_apply_to_sibling_module() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"a { color: red; }"#);
+    fs.add_file("_b.scss", r#"b { @extend a !optional; }"#);
+
+    let input = r#"
+        @use "a";
+        @use "b";
+    "#;
+
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn use_extend_does_not_apply_downstream() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"a { @extend b !optional; color: red; }"#);
+
+    let input = r#"
+        @use "a";
+        b { color: blue; }
+    "#;
+
+    assert_eq!(
+        "a {\n  color: red;\n}\n\nb {\n  color: blue;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn use_extend_private_placeholder_across_modules() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"%-a { color: red; } %b { color: blue; }"#);
+
+    let input = r#"
+        @use "a";
+        c { @extend %-a !optional; @extend %b; }
+    "#;
+
+    assert_eq!(
+        "c {\n  color: blue;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn use_extend_through_forward() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"a { color: red; }"#);
+    fs.add_file("_b.scss", r#"@forward "a";"#);
+
+    let input = r#"
+        @use "b";
+        c { @extend a; }
+    "#;
+
+    assert_eq!(
+        "a, c {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn use_shared_module_extended_by_multiple_modules() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"a { color: red; }"#);
+    fs.add_file("_b.scss", r#"@use "a"; b { @extend a; }"#);
+    fs.add_file("_c.scss", r#"@use "a"; c { @extend a; }"#);
+
+    let input = r#"
+        @use "b";
+        @use "c";
+    "#;
+
+    assert_eq!(
+        "a, c, b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn use_module_css_emitted_before_dependents() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"a { color: red; }"#);
+    fs.add_file("_b.scss", r#"@use "a"; b { color: red; }"#);
+    fs.add_file("_c.scss", r#"@use "b"; @use "a"; c { color: red; }"#);
+
+    let input = r#"
+        @use "c";
+        @use "b";
+        d { color: red; }
+    "#;
+
+    assert_eq!(
+        "a {\n  color: red;\n}\n\nb {\n  color: red;\n}\n\nc {\n  color: red;\n}\n\nd {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn import_file_using_module_extended_by_importer() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"a { color: red; }"#);
+    fs.add_file("_b.scss", r#"@use "a"; b { color: blue; }"#);
+
+    let input = r#"
+        @import "b";
+        c { @extend a; }
+    "#;
+
+    assert_eq!(
+        "a, c {\n  color: red;\n}\n\nb {\n  color: blue;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn nested_import_of_file_using_module() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"$x: red;"#);
+    fs.add_file("_b.scss", r#"@use "a"; b { color: a.$x; }"#);
+
+    let input = r#"
+        c {
+            @import "b";
+        }
+    "#;
+
+    assert_eq!(
+        "c b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default().fs(&fs)).expect(input)
+    );
+}
+
+#[test]
+fn use_extend_private_placeholder_across_modules_is_not_found() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"%-a { color: red; }"#);
+
+    let input = r#"
+        @use "a";
+        c { @extend %-a; }
+    "#;
+
+    assert_err!(
+        input,
+        "Error: The target selector was not found.",
+        grass::Options::default().fs(&fs)
+    );
+}
+
+#[test]
+fn use_extend_target_only_in_downstream_module_is_not_found() {
+    let mut fs = TestFs::new();
+
+    fs.add_file("_a.scss", r#"a { @extend b; }"#);
+    fs.add_file("_b.scss", r#"b { color: red; }"#);
+
+    let input = r#"
+        @use "a";
+        @use "b";
+    "#;
+
+    assert_err!(
+        input,
+        "Error: The target selector was not found.",
+        grass::Options::default().fs(&fs)
+    );
+}
+
 // todo: refactor these tests to use testfs where possible